@@ -189,5 +189,12 @@ fn main() {
     #[cfg(debug_assertions)]
     console_error_panic_hook::set_once();
 
-    sycamore::render_to(App, &mount_el);
+    sycamore::render_to(
+        || {
+            // Use a single listener per event type instead of two listeners for every row.
+            sycamore::web::delegate_events();
+            App()
+        },
+        &mount_el,
+    );
 }
@@ -11,19 +11,14 @@ pub struct Todo {
     id: Uuid,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Filter {
+    #[default]
     All,
     Active,
     Completed,
 }
 
-impl Default for Filter {
-    fn default() -> Self {
-        Self::All
-    }
-}
-
 impl Filter {
     fn url(self) -> &'static str {
         match self {
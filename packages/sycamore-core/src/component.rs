@@ -1,6 +1,7 @@
 //! Utilities for components and component properties.
 
 use std::fmt;
+use std::rc::Rc;
 
 use sycamore_reactive::*;
 
@@ -118,7 +119,11 @@ pub fn element_like_component_builder<T: Props, V, S>(_f: &impl Component<T, V,
 /// # }
 /// ```
 pub struct Children<V> {
-    f: Box<dyn FnOnce() -> V>,
+    f: ChildrenInner<V>,
+}
+enum ChildrenInner<V> {
+    Once(Box<dyn FnOnce() -> V>),
+    Fn(ChildrenFn<V>),
 }
 impl<V> fmt::Debug for Children<V> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -131,26 +136,81 @@ where
     F: FnOnce() -> V + 'static,
 {
     fn from(f: F) -> Self {
-        Self { f: Box::new(f) }
+        Self::new(f)
     }
 }
 
-impl<V: Default + 'static> Default for Children<V> {
-    fn default() -> Self {
+impl<V> From<ChildrenFn<V>> for Children<V> {
+    fn from(f: ChildrenFn<V>) -> Self {
         Self {
-            f: Box::new(V::default),
+            f: ChildrenInner::Fn(f),
         }
     }
 }
 
+impl<V: Default + 'static> Default for Children<V> {
+    fn default() -> Self {
+        Self::new(V::default)
+    }
+}
+
 impl<V> Children<V> {
     /// Instantiates the child view.
     pub fn call(self) -> V {
-        (self.f)()
+        match self.f {
+            ChildrenInner::Once(f) => f(),
+            ChildrenInner::Fn(f) => f.call(),
+        }
     }
 
     /// Create a new [`Children`] from a closure.
     pub fn new(f: impl FnOnce() -> V + 'static) -> Self {
-        Self { f: Box::new(f) }
+        Self {
+            f: ChildrenInner::Once(Box::new(f)),
+        }
+    }
+
+    /// Returns a [`ChildrenFn`] that can instantiate the child view more than once, if these
+    /// children were created from one. Otherwise, the children are returned unchanged.
+    pub fn into_fn(self) -> Result<ChildrenFn<V>, Self> {
+        match self.f {
+            ChildrenInner::Fn(f) => Ok(f),
+            f => Err(Self { f }),
+        }
+    }
+}
+
+/// Like [`Children`], but the child view can be instantiated more than once.
+///
+/// Use this instead of [`Children`] for props of components that need to create a view again, e.g.
+/// after the view was disposed. Unlike [`Children`], the closure must not move out of the values
+/// that it captures.
+#[derive(Clone)]
+pub struct ChildrenFn<V> {
+    f: Rc<dyn Fn() -> V>,
+}
+impl<V> fmt::Debug for ChildrenFn<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChildrenFn").finish()
+    }
+}
+
+impl<V: Default + 'static> Default for ChildrenFn<V> {
+    fn default() -> Self {
+        Self {
+            f: Rc::new(V::default),
+        }
+    }
+}
+
+impl<V> ChildrenFn<V> {
+    /// Instantiates the child view. Every call creates a new view.
+    pub fn call(&self) -> V {
+        (self.f)()
+    }
+
+    /// Create a new [`ChildrenFn`] from a closure.
+    pub fn new(f: impl Fn() -> V + 'static) -> Self {
+        Self { f: Rc::new(f) }
     }
 }
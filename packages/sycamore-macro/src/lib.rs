@@ -9,6 +9,7 @@ use syn::{parse_macro_input, DeriveInput};
 
 mod component;
 mod props;
+mod store;
 mod view;
 
 /// A macro for ergonomically creating complex UI complex layouts.
@@ -55,6 +56,47 @@ pub fn derive_props(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// The derive macro for `Store`. The macro generates a store type with a `Signal` for every field,
+/// named after the struct with a `Store` suffix.
+///
+/// Fields marked with `#[store(nested)]` must also derive `Store` and are split recursively. All
+/// the other fields must implement `Clone`.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// #[derive(Store, Clone, Debug, PartialEq)]
+/// struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// #[derive(Store, Clone, Debug, PartialEq)]
+/// struct AppState {
+///     #[store(nested)]
+///     user: User,
+///     count: i32,
+/// }
+///
+/// # let _ = create_root(|| {
+/// let store = create_store(AppState {
+///     user: User { name: "Alice".to_string(), age: 30 },
+///     count: 0,
+/// });
+/// // Only effects that depend on the name of the user are re-run.
+/// store.user.name.set("Bob".to_string());
+/// assert_eq!(store.get_clone().user.name, "Bob");
+/// # });
+/// ```
+#[proc_macro_derive(Store, attributes(store))]
+pub fn derive_store(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    store::impl_derive_store(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 /// A macro for feature gating code that should only be run on the server.
 ///
 /// By default, the target is used to determine the rendering mode. However, `--cfg
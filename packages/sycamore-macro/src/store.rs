@@ -0,0 +1,179 @@
+//! The `Store` derive macro implementation.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{parse_quote, DeriveInput, Error, Field, GenericParam, Result};
+
+pub fn impl_derive_store(ast: &DeriveInput) -> Result<TokenStream> {
+    let fields = match &ast.data {
+        syn::Data::Struct(data) => match &data.fields {
+            syn::Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    ast.span(),
+                    "Store is only supported for structs with named fields",
+                ))
+            }
+        },
+        syn::Data::Enum(_) => {
+            return Err(Error::new(ast.span(), "Store is not supported for enums"))
+        }
+        syn::Data::Union(_) => {
+            return Err(Error::new(ast.span(), "Store is not supported for unions"))
+        }
+    };
+
+    // Signals can only hold `'static` values.
+    let mut generics = ast.generics.clone();
+    for param in &ast.generics.params {
+        match param {
+            GenericParam::Type(ty) => {
+                let ident = &ty.ident;
+                generics
+                    .make_where_clause()
+                    .predicates
+                    .push(parse_quote!(#ident: 'static));
+            }
+            GenericParam::Lifetime(lifetime) => {
+                return Err(Error::new(
+                    lifetime.span(),
+                    "Store is not supported for structs with lifetime parameters",
+                ))
+            }
+            GenericParam::Const(_) => {}
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let vis = &ast.vis;
+    let name = &ast.ident;
+    let store_name = format_ident!("{name}Store");
+
+    let fields = fields
+        .iter()
+        .map(|field| Ok((field, is_nested(field)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let store_fields = fields.iter().map(|(field, nested)| {
+        let Field { vis, ident, ty, .. } = field;
+        let docs = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"));
+        let ty = if *nested {
+            quote! { <#ty as ::sycamore::rt::Store>::Store }
+        } else {
+            quote! { ::sycamore::rt::Signal<#ty> }
+        };
+        quote! {
+            #(#docs)*
+            #vis #ident: #ty,
+        }
+    });
+    let into_store = fields.iter().map(|(Field { ident, ty, .. }, nested)| {
+        if *nested {
+            quote! { #ident: <#ty as ::sycamore::rt::Store>::into_store(self.#ident) }
+        } else {
+            quote! { #ident: ::sycamore::rt::create_signal(self.#ident) }
+        }
+    });
+    let get_clone = |method: TokenStream| {
+        let fields = fields.iter().map(|(Field { ident, ty, .. }, nested)| {
+            if *nested {
+                quote! { #ident: <#ty as ::sycamore::rt::Store>::#method(__store.#ident) }
+            } else {
+                quote! { #ident: __store.#ident.#method() }
+            }
+        });
+        quote! { Self { #(#fields),* } }
+    };
+    let get_clone_tracked = get_clone(quote!(get_clone));
+    let get_clone_untracked = get_clone(quote!(get_clone_untracked));
+    let set = fields.iter().map(|(Field { ident, ty, .. }, nested)| {
+        if *nested {
+            quote! { <#ty as ::sycamore::rt::Store>::set(__store.#ident, __value.#ident); }
+        } else {
+            quote! { __store.#ident.set(__value.#ident); }
+        }
+    });
+
+    let store_doc = format!(
+        "The store of [`{name}`], with a signal for every field. Created using \
+         `create_store`."
+    );
+
+    Ok(quote! {
+        #[doc = #store_doc]
+        #vis struct #store_name #impl_generics #where_clause {
+            #(#store_fields)*
+        }
+
+        impl #impl_generics ::std::clone::Clone for #store_name #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl #impl_generics ::std::marker::Copy for #store_name #ty_generics #where_clause {}
+
+        impl #impl_generics #store_name #ty_generics #where_clause {
+            /// Returns a snapshot of all the fields of the store. The fields are tracked.
+            #vis fn get_clone(self) -> #name #ty_generics {
+                <#name #ty_generics as ::sycamore::rt::Store>::get_clone(self)
+            }
+
+            /// Returns a snapshot of all the fields of the store without tracking them.
+            #vis fn get_clone_untracked(self) -> #name #ty_generics {
+                <#name #ty_generics as ::sycamore::rt::Store>::get_clone_untracked(self)
+            }
+
+            /// Sets all the fields of the store in a single batch.
+            #vis fn set(self, value: #name #ty_generics) {
+                <#name #ty_generics as ::sycamore::rt::Store>::set(self, value)
+            }
+        }
+
+        impl #impl_generics ::sycamore::rt::Store for #name #ty_generics #where_clause {
+            type Store = #store_name #ty_generics;
+
+            fn into_store(self) -> Self::Store {
+                #store_name { #(#into_store),* }
+            }
+
+            fn get_clone(__store: Self::Store) -> Self {
+                #get_clone_tracked
+            }
+
+            fn get_clone_untracked(__store: Self::Store) -> Self {
+                #get_clone_untracked
+            }
+
+            fn set(__store: Self::Store, __value: Self) {
+                ::sycamore::rt::batch(move || {
+                    #(#set)*
+                });
+            }
+        }
+    })
+}
+
+/// Returns whether the field is marked with `#[store(nested)]`.
+fn is_nested(field: &Field) -> Result<bool> {
+    let mut nested = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("store"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("nested") {
+                nested = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown store attribute, expected `nested`"))
+            }
+        })?;
+    }
+    Ok(nested)
+}
@@ -4,9 +4,9 @@
 //! additional information to the codegen such as which mode (Client, Hydrate, SSR), etc...
 
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use sycamore_view_parser::ir::{DynNode, Node, Prop, PropType, Root, TagIdent, TagNode, TextNode};
-use syn::{Expr, Pat};
+use syn::{Expr, Pat, Path};
 
 pub struct Codegen {
     // TODO: configure mode: Client, Hydrate, SSR
@@ -65,7 +65,15 @@ impl Codegen {
             children,
         } = element;
 
-        let attributes = props.iter().map(|attr| self.attribute(attr));
+        // `class:` and `style:` directives are applied after the other attributes so that they are
+        // not overwritten by a static `class` or `style` attribute.
+        let (directives, plain): (Vec<_>, Vec<_>) = props
+            .iter()
+            .partition(|attr| is_class_or_style_directive(&attr.ty));
+        let attributes = plain
+            .into_iter()
+            .chain(directives)
+            .map(|attr| self.attribute(attr));
 
         let children = children
             .0
@@ -109,12 +117,49 @@ impl Codegen {
                 quote! { .attr(#ident, #dyn_value) }
             }
             PropType::Directive { dir, ident } => match dir.to_string().as_str() {
-                "on" => quote! { .on(::sycamore::rt::events::#ident, #value) },
+                // Built-in events take precedence. Otherwise, the descriptor of a custom event
+                // declared with `custom_event!` is looked up in the current scope.
+                "on" => quote! {
+                    .on({
+                        #[allow(unused_imports)]
+                        use ::sycamore::rt::events::*;
+                        #ident
+                    }, #value)
+                },
                 "prop" => {
                     let ident = ident.to_string();
                     quote! { .prop(#ident, #dyn_value) }
                 }
                 "bind" => quote! { .bind(::sycamore::rt::bind::#ident, #value) },
+                // Actions are plain functions, so they are looked up in the current scope.
+                "use" => quote! { .r#use(#ident, #dyn_value) },
+                "class" => {
+                    let ident = ident.to_string();
+                    quote! { .class_toggle(#ident, #dyn_value) }
+                }
+                "style" => {
+                    let ident = ident.to_string();
+                    quote! { .style_property(#ident, #dyn_value) }
+                }
+                _ => syn::Error::new(dir.span(), format!("unknown directive `{dir}`"))
+                    .to_compile_error(),
+            },
+            PropType::DirectiveHyphenated { dir, ident } => match dir.to_string().as_str() {
+                // Hyphenated events are custom events that are declared by the user with
+                // `custom_event!`, so the descriptor is looked up in the current scope. The
+                // identifier of the descriptor is checked to match the event name by the macro.
+                "on" => {
+                    let descriptor =
+                        format_ident!("{}", ident.replace('-', "_"), span = dir.span());
+                    quote! { .on(#descriptor, #value) }
+                }
+                "prop" => quote! { .prop(#ident, #dyn_value) },
+                "bind" => syn::Error::new(dir.span(), format!("unknown binding `{ident}`"))
+                    .to_compile_error(),
+                "use" => syn::Error::new(dir.span(), format!("invalid action name `{ident}`"))
+                    .to_compile_error(),
+                "class" => quote! { .class_toggle(#ident, #dyn_value) },
+                "style" => quote! { .style_property(#ident, #dyn_value) },
                 _ => syn::Error::new(dir.span(), format!("unknown directive `{dir}`"))
                     .to_compile_error(),
             },
@@ -154,6 +199,17 @@ impl Codegen {
 
         let children_quoted = if children.0.is_empty() {
             quote! {}
+        } else if is_error_boundary(ident) {
+            // The children of an `ErrorBoundary` are created again when the boundary is reset.
+            let codegen = Codegen {};
+            let children = codegen.root(children);
+            quote! {
+                .children(
+                    ::sycamore::rt::Children::from(::sycamore::rt::ChildrenFn::new(move || {
+                        #children
+                    }))
+                )
+            }
         } else {
             let codegen = Codegen {};
             let children = codegen.root(children);
@@ -167,18 +223,35 @@ impl Codegen {
         };
         quote! {{
             let __component = &#ident; // We do this to make sure the compiler can infer the value for `<G>`.
-            ::sycamore::rt::component_scope(move || ::sycamore::rt::Component::create(
-                __component,
-                ::sycamore::rt::element_like_component_builder(__component)
-                    #(.#plain_names(#plain_values))*
-                    #(#other_attributes)*
-                    #children_quoted
-                    .build()
-            ))
+            ::std::convert::Into::<::sycamore::rt::View>::into(
+                ::sycamore::rt::component_scope(move || ::sycamore::rt::Component::create(
+                    __component,
+                    ::sycamore::rt::element_like_component_builder(__component)
+                        #(.#plain_names(#plain_values))*
+                        #(#other_attributes)*
+                        #children_quoted
+                        .build()
+                ))
+            )
         }}
     }
 }
 
+fn is_class_or_style_directive(ty: &PropType) -> bool {
+    match ty {
+        PropType::Directive { dir, .. } | PropType::DirectiveHyphenated { dir, .. } => {
+            dir == "class" || dir == "style"
+        }
+        _ => false,
+    }
+}
+
+fn is_error_boundary(path: &Path) -> bool {
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == "ErrorBoundary")
+}
+
 fn is_component(ident: &TagIdent) -> bool {
     match ident {
         TagIdent::Path(path) => {
@@ -283,9 +356,9 @@ fn is_dyn_pattern(pat: &Pat) -> bool {
 fn is_dyn_macro(m: &syn::Macro) -> bool {
     // Bodies of nested inner view! macros will be checked for dynamic
     // parts when their own codegen is run.
-    !m.path
+    m.path
         .get_ident()
-        .is_some_and(|ident| "view" == &ident.to_string())
+        .is_none_or(|ident| "view" != &ident.to_string())
 }
 
 fn is_dyn_block(block: &syn::Block) -> bool {
@@ -0,0 +1,20 @@
+use sycamore::prelude::*;
+
+#[derive(Store)]
+enum NotAStruct {}
+
+#[derive(Store)]
+struct Tuple(i32);
+
+#[derive(Store)]
+struct Borrowed<'a> {
+    value: &'a str,
+}
+
+#[derive(Store)]
+struct UnknownAttribute {
+    #[store(flatten)]
+    value: i32,
+}
+
+fn main() {}
@@ -0,0 +1,93 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use sycamore::prelude::*;
+
+#[derive(Store, Clone, Debug, PartialEq)]
+pub struct User {
+    pub name: String,
+    pub age: u32,
+}
+
+#[derive(Store, Clone, Debug, PartialEq)]
+pub struct AppState {
+    /// The current user.
+    #[store(nested)]
+    pub user: User,
+    pub count: i32,
+}
+
+#[derive(Store, Clone)]
+struct Generic<T: Clone> {
+    value: T,
+}
+
+#[component(inline_props)]
+fn Counter(#[prop(setter(into))] count: MaybeDyn<i32>) -> View {
+    view! { (count.get()) }
+}
+
+fn main() {
+    let _ = create_root(|| {
+        let store = create_store(AppState {
+            user: User {
+                name: "Alice".to_string(),
+                age: 30,
+            },
+            count: 0,
+        });
+
+        let name_runs = Rc::new(Cell::new(0));
+        create_effect({
+            let name_runs = name_runs.clone();
+            move || {
+                store.user.name.track();
+                name_runs.set(name_runs.get() + 1);
+            }
+        });
+        let all_runs = Rc::new(Cell::new(0));
+        create_effect({
+            let all_runs = all_runs.clone();
+            move || {
+                let _ = store.get_clone();
+                all_runs.set(all_runs.get() + 1);
+            }
+        });
+        assert_eq!((name_runs.get(), all_runs.get()), (1, 1));
+
+        // Updating a field only re-runs the effects that depend on it.
+        store.count.set(1);
+        store.user.age.set(31);
+        assert_eq!((name_runs.get(), all_runs.get()), (1, 3));
+        store.user.name.set("Bob".to_string());
+        assert_eq!((name_runs.get(), all_runs.get()), (2, 4));
+
+        assert_eq!(
+            store.get_clone_untracked(),
+            AppState {
+                user: User {
+                    name: "Bob".to_string(),
+                    age: 31,
+                },
+                count: 1,
+            }
+        );
+
+        // Setting the whole store is batched.
+        store.set(AppState {
+            user: User {
+                name: "Carol".to_string(),
+                age: 40,
+            },
+            count: 2,
+        });
+        assert_eq!((name_runs.get(), all_runs.get()), (3, 5));
+        assert_eq!(store.user.get_clone().name, "Carol");
+
+        let _: View = view! { Counter(count=store.count) };
+
+        let generic = create_store(Generic { value: 1u8 });
+        generic.value.set(2);
+        assert_eq!(generic.get_clone().value, 2);
+    });
+}
@@ -17,3 +17,13 @@ fn component_ui() {
         t.compile_fail("tests/component/*-fail.rs");
     }
 }
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn store_ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/store/*-pass.rs");
+    if std::env::var("RUN_UI_TESTS").is_ok() {
+        t.compile_fail("tests/store/*-fail.rs");
+    }
+}
@@ -0,0 +1,15 @@
+use sycamore::prelude::*;
+use sycamore::web::custom_event;
+
+custom_event! {
+    changed: String = "value-changed";
+}
+
+fn compile_fail() {
+    let _ = create_root(|| {
+        let _: View = view! { custom-element(on:value-changed=|_| {}) };
+        let _: View = view! { custom-element(on:closed=|_| {}) };
+    });
+}
+
+fn main() {}
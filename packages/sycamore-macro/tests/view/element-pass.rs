@@ -1,4 +1,17 @@
+use std::borrow::Cow;
+
 use sycamore::prelude::*;
+use sycamore::web::custom_event;
+use sycamore::web::events::CustomEvent;
+use sycamore::web::web_sys::Element;
+
+custom_event! {
+    value_changed: String = "value-changed";
+    opened: () = "opened";
+}
+
+fn tooltip(_el: Element, _text: MaybeDyn<Cow<'static, str>>) {}
+fn autofocus(_el: Element, _: MaybeDyn<()>) {}
 
 fn compile_pass() {
     let _ = create_root(|| {
@@ -14,6 +27,20 @@ fn compile_pass() {
 
         let _: View = view! { button(class="my-btn", on:click=|_| {}) };
         let _: View = view! { button(class="my-btn", aria-hidden="true") };
+        let _: View =
+            view! { custom-element(on:value-changed=|ev: CustomEvent<String>| { ev.detail(); }) };
+        let _: View = view! { custom-element(on:opened=|_: CustomEvent<()>| {}) };
+        let _: View = view! { custom-element(prop:my-prop="value") };
+
+        let text = create_signal(String::new());
+        let _: View = view! { button(use:tooltip="Hello") };
+        let _: View = view! { button(use:tooltip=text) };
+        let _: View = view! { button(use:tooltip=text.get_clone()) };
+        let _: View = view! { input(use:autofocus) };
+
+        let active = create_signal(false);
+        let _: View = view! { p(class:active=active.get(), class:is-open=true) };
+        let _: View = view! { p(style:color="red", style:background-color=active.get().then_some("blue")) };
 
         let _: View = view! { p(dangerously_set_inner_html="<span>Test</span>") };
 
@@ -0,0 +1,14 @@
+use std::env;
+
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(sycamore_sync)");
+
+    // The `sync` feature has no effect on targets without threads, so that it can be enabled for
+    // both the server and the client of an app.
+    let is_wasm = env::var("CARGO_CFG_TARGET_ARCH").is_ok_and(|arch| arch == "wasm32");
+    let has_atomics = env::var("CARGO_CFG_TARGET_FEATURE")
+        .is_ok_and(|features| features.split(',').any(|feature| feature == "atomics"));
+    if env::var_os("CARGO_FEATURE_SYNC").is_some() && (!is_wasm || has_atomics) {
+        println!("cargo:rustc-cfg=sycamore_sync");
+    }
+}
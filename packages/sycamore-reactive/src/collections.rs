@@ -0,0 +1,767 @@
+//! Reactive collections that record their changes as diffs.
+
+use core::fmt;
+use core::hash::Hash;
+use core::ops::Deref;
+
+use crate::*;
+
+/// A change that was made to a [`SignalVec`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VecDiff<T> {
+    /// A value was pushed to the end of the `Vec`.
+    Push(T),
+    /// The last value of the `Vec` was removed.
+    Pop,
+    /// A value was inserted at `index`, shifting all the values after it to the right.
+    Insert {
+        /// The index of the new value.
+        index: usize,
+        /// The new value.
+        value: T,
+    },
+    /// The value at `index` was removed, shifting all the values after it to the left.
+    Remove {
+        /// The index of the removed value.
+        index: usize,
+    },
+    /// The values at `a` and `b` were swapped.
+    Swap {
+        /// The index of the first value.
+        a: usize,
+        /// The index of the second value.
+        b: usize,
+    },
+    /// The value at `index` was replaced with a new value.
+    Set {
+        /// The index of the value.
+        index: usize,
+        /// The new value.
+        value: T,
+    },
+    /// All the values were removed.
+    Clear,
+    /// All the values were replaced with new values.
+    Replace(Vec<T>),
+}
+
+impl<T> VecDiff<T> {
+    /// Applies the change to `items`.
+    pub(crate) fn apply_to(self, items: &mut Vec<T>) {
+        match self {
+            Self::Push(value) => items.push(value),
+            Self::Pop => {
+                items.pop();
+            }
+            Self::Insert { index, value } => items.insert(index, value),
+            Self::Remove { index } => {
+                items.remove(index);
+            }
+            Self::Swap { a, b } => items.swap(a, b),
+            Self::Set { index, value } => items[index] = value,
+            Self::Clear => items.clear(),
+            Self::Replace(values) => *items = values,
+        }
+    }
+}
+
+/// The queue of diffs of a single subscriber.
+type DiffQueue<D> = Shared<SharedCell<Vec<D>>>;
+type WeakDiffQueue<D> = WeakShared<SharedCell<Vec<D>>>;
+
+/// Returns the number of pending diffs of every subscriber that is still alive, so that the diffs
+/// that are sent afterwards can be discarded with [`restore_diffs`].
+fn pending_diffs<D>(subscribers: &[WeakDiffQueue<D>]) -> Vec<(WeakDiffQueue<D>, usize)> {
+    subscribers
+        .iter()
+        .filter_map(|weak| Some((weak.clone(), weak.upgrade()?.borrow_mut().len())))
+        .collect()
+}
+
+/// Discards the `sent` diffs that were sent to the subscribers since [`pending_diffs`] returned
+/// `saved`. Subscribers that subscribed or took diffs since then receive the diffs returned by
+/// `reset` instead, which rebuild the restored state from scratch.
+fn restore_diffs<D>(
+    subscribers: &[WeakDiffQueue<D>],
+    saved: &[(WeakDiffQueue<D>, usize)],
+    sent: usize,
+    reset: impl Fn() -> Vec<D>,
+) {
+    for weak in subscribers {
+        let Some(queue) = weak.upgrade() else {
+            continue;
+        };
+        let mut queue = queue.borrow_mut();
+        match saved.iter().find(|(saved, _)| saved.ptr_eq(weak)) {
+            Some(&(_, len)) if queue.len() == len + sent => queue.truncate(len),
+            _ => *queue = reset(),
+        }
+    }
+}
+
+pub(crate) struct VecState<T> {
+    items: Vec<T>,
+    subscribers: Vec<WeakDiffQueue<VecDiff<T>>>,
+    /// The number of diffs that were sent so far.
+    sent: usize,
+}
+
+impl<T: Clone> VecState<T> {
+    /// Sends the `diff` to every subscriber that is still alive.
+    fn emit(&mut self, diff: VecDiff<T>) {
+        self.sent += 1;
+        self.subscribers.retain(|queue| queue.strong_count() > 0);
+        if let Some((last, rest)) = self.subscribers.split_last() {
+            for queue in rest {
+                queue.upgrade().unwrap().borrow_mut().push(diff.clone());
+            }
+            last.upgrade().unwrap().borrow_mut().push(diff);
+        }
+    }
+}
+
+/// A read-only reactive `Vec` that records every change made to it as a [`VecDiff`].
+///
+/// Unlike a `ReadSignal<Vec<T>>`, consumers of a `ReadSignalVec` do not need to clone and diff the
+/// whole `Vec` to find out what changed. Instead, they can [`subscribe`](Self::subscribe) to the
+/// diffs and only apply those. This is what [`map_keyed`] and [`map_indexed`] do when they are
+/// passed a `ReadSignalVec`.
+///
+/// A `ReadSignalVec` can be obtained by dereferencing a [`SignalVec`].
+pub struct ReadSignalVec<T: 'static>(pub(crate) Signal<VecState<T>>);
+
+/// A reactive `Vec` that records every change made to it as a [`VecDiff`].
+///
+/// This is the writable version of [`ReadSignalVec`]. The `Vec` can only be updated using methods
+/// that produce a diff, such as [`push`](Self::push), [`insert`](Self::insert),
+/// [`remove`](Self::remove), [`swap`](Self::swap) and [`set`](Self::set).
+///
+/// # Example
+/// ```
+/// # use sycamore_reactive::*;
+/// # create_root(|| {
+/// let list = create_signal_vec(vec![1, 2, 3]);
+/// let diffs = list.subscribe();
+///
+/// list.push(4);
+/// list.swap(0, 1);
+/// assert_eq!(list.get_clone(), vec![2, 1, 3, 4]);
+/// assert_eq!(diffs.take(), vec![VecDiff::Push(4), VecDiff::Swap { a: 0, b: 1 }]);
+/// # });
+/// ```
+pub struct SignalVec<T: 'static>(ReadSignalVec<T>);
+
+/// Creates a new [`SignalVec`] with the initial `values`.
+#[cfg_attr(debug_assertions, track_caller)]
+pub fn create_signal_vec<T: MaybeSend>(values: Vec<T>) -> SignalVec<T> {
+    SignalVec(ReadSignalVec(create_signal(VecState {
+        items: values,
+        subscribers: Vec::new(),
+        sent: 0,
+    })))
+}
+
+impl<T> ReadSignalVec<T> {
+    /// Calls the closure with a reference to the values. The `Vec` is tracked.
+    pub fn with<U>(self, f: impl FnOnce(&[T]) -> U) -> U {
+        self.0.with(|state| f(&state.items))
+    }
+
+    /// Calls the closure with a reference to the values without tracking the `Vec`.
+    pub fn with_untracked<U>(self, f: impl FnOnce(&[T]) -> U) -> U {
+        self.0.with_untracked(|state| f(&state.items))
+    }
+
+    /// Returns a clone of the values. The `Vec` is tracked.
+    pub fn get_clone(self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.with(|items| items.to_vec())
+    }
+
+    /// Returns a clone of the values without tracking the `Vec`.
+    pub fn get_clone_untracked(self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.with_untracked(|items| items.to_vec())
+    }
+
+    /// Returns the number of values. The `Vec` is tracked.
+    pub fn len(self) -> usize {
+        self.with(|items| items.len())
+    }
+
+    /// Returns `true` if there are no values. The `Vec` is tracked.
+    pub fn is_empty(self) -> bool {
+        self.with(|items| items.is_empty())
+    }
+
+    /// Tracks the `Vec` without reading its values.
+    pub fn track(self) {
+        self.0.track();
+    }
+
+    /// Returns `true` if the `Vec` has not been disposed yet.
+    pub fn is_alive(self) -> bool {
+        self.0.is_alive()
+    }
+
+    /// Subscribes to the changes made to the `Vec` from now on. The changes can be read with
+    /// [`VecDiffs::take`].
+    ///
+    /// Every subscriber receives its own copy of the diffs. The subscription ends when the
+    /// returned [`VecDiffs`] is dropped.
+    pub fn subscribe(self) -> VecDiffs<T> {
+        let queue = Shared::new(SharedCell::new(Vec::new()));
+        self.0
+            .update_silent_unrestored(|state| state.subscribers.push(Shared::downgrade(&queue)));
+        VecDiffs {
+            list: *self.0,
+            queue,
+        }
+    }
+}
+
+impl<T: Clone + MaybeSend> SignalVec<T> {
+    /// Saves the values and the pending diffs if this is the first write in the current
+    /// [`transaction`], so that they can be restored if it fails.
+    fn save_snapshot(self) {
+        let signal = self.0 .0;
+        signal.0.root.save_snapshot(signal.0.id, || {
+            let (items, sent, saved) = signal.with_untracked(|state| {
+                let saved = pending_diffs(&state.subscribers);
+                (state.items.clone(), state.sent, saved)
+            });
+            Snapshot::Restore(Box::new(move |value: &mut AnyValue| {
+                let state: &mut VecState<T> = value.downcast_mut().expect("wrong signal type");
+                restore_diffs(&state.subscribers, &saved, state.sent - sent, || {
+                    vec![VecDiff::Replace(items.clone())]
+                });
+                Box::new(core::mem::replace(&mut state.items, items))
+            }))
+        });
+    }
+
+    /// Updates the values and records the diff that is returned by `f`.
+    fn update_with_diff<U>(self, f: impl FnOnce(&mut Vec<T>) -> (U, Option<VecDiff<T>>)) -> U {
+        self.save_snapshot();
+        self.0 .0.update_unrestored(|state| {
+            let (ret, diff) = f(&mut state.items);
+            if let Some(diff) = diff {
+                state.emit(diff);
+            }
+            ret
+        })
+    }
+
+    /// Applies the `diff` to the values and records it.
+    pub(crate) fn apply(self, diff: VecDiff<T>) {
+        self.update_with_diff(|items| {
+            diff.clone().apply_to(items);
+            ((), Some(diff))
+        });
+    }
+
+    /// Appends a value to the end of the `Vec`.
+    pub fn push(self, value: T) {
+        self.update_with_diff(|items| {
+            items.push(value.clone());
+            ((), Some(VecDiff::Push(value)))
+        });
+    }
+
+    /// Removes the last value of the `Vec` and returns it, or `None` if it is empty.
+    pub fn pop(self) -> Option<T> {
+        self.update_with_diff(|items| {
+            let value = items.pop();
+            let diff = value.is_some().then_some(VecDiff::Pop);
+            (value, diff)
+        })
+    }
+
+    /// Inserts a value at `index`, shifting all the values after it to the right.
+    ///
+    /// # Panics
+    /// Panics if `index > len`.
+    pub fn insert(self, index: usize, value: T) {
+        self.update_with_diff(|items| {
+            items.insert(index, value.clone());
+            ((), Some(VecDiff::Insert { index, value }))
+        });
+    }
+
+    /// Removes the value at `index` and returns it, shifting all the values after it to the left.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn remove(self, index: usize) -> T {
+        self.update_with_diff(|items| (items.remove(index), Some(VecDiff::Remove { index })))
+    }
+
+    /// Swaps the values at `a` and `b`.
+    ///
+    /// # Panics
+    /// Panics if `a` or `b` are out of bounds.
+    pub fn swap(self, a: usize, b: usize) {
+        self.update_with_diff(|items| {
+            items.swap(a, b);
+            ((), (a != b).then_some(VecDiff::Swap { a, b }))
+        });
+    }
+
+    /// Replaces the value at `index` with a new value and returns the old value.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn set(self, index: usize, value: T) -> T {
+        self.update_with_diff(|items| {
+            let old = core::mem::replace(&mut items[index], value.clone());
+            (old, Some(VecDiff::Set { index, value }))
+        })
+    }
+
+    /// Removes all the values.
+    pub fn clear(self) {
+        self.update_with_diff(|items| {
+            let diff = (!items.is_empty()).then_some(VecDiff::Clear);
+            items.clear();
+            ((), diff)
+        });
+    }
+
+    /// Replaces all the values and returns the old values.
+    ///
+    /// Consumers of the diffs can not tell what changed, so prefer the other methods when
+    /// possible.
+    pub fn replace(self, values: Vec<T>) -> Vec<T> {
+        self.update_with_diff(|items| {
+            let old = core::mem::replace(items, values.clone());
+            (old, Some(VecDiff::Replace(values)))
+        })
+    }
+}
+
+impl<T> Deref for SignalVec<T> {
+    type Target = ReadSignalVec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> Clone for ReadSignalVec<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for ReadSignalVec<T> {}
+
+impl<T> Clone for SignalVec<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for SignalVec<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for ReadSignalVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.with(|items| items.fmt(f))
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SignalVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A subscription to the changes of a [`ReadSignalVec`]. Created using
+/// [`ReadSignalVec::subscribe`].
+pub struct VecDiffs<T: 'static> {
+    list: ReadSignal<VecState<T>>,
+    queue: DiffQueue<VecDiff<T>>,
+}
+
+impl<T> VecDiffs<T> {
+    /// Returns all the changes that were made since the last call, in the order in which they were
+    /// made. The `Vec` is tracked, so calling this inside an effect re-runs the effect every time
+    /// the `Vec` changes.
+    pub fn take(&self) -> Vec<VecDiff<T>> {
+        self.list.track();
+        self.queue.take()
+    }
+}
+
+impl<T> fmt::Debug for VecDiffs<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VecDiffs")
+            .field("pending", &self.queue.borrow_mut().len())
+            .finish()
+    }
+}
+
+/// A change that was made to a [`SignalMap`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MapDiff<K, V> {
+    /// A value was inserted at `key`, replacing the previous value if there was one.
+    Insert {
+        /// The key of the value.
+        key: K,
+        /// The new value.
+        value: V,
+    },
+    /// The value at `key` was removed.
+    Remove {
+        /// The key of the removed value.
+        key: K,
+    },
+    /// All the entries were removed.
+    Clear,
+}
+
+pub(crate) struct MapState<K, V> {
+    entries: HashMap<K, V>,
+    subscribers: Vec<WeakDiffQueue<MapDiff<K, V>>>,
+    /// The number of diffs that were sent so far.
+    sent: usize,
+}
+
+impl<K: Clone, V: Clone> MapState<K, V> {
+    /// Sends the `diff` to every subscriber that is still alive.
+    fn emit(&mut self, diff: MapDiff<K, V>) {
+        self.sent += 1;
+        self.subscribers.retain(|queue| queue.strong_count() > 0);
+        for queue in &self.subscribers {
+            queue.upgrade().unwrap().borrow_mut().push(diff.clone());
+        }
+    }
+}
+
+/// A read-only reactive `HashMap` that records every change made to it as a [`MapDiff`].
+///
+/// A `ReadSignalMap` can be obtained by dereferencing a [`SignalMap`].
+pub struct ReadSignalMap<K: 'static, V: 'static>(Signal<MapState<K, V>>);
+
+/// A reactive `HashMap` that records every change made to it as a [`MapDiff`].
+///
+/// This is the writable version of [`ReadSignalMap`].
+///
+/// # Example
+/// ```
+/// # use sycamore_reactive::*;
+/// # create_root(|| {
+/// let scores = create_signal_map([("alice", 1)]);
+/// let diffs = scores.subscribe();
+///
+/// scores.insert("bob", 2);
+/// scores.remove(&"alice");
+/// assert_eq!(scores.get_clone(&"bob"), Some(2));
+/// assert_eq!(
+///     diffs.take(),
+///     vec![
+///         MapDiff::Insert { key: "bob", value: 2 },
+///         MapDiff::Remove { key: "alice" },
+///     ]
+/// );
+/// # });
+/// ```
+pub struct SignalMap<K: 'static, V: 'static>(ReadSignalMap<K, V>);
+
+/// Creates a new [`SignalMap`] with the initial `entries`.
+#[cfg_attr(debug_assertions, track_caller)]
+pub fn create_signal_map<K, V>(entries: impl IntoIterator<Item = (K, V)>) -> SignalMap<K, V>
+where
+    K: Hash + Eq + MaybeSend,
+    V: MaybeSend,
+{
+    SignalMap(ReadSignalMap(create_signal(MapState {
+        entries: entries.into_iter().collect(),
+        subscribers: Vec::new(),
+        sent: 0,
+    })))
+}
+
+impl<K: Hash + Eq, V> ReadSignalMap<K, V> {
+    /// Calls the closure with a reference to the entries. The map is tracked.
+    pub fn with<U>(self, f: impl FnOnce(&HashMap<K, V>) -> U) -> U {
+        self.0.with(|state| f(&state.entries))
+    }
+
+    /// Calls the closure with a reference to the entries without tracking the map.
+    pub fn with_untracked<U>(self, f: impl FnOnce(&HashMap<K, V>) -> U) -> U {
+        self.0.with_untracked(|state| f(&state.entries))
+    }
+
+    /// Returns a clone of the value at `key`. The map is tracked.
+    pub fn get_clone(self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.with(|entries| entries.get(key).cloned())
+    }
+
+    /// Returns `true` if the map contains a value at `key`. The map is tracked.
+    pub fn contains_key(self, key: &K) -> bool {
+        self.with(|entries| entries.contains_key(key))
+    }
+
+    /// Returns the number of entries. The map is tracked.
+    pub fn len(self) -> usize {
+        self.with(|entries| entries.len())
+    }
+
+    /// Returns `true` if there are no entries. The map is tracked.
+    pub fn is_empty(self) -> bool {
+        self.with(|entries| entries.is_empty())
+    }
+
+    /// Tracks the map without reading its entries.
+    pub fn track(self) {
+        self.0.track();
+    }
+
+    /// Subscribes to the changes made to the map from now on. The changes can be read with
+    /// [`MapDiffs::take`].
+    ///
+    /// Every subscriber receives its own copy of the diffs. The subscription ends when the
+    /// returned [`MapDiffs`] is dropped.
+    pub fn subscribe(self) -> MapDiffs<K, V> {
+        let queue = Shared::new(SharedCell::new(Vec::new()));
+        self.0
+            .update_silent_unrestored(|state| state.subscribers.push(Shared::downgrade(&queue)));
+        MapDiffs {
+            map: *self.0,
+            queue,
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone + MaybeSend, V: Clone + MaybeSend> SignalMap<K, V> {
+    /// Saves the entries and the pending diffs if this is the first write in the current
+    /// [`transaction`], so that they can be restored if it fails.
+    fn save_snapshot(self) {
+        let signal = self.0 .0;
+        signal.0.root.save_snapshot(signal.0.id, || {
+            let (entries, sent, saved) = signal.with_untracked(|state| {
+                let saved = pending_diffs(&state.subscribers);
+                (state.entries.clone(), state.sent, saved)
+            });
+            Snapshot::Restore(Box::new(move |value: &mut AnyValue| {
+                let state: &mut MapState<K, V> = value.downcast_mut().expect("wrong signal type");
+                restore_diffs(&state.subscribers, &saved, state.sent - sent, || {
+                    let inserts = entries.iter().map(|(key, value)| MapDiff::Insert {
+                        key: key.clone(),
+                        value: value.clone(),
+                    });
+                    core::iter::once(MapDiff::Clear).chain(inserts).collect()
+                });
+                Box::new(core::mem::replace(&mut state.entries, entries))
+            }))
+        });
+    }
+
+    /// Inserts a value at `key` and returns the previous value, if any.
+    pub fn insert(self, key: K, value: V) -> Option<V> {
+        self.save_snapshot();
+        self.0 .0.update_unrestored(|state| {
+            let old = state.entries.insert(key.clone(), value.clone());
+            state.emit(MapDiff::Insert { key, value });
+            old
+        })
+    }
+
+    /// Removes the value at `key` and returns it, if any.
+    pub fn remove(self, key: &K) -> Option<V> {
+        self.save_snapshot();
+        self.0 .0.update_unrestored(|state| {
+            let old = state.entries.remove(key);
+            if old.is_some() {
+                state.emit(MapDiff::Remove { key: key.clone() });
+            }
+            old
+        })
+    }
+
+    /// Removes all the entries.
+    pub fn clear(self) {
+        self.save_snapshot();
+        self.0 .0.update_unrestored(|state| {
+            if !state.entries.is_empty() {
+                state.entries.clear();
+                state.emit(MapDiff::Clear);
+            }
+        });
+    }
+}
+
+impl<K, V> Deref for SignalMap<K, V> {
+    type Target = ReadSignalMap<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<K, V> Clone for ReadSignalMap<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<K, V> Copy for ReadSignalMap<K, V> {}
+
+impl<K, V> Clone for SignalMap<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<K, V> Copy for SignalMap<K, V> {}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for ReadSignalMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.with(|state| state.entries.fmt(f))
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for SignalMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A subscription to the changes of a [`ReadSignalMap`]. Created using
+/// [`ReadSignalMap::subscribe`].
+pub struct MapDiffs<K: 'static, V: 'static> {
+    map: ReadSignal<MapState<K, V>>,
+    queue: DiffQueue<MapDiff<K, V>>,
+}
+
+impl<K, V> MapDiffs<K, V> {
+    /// Returns all the changes that were made since the last call, in the order in which they were
+    /// made. The map is tracked, so calling this inside an effect re-runs the effect every time the
+    /// map changes.
+    pub fn take(&self) -> Vec<MapDiff<K, V>> {
+        self.map.track();
+        self.queue.take()
+    }
+}
+
+impl<K, V> fmt::Debug for MapDiffs<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapDiffs")
+            .field("pending", &self.queue.borrow_mut().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signal_vec_diffs() {
+        let _ = create_root(|| {
+            let list = create_signal_vec(vec![1, 2, 3]);
+            let diffs = list.subscribe();
+
+            list.push(4);
+            list.insert(0, 0);
+            assert_eq!(list.remove(1), 1);
+            list.swap(0, 1);
+            list.swap(2, 2);
+            assert_eq!(list.set(3, 5), 4);
+            assert_eq!(list.pop(), Some(5));
+            assert_eq!(list.get_clone(), vec![2, 0, 3]);
+            assert_eq!(
+                diffs.take(),
+                vec![
+                    VecDiff::Push(4),
+                    VecDiff::Insert { index: 0, value: 0 },
+                    VecDiff::Remove { index: 1 },
+                    VecDiff::Swap { a: 0, b: 1 },
+                    VecDiff::Set { index: 3, value: 5 },
+                    VecDiff::Pop,
+                ]
+            );
+            assert_eq!(diffs.take(), vec![]);
+
+            assert_eq!(list.replace(vec![7]), vec![2, 0, 3]);
+            list.clear();
+            list.clear();
+            assert_eq!(list.pop(), None);
+            assert_eq!(
+                diffs.take(),
+                vec![VecDiff::Replace(vec![7]), VecDiff::Clear]
+            );
+        });
+    }
+
+    #[test]
+    fn signal_vec_subscribers() {
+        let _ = create_root(|| {
+            let list = create_signal_vec(Vec::new());
+            let a = list.subscribe();
+            let b = list.subscribe();
+            list.push(1);
+            drop(b);
+            list.push(2);
+            assert_eq!(a.take(), vec![VecDiff::Push(1), VecDiff::Push(2)]);
+            list.0
+                 .0
+                .with_untracked(|state| assert_eq!(state.subscribers.len(), 1));
+        });
+    }
+
+    #[test]
+    fn signal_vec_diffs_are_tracked() {
+        let _ = create_root(|| {
+            let list = create_signal_vec(Vec::new());
+            let diffs = list.subscribe();
+            let received = create_signal(Vec::new());
+            create_effect(move || {
+                let diffs = diffs.take();
+                received.update_silent(|received| received.extend(diffs));
+            });
+
+            batch(move || {
+                list.push(1);
+                list.push(2);
+            });
+            list.remove(0);
+            assert_eq!(
+                received.get_clone(),
+                vec![
+                    VecDiff::Push(1),
+                    VecDiff::Push(2),
+                    VecDiff::Remove { index: 0 }
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn signal_map_diffs() {
+        let _ = create_root(|| {
+            let map = create_signal_map([(1, "a")]);
+            let diffs = map.subscribe();
+
+            assert_eq!(map.insert(2, "b"), None);
+            assert_eq!(map.insert(1, "c"), Some("a"));
+            assert_eq!(map.remove(&3), None);
+            assert_eq!(map.remove(&2), Some("b"));
+            assert_eq!(map.get_clone(&1), Some("c"));
+            map.clear();
+            assert!(map.is_empty());
+            assert_eq!(
+                diffs.take(),
+                vec![
+                    MapDiff::Insert { key: 2, value: "b" },
+                    MapDiff::Insert { key: 1, value: "c" },
+                    MapDiff::Remove { key: 2 },
+                    MapDiff::Clear,
+                ]
+            );
+        });
+    }
+}
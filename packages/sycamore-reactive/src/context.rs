@@ -1,10 +1,10 @@
 //! Context values.
 
-use std::any::{type_name, Any};
+use core::any::type_name;
 
 use slotmap::Key;
 
-use crate::{create_child_scope, NodeId, Root};
+use crate::{create_child_scope, AnyValue, Box, MaybeSend, NodeId, Root};
 
 /// Provide a context value in this scope.
 ///
@@ -12,7 +12,7 @@ use crate::{create_child_scope, NodeId, Root};
 /// This panics if a context value of the same type exists already in this scope. Note that it is
 /// allowed to have context values with the same type in _different_ scopes.
 #[cfg_attr(debug_assertions, track_caller)]
-pub fn provide_context<T: 'static>(value: T) {
+pub fn provide_context<T: MaybeSend + 'static>(value: T) {
     let root = Root::global();
     provide_context_in_node(root.current_node.get(), value);
 }
@@ -37,7 +37,10 @@ pub fn provide_context<T: 'static>(value: T) {
 /// assert_eq!(use_context::<i32>(), 123);
 /// # });
 /// ```
-pub fn provide_context_in_new_scope<T: 'static, U>(value: T, f: impl FnOnce() -> U) -> U {
+pub fn provide_context_in_new_scope<T: MaybeSend + 'static, U>(
+    value: T,
+    f: impl FnOnce() -> U,
+) -> U {
     let mut ret = None;
     create_child_scope(|| {
         provide_context(value);
@@ -48,10 +51,10 @@ pub fn provide_context_in_new_scope<T: 'static, U>(value: T, f: impl FnOnce() ->
 
 /// Internal implementation for [`provide_context`].
 #[cfg_attr(debug_assertions, track_caller)]
-fn provide_context_in_node<T: 'static>(id: NodeId, value: T) {
+fn provide_context_in_node<T: MaybeSend + 'static>(id: NodeId, value: T) {
     let root = Root::global();
     let mut nodes = root.nodes.borrow_mut();
-    let any: Box<dyn Any> = Box::new(value);
+    let any: Box<AnyValue> = Box::new(value);
 
     let node = &mut nodes[id];
     if node
@@ -65,6 +68,8 @@ fn provide_context_in_node<T: 'static>(id: NodeId, value: T) {
         );
     }
     node.context.push(any);
+    #[cfg(feature = "inspect")]
+    node.context_types.push(type_name::<T>());
 }
 
 /// Tries to get a context value of the given type. If no context is found, returns `None`.
@@ -101,7 +106,7 @@ pub fn use_context<T: Clone + 'static>() -> T {
 
 /// Try to get a context with the given type. If no context is found, returns the value of the
 /// function and sets the value of the context in the current scope.
-pub fn use_context_or_else<T: Clone + 'static, F: FnOnce() -> T>(f: F) -> T {
+pub fn use_context_or_else<T: Clone + MaybeSend + 'static, F: FnOnce() -> T>(f: F) -> T {
     try_use_context().unwrap_or_else(|| {
         let value = f();
         provide_context(value.clone());
@@ -1,9 +1,10 @@
 //! Side effects!
 
-use std::cell::RefCell;
-use std::rc::Rc;
-
-use crate::create_memo;
+#[cfg(not(sycamore_sync))]
+use crate::Box;
+#[cfg(feature = "inspect")]
+use crate::NodeKind;
+use crate::{create_memo, MaybeSend};
 
 /// Creates an effect on signals used inside the effect closure.
 ///
@@ -27,8 +28,13 @@ use crate::create_memo;
 /// recommended to update signal states inside an effect. You probably should be using a
 /// [`create_memo`](crate::create_memo) instead.
 #[cfg_attr(debug_assertions, track_caller)]
-pub fn create_effect(f: impl FnMut() + 'static) {
-    create_memo(f);
+pub fn create_effect(f: impl FnMut() + MaybeSend + 'static) {
+    #[cfg_attr(not(feature = "inspect"), allow(unused_variables))]
+    let effect = create_memo(f);
+    #[cfg(feature = "inspect")]
+    {
+        effect.root.nodes.borrow_mut()[effect.id].kind = NodeKind::Effect;
+    }
 }
 
 /// Creates an effect that runs a different code path on the first run.
@@ -57,10 +63,16 @@ pub fn create_effect(f: impl FnMut() + 'static) {
 /// create signals that are alive in subsequent runs, you should use
 /// [`use_current_scope`](crate::use_current_scope) and
 /// [`NodeHandle::run_in`](crate::NodeHandle::run_in).
+///
+/// This is not available with the `sync` feature.
+#[cfg(not(sycamore_sync))]
 #[cfg_attr(debug_assertions, track_caller)]
 pub fn create_effect_initial<T: 'static>(
     initial: impl FnOnce() -> (Box<dyn FnMut() + 'static>, T) + 'static,
 ) -> T {
+    use alloc::rc::Rc;
+    use core::cell::RefCell;
+
     let ret = Rc::new(RefCell::new(None));
     let mut initial = Some(initial);
     let mut effect = None;
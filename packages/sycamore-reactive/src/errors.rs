@@ -0,0 +1,405 @@
+//! Error handling in the owner tree.
+
+use core::any::Any;
+use core::error::Error;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::*;
+
+/// The type of the errors that can be reported to an error handler. This is
+/// `dyn Error + Send + Sync` with the `sync` feature and `dyn Error` otherwise.
+#[cfg(sycamore_sync)]
+pub type DynError = dyn Error + Send + Sync;
+/// The type of the errors that can be reported to an error handler. This is
+/// `dyn Error + Send + Sync` with the `sync` feature and `dyn Error` otherwise.
+#[cfg(not(sycamore_sync))]
+pub type DynError = dyn Error;
+
+/// An error that was caught in a reactive scope, either because it was passed to [`report_error`]
+/// or because the code panicked.
+///
+/// `CapturedError` is cheap to clone.
+#[derive(Clone)]
+pub struct CapturedError {
+    error: Shared<DynError>,
+    is_panic: bool,
+}
+
+impl CapturedError {
+    /// Creates a new `CapturedError` from an error.
+    ///
+    /// With the `sync` feature, the error must be `Send + Sync`.
+    pub fn new(error: impl Into<Box<DynError>>) -> Self {
+        let error = error.into();
+        // Do not wrap errors that were already captured.
+        match error.downcast::<Self>() {
+            Ok(captured) => *captured,
+            Err(error) => Self {
+                error: error.into(),
+                is_panic: false,
+            },
+        }
+    }
+
+    /// Creates a new `CapturedError` from the payload of a panic.
+    pub(crate) fn from_panic(payload: Box<dyn Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&'static str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => "panicked".to_string(),
+            },
+        };
+        Self {
+            error: Shared::new(PanicError(message)),
+            is_panic: true,
+        }
+    }
+
+    /// Returns `true` if the error was caused by a panic. The panic message is used as the message
+    /// of the error.
+    pub fn is_panic(&self) -> bool {
+        self.is_panic
+    }
+
+    /// Returns a reference to the underlying error.
+    pub fn error(&self) -> &(dyn Error + 'static) {
+        &*self.error
+    }
+
+    /// Returns a reference to the underlying error if it is of type `E`.
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.error.downcast_ref()
+    }
+}
+
+impl fmt::Display for CapturedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl fmt::Debug for CapturedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CapturedError")
+            .field("error", &self.error)
+            .field("is_panic", &self.is_panic)
+            .finish()
+    }
+}
+
+impl Error for CapturedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+/// The error used for panics. Displays the panic message.
+#[derive(Debug)]
+struct PanicError(String);
+
+impl fmt::Display for PanicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for PanicError {}
+
+#[cfg(sycamore_sync)]
+type ErrorHandlerFn = dyn Fn(CapturedError) + Send + Sync;
+#[cfg(not(sycamore_sync))]
+type ErrorHandlerFn = dyn Fn(CapturedError);
+
+/// The error handler of a scope. Stored as a context value.
+#[derive(Clone)]
+pub(crate) struct ErrorHandler(Shared<ErrorHandlerFn>);
+
+impl ErrorHandler {
+    pub(crate) fn handle(&self, error: CapturedError) {
+        (self.0)(error);
+    }
+}
+
+/// Registers an error handler in the current scope.
+///
+/// Errors that are reported with [`report_error`] in this scope or any of its descendants are
+/// passed to the nearest error handler. Panics inside memos and effects are also caught and passed
+/// to the nearest error handler of the memo or effect. The memo or effect keeps its previous value
+/// and runs again when one of its dependencies changes.
+///
+/// Panics can only be caught on targets that support unwinding. On `wasm32-unknown-unknown`,
+/// panics always abort.
+///
+/// # Panics
+/// This panics if an error handler exists already in this scope.
+///
+/// # Example
+/// ```
+/// # use sycamore_reactive::*;
+/// # create_root(|| {
+/// let last_error = create_signal(None);
+/// provide_error_handler(move |error| last_error.set(Some(error.to_string())));
+///
+/// report_error("something went wrong");
+/// assert_eq!(last_error.get_clone().as_deref(), Some("something went wrong"));
+/// # });
+/// ```
+#[cfg_attr(debug_assertions, track_caller)]
+pub fn provide_error_handler(handler: impl Fn(CapturedError) + MaybeSend + MaybeSync + 'static) {
+    provide_context(ErrorHandler(Shared::new(handler)));
+    let root = Root::global();
+    root.error_handlers.set(root.error_handlers.get() + 1);
+    on_cleanup(move || {
+        root.error_handlers
+            .set(root.error_handlers.get().saturating_sub(1));
+    });
+}
+
+/// Reports an error to the nearest error handler of the current scope. See
+/// [`provide_error_handler`].
+///
+/// # Panics
+/// This panics if there is no error handler in the current scope or any of its ancestors.
+#[cfg_attr(debug_assertions, track_caller)]
+pub fn report_error(error: impl Into<Box<DynError>>) {
+    let error = CapturedError::new(error);
+    match try_use_context::<ErrorHandler>() {
+        Some(handler) => handler.handle(error),
+        None => panic!("unhandled error: {error}"),
+    }
+}
+
+/// Returns the nearest error handler of `node`, if any.
+pub(crate) fn error_handler_of(node: NodeHandle) -> Option<ErrorHandler> {
+    node.run_in(try_use_context::<ErrorHandler>)
+}
+
+/// Runs `f` and catches any panic. This is used for rendering parts of an app that should not
+/// tear down the rest of the app when they panic.
+///
+/// If `f` panics, the reactive state (the current scope, dependency tracking and batching) is
+/// restored to what it was before calling `f`. Anything that was created inside `f` before it
+/// panicked is still owned by the current scope, so `f` should usually be run inside a
+/// [child scope](create_child_scope) that is disposed when it fails.
+///
+/// Panics can only be caught on targets that support unwinding. On `wasm32-unknown-unknown`,
+/// panics always abort.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, CapturedError> {
+    catch_unwind_in(Root::global(), f).map_err(CapturedError::from_panic)
+}
+
+/// Implementation for [`catch_panic`]. Returns the panic payload if `f` panicked.
+pub(crate) fn catch_unwind_in<T>(
+    root: &'static Root,
+    f: impl FnOnce() -> T,
+) -> Result<T, Box<dyn Any + Send>> {
+    let current_node = root.current_node.get();
+    let batching = root.batching.get();
+    let propagation_depth = root.propagation_depth.get();
+    // Track dependencies in a new tracker so that the outer tracker is not lost if `f` panics
+    // inside a nested tracked scope.
+    let outer = root.tracker.take();
+    if outer.is_some() {
+        root.tracker.replace(Some(DependencyTracker::default()));
+    }
+
+    #[cfg(feature = "std")]
+    let ret = catch_unwind(AssertUnwindSafe(f));
+    // Panics cannot be caught without `std`.
+    #[cfg(not(feature = "std"))]
+    let ret = Ok(f());
+
+    // Keep the dependencies that were tracked before `f` returned or panicked.
+    let inner = root.tracker.take();
+    let outer = match (outer, inner) {
+        (Some(mut outer), Some(inner)) => {
+            outer.dependencies.extend(inner.dependencies);
+            Some(outer)
+        }
+        (outer, _) => outer,
+    };
+    root.tracker.replace(outer);
+    if ret.is_err() {
+        Root::set_global(Some(root));
+        root.current_node.set(current_node);
+        root.propagation_depth.set(propagation_depth);
+        if !batching && root.batching.get() {
+            root.end_batch();
+        }
+    }
+    ret
+}
+
+/// Continues a panic that was caught by [`catch_unwind_in`].
+pub(crate) fn resume_unwind(payload: Box<dyn Any + Send>) -> ! {
+    #[cfg(feature = "std")]
+    std::panic::resume_unwind(payload);
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = payload;
+        unreachable!("panics are not caught without `std`")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt;
+    #[cfg(all(feature = "std", not(feature = "sync")))]
+    use std::cell::Cell;
+    #[cfg(all(feature = "std", not(feature = "sync")))]
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct MyError;
+
+    impl fmt::Display for MyError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("my error")
+        }
+    }
+
+    impl Error for MyError {}
+
+    #[test]
+    fn report_to_nearest_handler() {
+        let _ = create_root(|| {
+            let outer = create_signal(Vec::new());
+            let inner = create_signal(Vec::new());
+            provide_error_handler(move |error| outer.update(|errors| errors.push(error)));
+            create_child_scope(|| {
+                provide_error_handler(move |error| inner.update(|errors| errors.push(error)));
+                create_child_scope(|| report_error(MyError));
+            });
+            report_error("outer");
+
+            let inner = inner.get_clone();
+            assert_eq!(inner.len(), 1);
+            assert!(inner[0].downcast_ref::<MyError>().is_some());
+            assert!(!inner[0].is_panic());
+            assert_eq!(outer.with(|errors| errors[0].to_string()), "outer");
+        });
+    }
+
+    #[test]
+    #[should_panic = "unhandled error: my error"]
+    fn report_without_handler_panics() {
+        let _ = create_root(|| report_error(MyError));
+    }
+
+    #[test]
+    fn captured_errors_are_not_wrapped() {
+        let error = CapturedError::new(MyError);
+        let error = CapturedError::new(error);
+        assert!(error.downcast_ref::<MyError>().is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn catch_panic_restores_state() {
+        let _ = create_root(|| {
+            let scope = use_current_scope();
+            let signal = create_signal(0);
+            let memo = create_memo(move || {
+                let error = catch_panic(|| {
+                    batch(|| {
+                        create_child_scope(|| panic!("oops"));
+                    })
+                })
+                .unwrap_err();
+                assert!(error.is_panic());
+                assert_eq!(error.to_string(), "oops");
+                signal.get()
+            });
+            assert_eq!(use_current_scope().0, scope.0);
+
+            // The memo still tracks its dependencies.
+            signal.set(1);
+            assert_eq!(memo.get(), 1);
+        });
+    }
+
+    #[test]
+    #[cfg(not(feature = "sync"))]
+    #[cfg(feature = "std")]
+    fn effect_panic_is_reported() {
+        let _ = create_root(|| {
+            let error = create_signal(None);
+            let runs = Rc::new(Cell::new(0));
+            let signal = create_signal(0);
+            provide_error_handler(move |e| error.set(Some(e.to_string())));
+            create_effect({
+                let runs = Rc::clone(&runs);
+                move || {
+                    runs.set(runs.get() + 1);
+                    if signal.get() == 1 {
+                        panic!("effect failed");
+                    }
+                }
+            });
+            let memo = create_memo(move || signal.get() * 2);
+
+            signal.set(1);
+            assert_eq!(error.get_clone().as_deref(), Some("effect failed"));
+            assert_eq!(memo.get(), 2, "other nodes are still updated");
+
+            signal.set(2);
+            assert_eq!(runs.get(), 3, "effect runs again after panicking");
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn memo_panic_keeps_previous_value() {
+        let _ = create_root(|| {
+            let error = create_signal(None);
+            provide_error_handler(move |e| error.set(Some(e)));
+            let signal = create_signal(1);
+            let memo = create_memo(move || {
+                let value = signal.get();
+                assert!(value < 10, "too large");
+                value
+            });
+
+            signal.set(20);
+            assert_eq!(memo.get(), 1);
+            assert!(error.with(|e| e.as_ref().unwrap().is_panic()));
+            signal.set(5);
+            assert_eq!(memo.get(), 5);
+        });
+    }
+
+    #[test]
+    #[should_panic = "effect failed"]
+    fn effect_panic_without_handler() {
+        let _ = create_root(|| {
+            let signal = create_signal(0);
+            create_effect(move || {
+                if signal.get() == 1 {
+                    panic!("effect failed");
+                }
+            });
+            signal.set(1);
+        });
+    }
+
+    #[test]
+    #[should_panic = "effect failed"]
+    fn effect_panic_after_handler_is_disposed() {
+        let _ = create_root(|| {
+            let signal = create_signal(0);
+            let handler_scope = create_child_scope(|| provide_error_handler(|_| {}));
+            handler_scope.dispose();
+            create_effect(move || {
+                if signal.get() == 1 {
+                    panic!("effect failed");
+                }
+            });
+            signal.set(1);
+        });
+    }
+}
@@ -0,0 +1,411 @@
+//! Undo and redo for signals.
+
+use alloc::collections::VecDeque;
+use core::fmt;
+
+use crate::*;
+
+/// A change to a single signal that can be undone.
+trait Change: MaybeSend {
+    /// Restores the recorded value. Returns the change that reverts this one.
+    fn apply(self: Box<Self>) -> Box<dyn Change>;
+    /// Clones the change, so that the history can be restored if a [`transaction`] fails.
+    fn clone_box(&self) -> Box<dyn Change>;
+}
+
+struct SignalChange<T: 'static> {
+    signal: Signal<T>,
+    value: T,
+}
+
+impl<T: Clone + MaybeSend + 'static> Change for SignalChange<T> {
+    fn apply(self: Box<Self>) -> Box<dyn Change> {
+        let Self { signal, mut value } = *self;
+        if signal.is_alive() {
+            value = signal.replace(value);
+        }
+        Box::new(Self { signal, value })
+    }
+
+    fn clone_box(&self) -> Box<dyn Change> {
+        Box::new(Self {
+            signal: self.signal,
+            value: self.value.clone(),
+        })
+    }
+}
+
+/// All the changes that were made in a single update or batch.
+type Entry = Vec<Box<dyn Change>>;
+
+struct HistoryState {
+    depth: usize,
+    undo: VecDeque<Entry>,
+    redo: Vec<Entry>,
+    /// The propagation epoch of the last undo entry. Changes made in the same epoch are added to
+    /// the same entry.
+    epoch: Option<u64>,
+    /// The propagation epoch in which the changes of the last undo or redo are propagated. Changes
+    /// made in this epoch are not recorded.
+    applied_epoch: Option<u64>,
+}
+
+impl Clone for HistoryState {
+    fn clone(&self) -> Self {
+        let clone_entry = |entry: &Entry| entry.iter().map(|change| change.clone_box()).collect();
+        Self {
+            depth: self.depth,
+            undo: self.undo.iter().map(clone_entry).collect(),
+            redo: self.redo.iter().map(clone_entry).collect(),
+            epoch: self.epoch,
+            applied_epoch: self.applied_epoch,
+        }
+    }
+}
+
+impl HistoryState {
+    fn record(&mut self, epoch: u64, change: Box<dyn Change>) {
+        if self.depth == 0 {
+            return;
+        }
+        match self.undo.back_mut() {
+            Some(entry) if self.epoch == Some(epoch) => entry.push(change),
+            _ => {
+                self.undo.push_back(vec![change]);
+                if self.undo.len() > self.depth {
+                    self.undo.pop_front();
+                }
+                self.epoch = Some(epoch);
+            }
+        }
+        self.redo.clear();
+    }
+}
+
+/// Records the previous values of signals so that changes can be undone and redone.
+///
+/// Every time a tracked signal is updated, its previous value is recorded. All the updates that are
+/// made inside a single [`batch`] are grouped into a single history entry, as well as updates that
+/// are made by effects in response to an update. Since the signals are recorded when they change,
+/// updates from anywhere, including `bind:` directives, are recorded.
+///
+/// Create a new history with [`create_history`].
+///
+/// # Example
+/// ```
+/// # use sycamore_reactive::*;
+/// # let _ = create_root(|| {
+/// let history = create_history(100);
+/// let name = history.create_signal(String::from("Alice"));
+/// let age = history.create_signal(30);
+///
+/// name.set("Bob".to_string());
+/// batch(|| {
+///     name.set("Carol".to_string());
+///     age.set(31);
+/// });
+///
+/// history.undo();
+/// assert_eq!(name.get_clone(), "Bob");
+/// assert_eq!(age.get(), 30);
+/// history.redo();
+/// assert_eq!(name.get_clone(), "Carol");
+/// assert_eq!(age.get(), 31);
+/// # });
+/// ```
+#[derive(Clone, Copy)]
+pub struct History(Signal<HistoryState>);
+
+/// Creates a new [`History`] that remembers up to `depth` entries. Older entries are discarded.
+pub fn create_history(depth: usize) -> History {
+    let state = create_signal(HistoryState {
+        depth,
+        undo: VecDeque::new(),
+        redo: Vec::new(),
+        epoch: None,
+        applied_epoch: None,
+    });
+    // The history is updated in place, so it needs to be cloned if it is updated in a transaction.
+    state.enable_snapshots();
+    History(state)
+}
+
+impl History {
+    /// Creates a new signal that is tracked by this history. See [`History::track`].
+    pub fn create_signal<T: Clone + MaybeSend + 'static>(self, value: T) -> Signal<T> {
+        let signal = create_signal(value);
+        self.track(signal);
+        signal
+    }
+
+    /// Starts recording the changes of `signal`. The previous value of the signal is cloned every
+    /// time it is updated.
+    ///
+    /// The recording stops once the current scope is disposed.
+    pub fn track<T: Clone + MaybeSend + 'static>(self, signal: Signal<T>) {
+        let root = self.0.root;
+        // Tracked signals can be updated in place inside a transaction.
+        signal.enable_snapshots();
+        let mut prev = None;
+        create_effect(move || {
+            let value = signal.get_clone();
+            let Some(prev) = prev.replace(value) else {
+                // First run. There is nothing to record yet.
+                return;
+            };
+            let epoch = root.propagation_epoch.get();
+            if self
+                .0
+                .with_untracked(|state| state.applied_epoch == Some(epoch))
+            {
+                return;
+            }
+            let change = SignalChange {
+                signal,
+                value: prev,
+            };
+            self.0.update(|state| state.record(epoch, Box::new(change)));
+        });
+    }
+
+    /// Reverts the last history entry. Returns `false` if there was nothing to undo.
+    ///
+    /// Other changes that are made in the same batch as the undo, or by effects in response to it,
+    /// are not recorded.
+    pub fn undo(self) -> bool {
+        let Some(entry) = self.0.update_silent(|state| state.undo.pop_back()) else {
+            return false;
+        };
+        let entry = self.apply(entry);
+        self.0.update(|state| {
+            state.redo.push(entry);
+            state.epoch = None;
+        });
+        true
+    }
+
+    /// Applies the last entry that was undone again. Returns `false` if there was nothing to redo.
+    pub fn redo(self) -> bool {
+        let Some(entry) = self.0.update_silent(|state| state.redo.pop()) else {
+            return false;
+        };
+        let entry = self.apply(entry);
+        self.0.update(|state| {
+            state.undo.push_back(entry);
+            state.epoch = None;
+        });
+        true
+    }
+
+    /// Applies all the changes in `entry` in reverse order. Returns the entry that reverts it.
+    fn apply(self, entry: Entry) -> Entry {
+        // The changes are propagated in the current propagation if there is one. Otherwise, they
+        // are propagated in the next one, either at the end of the batch below or, if this is
+        // called inside another batch, at the end of the outermost batch.
+        let root = self.0.root;
+        let epoch = root.propagation_epoch.get();
+        let applied_epoch = if root.propagation_depth.get() > 0 {
+            epoch
+        } else {
+            epoch + 1
+        };
+        self.0
+            .update_silent(|state| state.applied_epoch = Some(applied_epoch));
+        let mut reverted = batch(|| {
+            entry
+                .into_iter()
+                .rev()
+                .map(|change| change.apply())
+                .collect::<Entry>()
+        });
+        reverted.reverse();
+        reverted
+    }
+
+    /// Returns `true` if there is an entry to undo. This is tracked.
+    pub fn can_undo(self) -> bool {
+        self.0.with(|state| !state.undo.is_empty())
+    }
+
+    /// Returns `true` if there is an entry to redo. This is tracked.
+    pub fn can_redo(self) -> bool {
+        self.0.with(|state| !state.redo.is_empty())
+    }
+
+    /// Removes all the entries from the history. The values of the tracked signals are not changed.
+    pub fn clear(self) {
+        self.0.update(|state| {
+            state.undo.clear();
+            state.redo.clear();
+            state.epoch = None;
+        });
+    }
+}
+
+impl fmt::Debug for History {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.with_untracked(|state| {
+            f.debug_struct("History")
+                .field("depth", &state.depth)
+                .field("undo", &state.undo.len())
+                .field("redo", &state.redo.len())
+                .finish()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_redo() {
+        let _ = create_root(|| {
+            let history = create_history(10);
+            let state = history.create_signal(0);
+            assert!(!history.can_undo());
+
+            state.set(1);
+            state.update(|value| *value += 1);
+            assert_eq!(state.get(), 2);
+
+            assert!(history.undo());
+            assert_eq!(state.get(), 1);
+            assert!(history.undo());
+            assert_eq!(state.get(), 0);
+            assert!(!history.undo());
+
+            assert!(history.redo());
+            assert_eq!(state.get(), 1);
+            assert!(history.redo());
+            assert_eq!(state.get(), 2);
+            assert!(!history.redo());
+        });
+    }
+
+    #[test]
+    fn new_change_clears_redo() {
+        let _ = create_root(|| {
+            let history = create_history(10);
+            let state = history.create_signal(0);
+            state.set(1);
+            history.undo();
+            assert!(history.can_redo());
+
+            state.set(2);
+            assert!(!history.can_redo());
+            history.undo();
+            assert_eq!(state.get(), 0);
+        });
+    }
+
+    #[test]
+    fn batch_is_single_entry() {
+        let _ = create_root(|| {
+            let history = create_history(10);
+            let a = history.create_signal(0);
+            let b = history.create_signal(0);
+            batch(|| {
+                a.set(1);
+                a.set(2);
+                b.set(1);
+            });
+            a.set(3);
+
+            history.undo();
+            assert_eq!((a.get(), b.get()), (2, 1));
+            history.undo();
+            assert_eq!((a.get(), b.get()), (0, 0));
+            assert!(!history.can_undo());
+
+            history.redo();
+            assert_eq!((a.get(), b.get()), (2, 1));
+        });
+    }
+
+    #[test]
+    fn changes_from_effects_are_grouped() {
+        let _ = create_root(|| {
+            let history = create_history(10);
+            let a = history.create_signal(0);
+            let double = history.create_signal(0);
+            create_effect(move || double.set(a.get() * 2));
+            history.clear();
+
+            a.set(1);
+            assert_eq!(double.get(), 2);
+            history.undo();
+            assert_eq!((a.get(), double.get()), (0, 0));
+            assert!(!history.can_undo());
+        });
+    }
+
+    #[test]
+    fn undo_in_batch() {
+        let _ = create_root(|| {
+            let history = create_history(10);
+            let state = history.create_signal(0);
+            state.set(1);
+            state.set(2);
+
+            batch(|| {
+                history.undo();
+            });
+            assert_eq!(state.get(), 1);
+            assert!(history.can_undo());
+            assert!(history.can_redo());
+
+            batch(|| {
+                history.redo();
+            });
+            assert_eq!(state.get(), 2);
+            assert!(!history.can_redo());
+
+            batch(|| {
+                history.undo();
+                history.undo();
+            });
+            assert_eq!(state.get(), 0);
+            assert!(!history.can_undo());
+            history.redo();
+            history.redo();
+            assert_eq!(state.get(), 2);
+
+            // Changes made after the undo are recorded again.
+            state.set(3);
+            assert!(!history.can_redo());
+            history.undo();
+            assert_eq!(state.get(), 2);
+        });
+    }
+
+    #[test]
+    fn depth_limit() {
+        let _ = create_root(|| {
+            let history = create_history(2);
+            let state = history.create_signal(0);
+            for i in 1..=5 {
+                state.set(i);
+            }
+            while history.undo() {}
+            assert_eq!(state.get(), 3);
+        });
+    }
+
+    #[test]
+    fn can_undo_is_reactive() {
+        let _ = create_root(|| {
+            let history = create_history(10);
+            let state = history.create_signal(0);
+            let can_undo = create_memo(move || history.can_undo());
+            let can_redo = create_memo(move || history.can_redo());
+            assert!(!can_undo.get());
+
+            state.set(1);
+            assert!(can_undo.get());
+            history.undo();
+            assert!(!can_undo.get());
+            assert!(can_redo.get());
+        });
+    }
+}
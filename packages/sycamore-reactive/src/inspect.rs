@@ -0,0 +1,373 @@
+//! Read-only inspection of the reactive graph.
+
+use core::fmt::Write;
+
+use slotmap::{Key, SecondaryMap, SlotMap};
+
+use crate::*;
+
+/// The kind of a node in the reactive graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    /// A node created by [`create_signal`].
+    Signal,
+    /// A node created by [`create_memo`] or one of the selector functions.
+    Memo,
+    /// A node created by [`create_lazy_memo`].
+    LazyMemo,
+    /// A node created by [`create_effect`].
+    Effect,
+    /// A node created by [`create_child_scope`] or [`create_root`]. Scopes own other nodes but do
+    /// not hold a value.
+    Scope,
+}
+
+impl NodeKind {
+    /// Returns the name of the kind in lowercase, e.g. `"signal"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Signal => "signal",
+            Self::Memo => "memo",
+            Self::LazyMemo => "lazy memo",
+            Self::Effect => "effect",
+            Self::Scope => "scope",
+        }
+    }
+}
+
+/// Information about a single node in a [`GraphSnapshot`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeInfo {
+    /// The id of the node. Ids are unique for the whole lifetime of the root, so they can be used
+    /// to compare nodes across snapshots.
+    pub id: u64,
+    /// The kind of the node.
+    pub kind: NodeKind,
+    /// The node that owns this node, if any. A node is disposed when its owner is disposed.
+    pub owner: Option<u64>,
+    /// The nodes that are owned by this node.
+    pub owned: Vec<u64>,
+    /// The nodes that this node reads from.
+    pub dependencies: Vec<u64>,
+    /// The nodes that read from this node.
+    pub dependents: Vec<u64>,
+    /// Where the node was created, e.g. `src/main.rs:10:5`. Only available in debug builds.
+    pub location: Option<String>,
+    /// The type names of the context values provided in this node.
+    pub context_types: Vec<&'static str>,
+    /// How many times a memo or an effect was re-run because one of its dependencies changed. This
+    /// does not include the initial run.
+    pub runs: u64,
+}
+
+/// A snapshot of the reactive graph of a root. Created using [`inspect_graph`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GraphSnapshot {
+    /// All the nodes that are alive. The nodes are sorted by the owner tree, starting from the
+    /// root scope, with every node directly followed by the nodes that it owns.
+    pub nodes: Vec<NodeInfo>,
+}
+
+/// Returns a snapshot of the reactive graph of the current root.
+///
+/// This is meant for debugging, e.g. to find effects that run too often or scopes that are never
+/// disposed. Taking a snapshot does not affect the reactive graph.
+///
+/// # Example
+/// ```
+/// # use sycamore_reactive::*;
+/// # create_root(|| {
+/// let signal = create_signal(1);
+/// create_effect(move || println!("{}", signal.get()));
+///
+/// let graph = inspect_graph();
+/// assert_eq!(graph.count(NodeKind::Effect), 1);
+/// println!("{}", graph.to_dot());
+/// # });
+/// ```
+///
+/// # Panics
+/// Panics if there is no current root.
+pub fn inspect_graph() -> GraphSnapshot {
+    let root = Root::global();
+    let nodes = root.nodes.borrow();
+
+    let mut infos = Vec::with_capacity(nodes.len());
+    let mut visited = SecondaryMap::with_capacity(nodes.len());
+    let mut visit = |start: NodeId| {
+        let mut stack = vec![start];
+        while let Some(id) = stack.pop() {
+            if visited.insert(id, ()).is_some() {
+                continue;
+            }
+            infos.push(node_info(&nodes, id));
+            stack.extend(
+                nodes[id]
+                    .children
+                    .iter()
+                    .rev()
+                    .filter(|id| nodes.contains_key(**id)),
+            );
+        }
+    };
+    // Visit the owner tree of the root scope first, then the trees of any other nodes without a
+    // live owner.
+    visit(root.root_node.get());
+    for (id, node) in nodes.iter() {
+        if !nodes.contains_key(node.parent) {
+            visit(id);
+        }
+    }
+
+    GraphSnapshot { nodes: infos }
+}
+
+fn node_info(nodes: &SlotMap<NodeId, ReactiveNode>, id: NodeId) -> NodeInfo {
+    let node = &nodes[id];
+    // Links to nodes that were already disposed are skipped.
+    let ids = |ids: &[NodeId]| {
+        ids.iter()
+            .filter(|id| nodes.contains_key(**id))
+            .map(|id| id.data().as_ffi())
+            .collect()
+    };
+    NodeInfo {
+        id: id.data().as_ffi(),
+        kind: node.kind,
+        owner: (!node.parent.is_null() && nodes.contains_key(node.parent))
+            .then(|| node.parent.data().as_ffi()),
+        owned: ids(&node.children),
+        dependencies: ids(&node.dependencies),
+        dependents: ids(&node.dependents),
+        #[cfg(debug_assertions)]
+        location: Some(node.created_at.to_string()),
+        #[cfg(not(debug_assertions))]
+        location: None,
+        context_types: node.context_types.clone(),
+        runs: node.runs,
+    }
+}
+
+impl GraphSnapshot {
+    /// Returns the node with the given `id`, if it is in the snapshot.
+    pub fn node(&self, id: u64) -> Option<&NodeInfo> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    /// Returns the number of nodes of the given `kind`.
+    pub fn count(&self, kind: NodeKind) -> usize {
+        self.nodes.iter().filter(|node| node.kind == kind).count()
+    }
+
+    /// Exports the snapshot as JSON.
+    ///
+    /// The output is an object with a `nodes` array. Every node is an object with the same fields
+    /// as [`NodeInfo`], where `kind` is one of `"signal"`, `"memo"`, `"lazy memo"`, `"effect"` or
+    /// `"scope"` and missing values are `null`.
+    pub fn to_json(&self) -> String {
+        let ids = |ids: &[u64]| {
+            let ids = ids.iter().map(u64::to_string).collect::<Vec<_>>();
+            format!("[{}]", ids.join(","))
+        };
+        let mut json = String::from("{\"nodes\":[");
+        for (i, node) in self.nodes.iter().enumerate() {
+            if i != 0 {
+                json.push(',');
+            }
+            let owner = node
+                .owner
+                .map_or_else(|| "null".to_string(), |id| id.to_string());
+            let location = node
+                .location
+                .as_deref()
+                .map_or_else(|| "null".to_string(), json_string);
+            let context_types = node
+                .context_types
+                .iter()
+                .map(|name| json_string(name))
+                .collect::<Vec<_>>();
+            let _ = write!(
+                json,
+                "{{\"id\":{},\"kind\":\"{}\",\"owner\":{owner},\"owned\":{},\"dependencies\":{},\
+                 \"dependents\":{},\"location\":{location},\"context_types\":[{}],\"runs\":{}}}",
+                node.id,
+                node.kind.as_str(),
+                ids(&node.owned),
+                ids(&node.dependencies),
+                ids(&node.dependents),
+                context_types.join(","),
+                node.runs,
+            );
+        }
+        json.push_str("]}");
+        json
+    }
+
+    /// Exports the snapshot as a [Graphviz](https://graphviz.org) DOT graph.
+    ///
+    /// Solid edges point from a node to the nodes that depend on it, i.e. in the direction in
+    /// which updates are propagated. Dashed edges point from an owner to the nodes it owns.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph reactive {\n");
+        for node in &self.nodes {
+            let shape = match node.kind {
+                NodeKind::Signal => "ellipse",
+                NodeKind::Memo | NodeKind::LazyMemo => "diamond",
+                NodeKind::Effect => "box",
+                NodeKind::Scope => "folder",
+            };
+            let mut label = format!("{} {}", node.kind.as_str(), node.id);
+            if node.runs > 0 {
+                let _ = write!(label, "\nruns: {}", node.runs);
+            }
+            if let Some(location) = &node.location {
+                let _ = write!(label, "\n{location}");
+            }
+            for name in &node.context_types {
+                let _ = write!(label, "\ncontext: {name}");
+            }
+            let _ = writeln!(
+                dot,
+                "  n{} [label={}, shape={shape}];",
+                node.id,
+                json_string(&label)
+            );
+        }
+        for node in &self.nodes {
+            for owned in &node.owned {
+                let _ = writeln!(dot, "  n{} -> n{owned} [style=dashed];", node.id);
+            }
+            for dependent in &node.dependents {
+                let _ = writeln!(dot, "  n{} -> n{dependent};", node.id);
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Returns `s` as a quoted and escaped string. This is valid both in JSON and in DOT.
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inspect_kinds_and_edges() {
+        let _ = create_root(|| {
+            provide_context(1u8);
+            let signal = create_signal(1);
+            let double = create_memo(move || signal.get() * 2);
+            create_effect(move || {
+                double.track();
+            });
+            create_child_scope(|| {
+                let _ = create_signal(());
+            });
+
+            let graph = inspect_graph();
+            let kinds = graph.nodes.iter().map(|node| node.kind).collect::<Vec<_>>();
+            assert_eq!(
+                kinds,
+                [
+                    NodeKind::Scope,
+                    NodeKind::Signal,
+                    NodeKind::Memo,
+                    NodeKind::Effect,
+                    NodeKind::Scope,
+                    NodeKind::Signal,
+                ]
+            );
+            let root = &graph.nodes[0];
+            assert_eq!(root.owner, None);
+            assert_eq!(root.context_types, ["u8"]);
+            assert_eq!(root.owned.len(), 4);
+
+            let (signal, memo, effect) = (&graph.nodes[1], &graph.nodes[2], &graph.nodes[3]);
+            assert_eq!(signal.owner, Some(root.id));
+            assert_eq!(signal.dependents, [memo.id]);
+            assert_eq!(memo.dependencies, [signal.id]);
+            assert_eq!(memo.dependents, [effect.id]);
+            assert_eq!(effect.dependencies, [memo.id]);
+            assert_eq!(graph.node(effect.id), Some(effect));
+            #[cfg(debug_assertions)]
+            assert!(signal.location.as_ref().unwrap().contains("inspect.rs"));
+        });
+    }
+
+    #[test]
+    fn inspect_disposed_nodes() {
+        let _ = create_root(|| {
+            let scope = create_child_scope(|| {
+                let _ = create_signal(1);
+            });
+            assert_eq!(inspect_graph().nodes.len(), 3);
+            scope.dispose();
+            let graph = inspect_graph();
+            assert_eq!(graph.nodes.len(), 1);
+            assert_eq!(graph.nodes[0].owned, []);
+        });
+    }
+
+    #[test]
+    fn export_json_and_dot() {
+        let _ = create_root(|| {
+            let signal = create_signal(1);
+            create_effect(move || signal.track());
+            signal.set(2);
+
+            let graph = inspect_graph();
+            let [root, signal, effect] = [0, 1, 2].map(|i| graph.nodes[i].id);
+            let location = |i: usize| {
+                graph.nodes[i]
+                    .location
+                    .as_deref()
+                    .map_or_else(|| "null".to_string(), json_string)
+            };
+
+            assert_eq!(
+                graph.to_json(),
+                format!(
+                    "{{\"nodes\":[\
+                     {{\"id\":{root},\"kind\":\"scope\",\"owner\":null,\"owned\":[{signal},{effect}],\"dependencies\":[],\"dependents\":[],\"location\":{},\"context_types\":[],\"runs\":0}},\
+                     {{\"id\":{signal},\"kind\":\"signal\",\"owner\":{root},\"owned\":[],\"dependencies\":[],\"dependents\":[{effect}],\"location\":{},\"context_types\":[],\"runs\":0}},\
+                     {{\"id\":{effect},\"kind\":\"effect\",\"owner\":{root},\"owned\":[],\"dependencies\":[{signal}],\"dependents\":[],\"location\":{},\"context_types\":[],\"runs\":1}}\
+                     ]}}",
+                    location(0),
+                    location(1),
+                    location(2),
+                )
+            );
+
+            let dot = graph.to_dot();
+            assert!(dot.starts_with("digraph reactive {\n"));
+            assert!(dot.contains(&format!("  n{root} -> n{signal} [style=dashed];\n")));
+            assert!(dot.contains(&format!("  n{signal} -> n{effect};\n")));
+            assert!(dot.contains("runs: 1"));
+        });
+    }
+
+    #[test]
+    fn escape_strings() {
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+    }
+}
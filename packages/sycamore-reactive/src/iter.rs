@@ -1,37 +1,81 @@
 //! Reactive utilities for dealing with lists and iterables.
 
-use std::collections::HashMap;
-use std::hash::Hash;
-use std::mem;
+use core::hash::Hash;
+use core::mem;
 
 use crate::*;
 
+/// A list that can be iterated over with [`map_keyed`] and [`map_indexed`].
+///
+/// This is either a `Vec` that is diffed every time it changes, or a [`ReadSignalVec`] whose
+/// diffs are applied directly.
+pub enum ListSource<T: 'static> {
+    /// A `Vec` that is cloned and diffed every time it changes.
+    Dyn(MaybeDyn<Vec<T>>),
+    /// A [`ReadSignalVec`] whose diffs are applied directly.
+    Vec(ReadSignalVec<T>),
+}
+
+impl<T: Clone + MaybeSend> ListSource<T> {
+    /// Returns a clone of the current values. The list is tracked.
+    pub fn get_clone(&self) -> Vec<T> {
+        match self {
+            Self::Dyn(list) => list.get_clone(),
+            Self::Vec(list) => list.get_clone(),
+        }
+    }
+}
+
+impl<T, L: Into<MaybeDyn<Vec<T>>>> From<L> for ListSource<T> {
+    fn from(list: L) -> Self {
+        Self::Dyn(list.into())
+    }
+}
+
+impl<T> From<ReadSignalVec<T>> for ListSource<T> {
+    fn from(list: ReadSignalVec<T>) -> Self {
+        Self::Vec(list)
+    }
+}
+
+impl<T> From<SignalVec<T>> for ListSource<T> {
+    fn from(list: SignalVec<T>) -> Self {
+        Self::Vec(*list)
+    }
+}
+
 /// Function that maps a `Vec` to another `Vec` via a map function and a key.
 ///
 /// The mapped `Vec` is lazily computed, meaning that it's value will only be updated when
-/// requested. Modifications to the input `Vec` are diffed using keys to prevent recomputing values
-/// that have not changed.
+/// requested. Modifications to the input `Vec` are diffed using keys to prevent
+/// recomputing values that have not changed.
 ///
 /// This function is the underlying utility behind `Keyed`.
 ///
 /// # Params
-/// * `list` - The list to be mapped. The list must be a [`ReadSignal`] (obtained from a [`Signal`])
-///   and therefore reactive.
+/// * `list` - The list to be mapped. The list must be a [`ReadSignal`] (obtained from a
+///   [`Signal`]) and therefore reactive. If the list is a [`SignalVec`], its diffs are
+///   applied directly instead (see [`ReadSignalVec::map_keyed`]).
 /// * `map_fn` - A closure that maps from the input type to the output type.
 /// * `key_fn` - A closure that returns an _unique_ key to each entry.
 ///
 ///  _Credits: Based on TypeScript implementation in <https://github.com/solidjs/solid>_
 pub fn map_keyed<T, K, U>(
-    list: impl Into<MaybeDyn<Vec<T>>> + 'static,
-    mut map_fn: impl FnMut(T) -> U + 'static,
-    key_fn: impl Fn(&T) -> K + 'static,
+    list: impl Into<ListSource<T>> + 'static,
+    mut map_fn: impl FnMut(T) -> U + MaybeSend + 'static,
+    key_fn: impl Fn(&T) -> K + MaybeSend + 'static,
 ) -> ReadSignal<Vec<U>>
 where
-    T: PartialEq + Clone + 'static,
-    K: Eq + Hash,
-    U: Clone,
+    T: PartialEq + Clone + MaybeSend + 'static,
+    K: Eq + Hash + MaybeSend + 'static,
+    U: Clone + MaybeSend + 'static,
 {
-    let list = list.into();
+    let list = match list.into() {
+        ListSource::Dyn(list) => list,
+        ListSource::Vec(list) => {
+            return *map_signal_vec::<_, _, _, Signal<Vec<U>>>(list, map_fn, Some(key_fn))
+        }
+    };
     // Previous state used for diffing.
     let mut items = Vec::new();
 
@@ -179,8 +223,8 @@ where
 /// Function that maps a `Vec` to another `Vec` via a map function.
 ///
 /// The mapped `Vec` is lazily computed, meaning that it's value will only be updated when
-/// requested. Modifications to the input `Vec` are diffed by index to prevent recomputing values
-/// that have not changed.
+/// requested. Modifications to the input `Vec` are diffed by index to prevent recomputing
+/// values that have not changed.
 ///
 /// Generally, it is preferred to use [`map_keyed`] instead when a key function
 /// is available.
@@ -188,18 +232,24 @@ where
 /// This function is the underlying utility behind `Indexed`.
 ///
 /// # Params
-/// * `list` - The list to be mapped. The list must be a [`ReadSignal`] (obtained from a [`Signal`])
-///   and therefore reactive.
+/// * `list` - The list to be mapped. The list must be a [`ReadSignal`] (obtained from a
+///   [`Signal`]) and therefore reactive. If the list is a [`SignalVec`], its diffs are
+///   applied directly instead (see [`ReadSignalVec::map_indexed`]).
 /// * `map_fn` - A closure that maps from the input type to the output type.
 pub fn map_indexed<T, U>(
-    list: impl Into<MaybeDyn<Vec<T>>> + 'static,
-    mut map_fn: impl FnMut(T) -> U + 'static,
+    list: impl Into<ListSource<T>> + 'static,
+    mut map_fn: impl FnMut(T) -> U + MaybeSend + 'static,
 ) -> ReadSignal<Vec<U>>
 where
-    T: PartialEq + Clone + 'static,
-    U: Clone,
+    T: PartialEq + Clone + MaybeSend + 'static,
+    U: Clone + MaybeSend + 'static,
 {
-    let list = list.into();
+    let list = match list.into() {
+        ListSource::Dyn(list) => list,
+        ListSource::Vec(list) => {
+            return *map_signal_vec::<_, _, _, Signal<Vec<U>>>(list, map_fn, None::<fn(&T)>)
+        }
+    };
     // Previous state used for diffing.
     let mut items = Vec::new();
     let mut mapped = Vec::new();
@@ -266,9 +316,209 @@ where
     create_memo(move || scope.run_in(&mut update))
 }
 
+impl<T: Clone + MaybeSend> ReadSignalVec<T> {
+    /// Maps every value to a new [`ReadSignalVec`] using a map function and a key.
+    ///
+    /// Unlike [`map_keyed`] with a `Vec`, the values are never cloned or diffed. Instead, every
+    /// change to this `Vec` is applied directly to the mapped `Vec`. Values are only mapped again
+    /// when they are replaced with a value that has a different key. The mapped values are
+    /// updated eagerly.
+    ///
+    /// This is the underlying utility behind `Keyed` when it is passed a [`SignalVec`].
+    pub fn map_keyed<K, U>(
+        self,
+        map_fn: impl FnMut(T) -> U + MaybeSend + 'static,
+        key_fn: impl Fn(&T) -> K + MaybeSend + 'static,
+    ) -> ReadSignalVec<U>
+    where
+        K: Eq + Hash + MaybeSend + 'static,
+        U: Clone + MaybeSend + 'static,
+    {
+        *map_signal_vec::<_, _, _, SignalVec<U>>(self, map_fn, Some(key_fn))
+    }
+
+    /// Maps every value to a new [`ReadSignalVec`] using a map function.
+    ///
+    /// Unlike [`map_indexed`] with a `Vec`, the values are never cloned or diffed. Instead, every
+    /// change to this `Vec` is applied directly to the mapped `Vec`. The mapped values are updated
+    /// eagerly.
+    ///
+    /// This is the underlying utility behind `Indexed` when it is passed a [`SignalVec`].
+    pub fn map_indexed<U>(
+        self,
+        map_fn: impl FnMut(T) -> U + MaybeSend + 'static,
+    ) -> ReadSignalVec<U>
+    where
+        U: Clone + MaybeSend + 'static,
+    {
+        *map_signal_vec::<_, _, _, SignalVec<U>>(self, map_fn, None::<fn(&T)>)
+    }
+}
+
+/// The output of [`map_signal_vec`]. This is either a [`SignalVec`] that records the changes, or a
+/// plain `Signal<Vec<U>>` that is updated in place without cloning the whole `Vec`.
+trait MappedVec<U>: Copy + 'static {
+    fn create(values: Vec<U>) -> Self;
+    fn apply(self, diff: VecDiff<U>);
+    fn get_clone_untracked(self) -> Vec<U>;
+}
+
+impl<U: Clone + MaybeSend> MappedVec<U> for SignalVec<U> {
+    fn create(values: Vec<U>) -> Self {
+        create_signal_vec(values)
+    }
+
+    fn apply(self, diff: VecDiff<U>) {
+        SignalVec::apply(self, diff);
+    }
+
+    fn get_clone_untracked(self) -> Vec<U> {
+        ReadSignalVec::get_clone_untracked(*self)
+    }
+}
+
+impl<U: Clone + MaybeSend> MappedVec<U> for Signal<Vec<U>> {
+    fn create(values: Vec<U>) -> Self {
+        create_signal(values)
+    }
+
+    fn apply(self, diff: VecDiff<U>) {
+        self.update(|items| diff.apply_to(items));
+    }
+
+    fn get_clone_untracked(self) -> Vec<U> {
+        self.with_untracked(|items| items.clone())
+    }
+}
+
+/// Implementation of [`ReadSignalVec::map_keyed`] and [`ReadSignalVec::map_indexed`]. Values are
+/// only compared by key if there is a `key_fn`.
+fn map_signal_vec<T, K, U, M>(
+    list: ReadSignalVec<T>,
+    mut map_fn: impl FnMut(T) -> U + MaybeSend + 'static,
+    key_fn: Option<impl Fn(&T) -> K + MaybeSend + 'static>,
+) -> M
+where
+    T: Clone + MaybeSend + 'static,
+    K: Eq + Hash + MaybeSend + 'static,
+    U: Clone + MaybeSend + 'static,
+    M: MappedVec<U> + MaybeSend,
+{
+    // Map the values in the outer scope so that they are not disposed when the effect re-runs.
+    let scope = use_current_scope();
+    let mut map_value = move |value: T| {
+        let mut tmp = None;
+        let disposer = scope.run_in(|| create_child_scope(|| tmp = Some(map_fn(value))));
+        (tmp.unwrap(), disposer)
+    };
+    let key = move |value: &T| key_fn.as_ref().map(|key_fn| key_fn(value));
+
+    let diffs = list.subscribe();
+    let values = list.get_clone_untracked();
+    let mut keys = values.iter().map(&key).collect::<Vec<_>>();
+    let (values, mut disposers): (Vec<_>, Vec<_>) = values.into_iter().map(&mut map_value).unzip();
+    let mapped = M::create(values);
+
+    create_effect(move || {
+        let diffs = diffs.take();
+        // Notify the subscribers of the mapped `Vec` once all the diffs have been applied.
+        untrack(|| {
+            batch(|| {
+                for diff in diffs {
+                    match diff {
+                        VecDiff::Push(value) => {
+                            keys.push(key(&value));
+                            let (value, disposer) = map_value(value);
+                            disposers.push(disposer);
+                            mapped.apply(VecDiff::Push(value));
+                        }
+                        VecDiff::Pop => {
+                            keys.pop();
+                            disposers.pop().unwrap().dispose();
+                            mapped.apply(VecDiff::Pop);
+                        }
+                        VecDiff::Insert { index, value } => {
+                            keys.insert(index, key(&value));
+                            let (value, disposer) = map_value(value);
+                            disposers.insert(index, disposer);
+                            mapped.apply(VecDiff::Insert { index, value });
+                        }
+                        VecDiff::Remove { index } => {
+                            keys.remove(index);
+                            disposers.remove(index).dispose();
+                            mapped.apply(VecDiff::Remove { index });
+                        }
+                        VecDiff::Swap { a, b } => {
+                            keys.swap(a, b);
+                            disposers.swap(a, b);
+                            mapped.apply(VecDiff::Swap { a, b });
+                        }
+                        VecDiff::Set { index, value } => {
+                            let new_key = key(&value);
+                            // Keep the mapped value if the key did not change.
+                            if new_key.is_none() || new_key != keys[index] {
+                                keys[index] = new_key;
+                                let (value, disposer) = map_value(value);
+                                mem::replace(&mut disposers[index], disposer).dispose();
+                                mapped.apply(VecDiff::Set { index, value });
+                            }
+                        }
+                        VecDiff::Clear => {
+                            keys.clear();
+                            for disposer in disposers.drain(..) {
+                                disposer.dispose();
+                            }
+                            mapped.apply(VecDiff::Clear);
+                        }
+                        VecDiff::Replace(values) => {
+                            // Reuse the mapped values of the keys that are still present.
+                            let old_values = mapped.get_clone_untracked();
+                            let mut old = HashMap::new();
+                            for (key, (value, disposer)) in mem::take(&mut keys)
+                                .into_iter()
+                                .zip(old_values.into_iter().zip(disposers.drain(..)))
+                            {
+                                match key {
+                                    Some(key) => {
+                                        if let Some((_, prev)) = old.insert(key, (value, disposer))
+                                        {
+                                            prev.dispose();
+                                        }
+                                    }
+                                    None => disposer.dispose(),
+                                }
+                            }
+                            let mut new_values = Vec::with_capacity(values.len());
+                            for value in values {
+                                let value_key = key(&value);
+                                let (value, disposer) =
+                                    match value_key.as_ref().and_then(|k| old.remove(k)) {
+                                        Some(old) => old,
+                                        None => map_value(value),
+                                    };
+                                keys.push(value_key);
+                                disposers.push(disposer);
+                                new_values.push(value);
+                            }
+                            for (_, disposer) in old.into_values() {
+                                disposer.dispose();
+                            }
+                            mapped.apply(VecDiff::Replace(new_values));
+                        }
+                    }
+                }
+            })
+        });
+    });
+
+    mapped
+}
+
 #[cfg(test)]
 mod tests {
+    #[cfg(not(feature = "sync"))]
     use std::cell::Cell;
+    #[cfg(not(feature = "sync"))]
     use std::rc::Rc;
 
     use super::*;
@@ -314,6 +564,7 @@ mod tests {
 
     /// Test that using [`Scope::map_keyed`] will reuse previous computations.
     #[test]
+    #[cfg(not(feature = "sync"))]
     fn keyed_use_previous_computation() {
         let _ = create_root(|| {
             let a = create_signal(vec![1, 2, 3]);
@@ -343,6 +594,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "sync"))]
     fn keyed_call_cleanup_on_remove() {
         let _ = create_root(|| {
             let a = create_signal(vec![1, 2, 3]);
@@ -374,6 +626,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "sync"))]
     fn keyed_call_cleanup_on_remove_all() {
         let _ = create_root(|| {
             let a = create_signal(vec![1, 2, 3]);
@@ -446,6 +699,7 @@ mod tests {
 
     /// Test that using [`map_indexed`] will reuse previous computations.
     #[test]
+    #[cfg(not(feature = "sync"))]
     fn indexed_use_previous_computation() {
         let _ = create_root(|| {
             let a = create_signal(vec![1, 2, 3]);
@@ -471,6 +725,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "sync"))]
     fn indexed_call_cleanup_on_remove() {
         let _ = create_root(|| {
             let a = create_signal(vec![1, 2, 3]);
@@ -498,6 +753,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "sync"))]
     fn indexed_call_cleanup_on_remove_all() {
         let _ = create_root(|| {
             let a = create_signal(vec![1, 2, 3]);
@@ -517,4 +773,139 @@ mod tests {
             assert_eq!(counter.get(), 3);
         });
     }
+
+    #[test]
+    #[cfg(not(feature = "sync"))]
+    fn keyed_signal_vec() {
+        let _ = create_root(|| {
+            let a = create_signal_vec(vec![1, 2, 3]);
+            let calls = Rc::new(Cell::new(0));
+            let mapped = map_keyed(
+                a,
+                {
+                    let calls = Rc::clone(&calls);
+                    move |x| {
+                        calls.set(calls.get() + 1);
+                        x * 2
+                    }
+                },
+                |x| *x,
+            );
+            assert_eq!(mapped.get_clone(), vec![2, 4, 6]);
+
+            a.push(4);
+            a.insert(0, 0);
+            a.swap(1, 2);
+            a.remove(3);
+            assert_eq!(mapped.get_clone(), vec![0, 4, 2, 8]);
+            // Only the new values were mapped.
+            assert_eq!(calls.get(), 5);
+
+            a.set(0, 0);
+            assert_eq!(calls.get(), 5, "same key is not mapped again");
+            a.set(0, 5);
+            assert_eq!(mapped.get_clone(), vec![10, 4, 2, 8]);
+            assert_eq!(calls.get(), 6);
+
+            a.replace(vec![8, 4, 7]);
+            assert_eq!(mapped.get_clone(), vec![16, 8, 14]);
+            assert_eq!(calls.get(), 8, "only new keys are mapped");
+
+            a.clear();
+            assert_eq!(mapped.get_clone(), Vec::<i32>::new());
+        });
+    }
+
+    #[test]
+    fn keyed_signal_vec_does_not_clone_mapped_values() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(PartialEq)]
+        struct Value(i32);
+
+        impl Clone for Value {
+            fn clone(&self) -> Self {
+                CLONES.fetch_add(1, Ordering::SeqCst);
+                Self(self.0)
+            }
+        }
+
+        let _ = create_root(|| {
+            let a = create_signal_vec(vec![1, 2]);
+            let mapped = map_keyed(a, Value, |x| *x);
+
+            a.push(3);
+            a.remove(0);
+            let values = mapped.with(|mapped| mapped.iter().map(|x| x.0).collect::<Vec<_>>());
+            assert_eq!(values, vec![2, 3]);
+            assert_eq!(CLONES.load(Ordering::SeqCst), 0);
+        });
+    }
+
+    #[test]
+    fn indexed_signal_vec() {
+        let _ = create_root(|| {
+            let a = create_signal_vec(vec![1, 2, 3]);
+            let mapped = map_indexed(a, |x| x * 2);
+            assert_eq!(mapped.get_clone(), vec![2, 4, 6]);
+
+            a.set(1, 5);
+            a.pop();
+            a.push(1);
+            assert_eq!(mapped.get_clone(), vec![2, 10, 2]);
+
+            a.replace(vec![3]);
+            assert_eq!(mapped.get_clone(), vec![6]);
+        });
+    }
+
+    #[test]
+    #[cfg(not(feature = "sync"))]
+    fn signal_vec_call_cleanup_on_remove() {
+        let _ = create_root(|| {
+            let a = create_signal_vec(vec![1, 2, 3, 4]);
+            let counter = Rc::new(Cell::new(0));
+            let _mapped = a.map_keyed(
+                {
+                    let counter = Rc::clone(&counter);
+                    move |_| {
+                        let counter = Rc::clone(&counter);
+                        on_cleanup(move || {
+                            counter.set(counter.get() + 1);
+                        });
+                    }
+                },
+                |x| *x,
+            );
+            assert_eq!(counter.get(), 0, "no cleanup yet");
+
+            a.remove(1);
+            assert_eq!(counter.get(), 1);
+
+            a.swap(0, 2);
+            a.set(1, 3);
+            assert_eq!(counter.get(), 1, "same key is not cleaned up");
+
+            a.replace(vec![4, 5]);
+            assert_eq!(counter.get(), 3);
+
+            a.clear();
+            assert_eq!(counter.get(), 5);
+        });
+    }
+
+    #[test]
+    fn signal_vec_map_does_not_track_map_fn() {
+        let _ = create_root(|| {
+            let a = create_signal_vec(vec![1]);
+            let factor = create_signal(2);
+            let mapped = a.map_indexed(move |x| x * factor.get());
+
+            a.push(2);
+            factor.set(3);
+            assert_eq!(mapped.get_clone(), vec![2, 4]);
+        });
+    }
 }
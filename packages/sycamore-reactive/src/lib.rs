@@ -35,29 +35,126 @@
 //! # });
 //! ```
 //! Of course, the stable `.get()` also works on nightly as well if that's what you prefer.
+//!
+//! # A note on `sync`
+//!
+//! By default, the reactive system is single threaded. Enabling the `sync` feature makes
+//! [`RootHandle`], signals, and the other handles `Send + Sync`, so that a root can be moved to
+//! another thread between updates, e.g. by a work-stealing async runtime. In exchange, every value
+//! and closure that is stored in the reactive graph must be `Send` (see [`MaybeSend`]).
+//!
+//! This does not make everything that uses the reactive system `Send`. For instance, async
+//! components are still run on a `tokio::task::LocalSet`. The `sync` feature of `sycamore-web`
+//! runs them on a blocking thread so that the server side rendering futures are `Send`.
+//!
+//! The current root is still stored in a thread-local. Functions such as [`create_signal`] and
+//! [`batch`] must be called inside [`RootHandle::run_in`] on other threads. The state of a root is
+//! protected by a lock, so only one thread can access a root at a time.
+//!
+//! ```ignore
+//! # use sycamore_reactive::*;
+//! let mut count = None;
+//! let root = create_root(|| count = Some(create_signal(0)));
+//! let count = count.unwrap();
+//!
+//! std::thread::spawn(move || {
+//!     root.run_in(|| batch(|| count.set(1)));
+//! })
+//! .join()
+//! .unwrap();
+//! assert_eq!(count.get(), 1);
+//! ```
+//!
+//! `create_effect_initial` is not available with this feature. On `wasm32` without the `atomics`
+//! target feature, there is only one thread, so this feature has no effect. This allows enabling
+//! it for an app that is rendered both on a multi-threaded server and in the browser.
+//!
+//! # A note on `inspect`
+//!
+//! The `inspect` feature enables [`inspect_graph`] for taking a snapshot of the reactive graph.
+//! Without it, the nodes do not keep track of their kind, of how many times they ran, or of the
+//! type names of their context values.
+//!
+//! # A note on `no_std`
+//!
+//! This crate can be used without `std` (but with `alloc`) by disabling the default `std` feature.
+//! Without `std`, there are no thread-locals for storing the current root. Instead, a
+//! `RootStorage` must be installed with `set_root_storage` before creating a root. In
+//! single-threaded environments, `RootStorage::SINGLE_THREADED` can be used. These items are only
+//! available without the `std` feature.
+//!
+//! ```ignore
+//! # use sycamore_reactive::*;
+//! // SAFETY: The reactive system is only used from the main loop.
+//! unsafe { set_root_storage(&RootStorage::SINGLE_THREADED) };
+//!
+//! let _ = create_root(|| {
+//!     let count = create_signal(0);
+//!     create_effect(move || update_display(count.get()));
+//! });
+//! ```
+//!
+//! Panics cannot be caught without `std`, so panics inside effects and memos are never reported to
+//! error handlers and [`catch_panic`] always returns `Ok`.
 
 #![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(feature = "nightly", feature(fn_traits, unboxed_closures))]
 
+extern crate alloc;
+// The test harness always needs `std`.
+#[cfg(all(test, not(feature = "std")))]
+extern crate std;
+
+mod collections;
 mod context;
 mod effects;
+mod errors;
+mod history;
+#[cfg(feature = "inspect")]
+mod inspect;
 mod iter;
 mod maybe_dyn;
 mod memos;
 mod node;
 mod root;
 mod signals;
+mod store;
+mod sync;
+mod transaction;
 mod utils;
 
+pub use collections::*;
 pub use context::*;
 pub use effects::*;
+pub use errors::*;
+pub use history::*;
+#[cfg(feature = "inspect")]
+pub use inspect::*;
 pub use iter::*;
 pub use maybe_dyn::*;
 pub use memos::*;
 pub use node::*;
 pub use root::*;
 pub use signals::*;
+pub use store::*;
+pub(crate) use sync::{
+    AnyValue, CleanupFn, NodeCallback, RestoreFn, Shared, SharedCell, WeakShared,
+};
+pub use sync::{MaybeSend, MaybeSync};
+pub use transaction::*;
 pub use utils::*;
 
+// Types that are not in the prelude without `std`.
+pub(crate) use alloc::boxed::Box;
+pub(crate) use alloc::string::{String, ToString};
+pub(crate) use alloc::vec::Vec;
+#[allow(unused_imports)] // `format` is only used in debug builds or with `inspect`.
+pub(crate) use alloc::{format, vec};
+#[cfg(not(feature = "std"))]
+pub(crate) use hashbrown::{HashMap, HashSet};
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{HashMap, HashSet};
+
 /// Add name for proc-macro purposes.
 extern crate self as sycamore_reactive;
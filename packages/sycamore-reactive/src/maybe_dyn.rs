@@ -1,8 +1,12 @@
-use std::borrow::Cow;
-use std::rc::Rc;
+use alloc::borrow::Cow;
 
 use crate::*;
 
+#[cfg(sycamore_sync)]
+type DerivedFn<T> = dyn Fn() -> MaybeDyn<T> + Send + Sync;
+#[cfg(not(sycamore_sync))]
+type DerivedFn<T> = dyn Fn() -> MaybeDyn<T>;
+
 /// Represents a value that can be either static or dynamic.
 ///
 /// This is useful for cases where you want to accept a value that can be either static or dynamic,
@@ -30,7 +34,7 @@ where
     /// A dynamic value backed by a signal.
     Signal(ReadSignal<T>),
     /// A derived dynamic value.
-    Derived(Rc<dyn Fn() -> Self>),
+    Derived(Shared<DerivedFn<T>>),
 }
 
 impl<T: Into<Self> + 'static> MaybeDyn<T> {
@@ -38,7 +42,7 @@ impl<T: Into<Self> + 'static> MaybeDyn<T> {
     /// a clone if we are just storing a static value.
     pub fn evaluate(self) -> T
     where
-        T: Clone,
+        T: Clone + MaybeSend,
     {
         match self {
             Self::Static(value) => value,
@@ -53,7 +57,7 @@ impl<T: Into<Self> + 'static> MaybeDyn<T> {
     /// instead.
     pub fn get(&self) -> T
     where
-        T: Copy,
+        T: Copy + MaybeSend,
     {
         match self {
             Self::Static(value) => *value,
@@ -67,7 +71,7 @@ impl<T: Into<Self> + 'static> MaybeDyn<T> {
     /// If the type implements [`Copy`], consider using [`get`](Self::get) instead.
     pub fn get_clone(&self) -> T
     where
-        T: Clone,
+        T: Clone + MaybeSend,
     {
         match self {
             Self::Static(value) => value.clone(),
@@ -94,23 +98,23 @@ impl<T: Into<Self> + 'static> MaybeDyn<T> {
     }
 }
 
-impl<T: Into<Self>, U: Into<MaybeDyn<T>> + Clone> From<ReadSignal<U>> for MaybeDyn<T> {
+impl<T: Into<Self>, U: Into<MaybeDyn<T>> + Clone + MaybeSend> From<ReadSignal<U>> for MaybeDyn<T> {
     fn from(val: ReadSignal<U>) -> Self {
         // Check if U == T, i.e. ReadSignal<U> is actually a ReadSignal<T>.
         //
         // If so, we use a trick to convert the generic type to the concrete type. This should be
         // optimized out by the compiler to be zero-cost.
         if let Some(val) =
-            (&mut Some(val) as &mut dyn std::any::Any).downcast_mut::<Option<ReadSignal<T>>>()
+            (&mut Some(val) as &mut dyn core::any::Any).downcast_mut::<Option<ReadSignal<T>>>()
         {
             MaybeDyn::Signal(val.unwrap())
         } else {
-            MaybeDyn::Derived(Rc::new(move || val.get_clone().into()))
+            MaybeDyn::Derived(Shared::new(move || val.get_clone().into()))
         }
     }
 }
 
-impl<T: Into<Self>, U: Into<MaybeDyn<T>> + Clone> From<Signal<U>> for MaybeDyn<T> {
+impl<T: Into<Self>, U: Into<MaybeDyn<T>> + Clone + MaybeSend> From<Signal<U>> for MaybeDyn<T> {
     fn from(val: Signal<U>) -> Self {
         Self::from(*val)
     }
@@ -119,11 +123,11 @@ impl<T: Into<Self>, U: Into<MaybeDyn<T>> + Clone> From<Signal<U>> for MaybeDyn<T
 // TODO: add #[diagnostic::do_not_recommend] when it is stablised.
 impl<F, U, T: Into<Self>> From<F> for MaybeDyn<T>
 where
-    F: Fn() -> U + 'static,
+    F: Fn() -> U + MaybeSend + MaybeSync + 'static,
     U: Into<MaybeDyn<T>>,
 {
     fn from(f: F) -> Self {
-        MaybeDyn::Derived(Rc::new(move || f().into()))
+        MaybeDyn::Derived(Shared::new(move || f().into()))
     }
 }
 
@@ -191,6 +195,7 @@ impl_into_maybe_dyn_with_convert!(
     Option<&'static str>, Option<String>
 );
 
+impl_into_maybe_dyn!(());
 impl_into_maybe_dyn!(bool);
 
 impl_into_maybe_dyn!(f32);
@@ -1,8 +1,10 @@
 //! Memos (aka. eager derived signals).
 
-use std::cell::RefCell;
+use core::cell::RefCell;
 
-use crate::{create_empty_signal, create_signal, ReadSignal, Root};
+#[cfg(feature = "inspect")]
+use crate::NodeKind;
+use crate::{create_empty_signal, create_signal, Box, MaybeSend, NodeState, ReadSignal, Root};
 
 /// Creates a memoized value from some signals.
 /// Unlike [`create_memo`], this function will not notify dependents of a
@@ -14,9 +16,9 @@ use crate::{create_empty_signal, create_signal, ReadSignal, Root};
 /// To use the type's [`PartialEq`] implementation instead of a custom function, use
 /// [`create_selector`].
 #[cfg_attr(debug_assertions, track_caller)]
-pub fn create_selector_with<T>(
-    mut f: impl FnMut() -> T + 'static,
-    mut eq: impl FnMut(&T, &T) -> bool + 'static,
+pub fn create_selector_with<T: MaybeSend>(
+    mut f: impl FnMut() -> T + MaybeSend + 'static,
+    mut eq: impl FnMut(&T, &T) -> bool + MaybeSend + 'static,
 ) -> ReadSignal<T> {
     let root = Root::global();
     let signal = create_empty_signal();
@@ -27,6 +29,10 @@ pub fn create_selector_with<T>(
     tracker.create_dependency_link(root, signal.id);
 
     let mut signal_mut = signal.get_mut();
+    #[cfg(feature = "inspect")]
+    {
+        signal_mut.kind = NodeKind::Memo;
+    }
     signal_mut.value = Some(Box::new(initial));
     signal_mut.callback = Some(Box::new(move |value| {
         let value = value.downcast_mut().expect("wrong memo type");
@@ -86,10 +92,64 @@ pub fn create_selector_with<T>(
 /// # });
 /// ```
 #[cfg_attr(debug_assertions, track_caller)]
-pub fn create_memo<T>(f: impl FnMut() -> T + 'static) -> ReadSignal<T> {
+pub fn create_memo<T: MaybeSend>(f: impl FnMut() -> T + MaybeSend + 'static) -> ReadSignal<T> {
     create_selector_with(f, |_, _| false)
 }
 
+/// Creates a memoized computation that is only computed when it is read.
+///
+/// Unlike [`create_memo`], the computation is not run when the memo is created. When one of its
+/// dependencies changes, the memo is only marked as dirty, and is computed again the next time it
+/// is read. The dependents of the memo are still updated, since its value might have changed.
+///
+/// This is useful for expensive derived values that are not always needed, e.g. data that is only
+/// displayed in a hidden tab. Like other memos, the result is cached until one of the dependencies
+/// changes, and the memo is disposed along with the scope that created it.
+///
+/// # Example
+/// ```
+/// # use sycamore_reactive::*;
+/// # create_root(|| {
+/// let state = create_signal(1);
+/// let runs = create_signal(0);
+/// let double = create_lazy_memo(move || {
+///     runs.set_silent(runs.get_untracked() + 1);
+///     state.get() * 2
+/// });
+/// assert_eq!(runs.get(), 0);
+///
+/// state.set(2);
+/// state.set(3);
+/// assert_eq!(runs.get(), 0);
+///
+/// assert_eq!(double.get(), 6);
+/// assert_eq!(double.get(), 6);
+/// assert_eq!(runs.get(), 1);
+/// # });
+/// ```
+#[cfg_attr(debug_assertions, track_caller)]
+pub fn create_lazy_memo<T: MaybeSend + 'static>(
+    mut f: impl FnMut() -> T + MaybeSend + 'static,
+) -> ReadSignal<T> {
+    let signal = create_empty_signal();
+    let mut signal_mut = signal.get_mut();
+    #[cfg(feature = "inspect")]
+    {
+        signal_mut.kind = NodeKind::LazyMemo;
+    }
+    signal_mut.lazy = true;
+    signal_mut.state = NodeState::Dirty;
+    // Placeholder until the memo is first computed. The callback replaces the whole value.
+    signal_mut.value = Some(Box::new(()));
+    signal_mut.callback = Some(Box::new(move |value| {
+        *value = Box::new(f());
+        true
+    }));
+    drop(signal_mut);
+
+    *signal
+}
+
 /// Creates a memoized value from some signals.
 ///
 /// Unlike [`create_memo`], this function will not notify dependents of a hange if the output is the
@@ -115,9 +175,9 @@ pub fn create_memo<T>(f: impl FnMut() -> T + 'static) -> ReadSignal<T> {
 /// # });
 /// ```
 #[cfg_attr(debug_assertions, track_caller)]
-pub fn create_selector<T>(f: impl FnMut() -> T + 'static) -> ReadSignal<T>
+pub fn create_selector<T>(f: impl FnMut() -> T + MaybeSend + 'static) -> ReadSignal<T>
 where
-    T: PartialEq,
+    T: PartialEq + MaybeSend,
 {
     create_selector_with(f, PartialEq::eq)
 }
@@ -156,13 +216,13 @@ where
 /// # });
 /// ```
 #[cfg_attr(debug_assertions, track_caller)]
-pub fn create_reducer<T, Msg>(
+pub fn create_reducer<T: MaybeSend, Msg>(
     initial: T,
     reduce: impl FnMut(&T, Msg) -> T,
 ) -> (ReadSignal<T>, impl Fn(Msg)) {
     let reduce = RefCell::new(reduce);
     let signal = create_signal(initial);
-    let dispatch = move |msg| signal.update(|value| *value = reduce.borrow_mut()(value, msg));
+    let dispatch = move |msg| signal.set_fn(|value| reduce.borrow_mut()(value, msg));
     (*signal, dispatch)
 }
 
@@ -294,6 +354,85 @@ mod tests {
         });
     }
 
+    #[test]
+    fn lazy_memo() {
+        let _ = create_root(|| {
+            let state = create_signal(0);
+            let counter = create_signal(0);
+            let double = create_lazy_memo(move || {
+                counter.set_silent(counter.get_untracked() + 1);
+                state.get() * 2
+            });
+            assert_eq!(counter.get(), 0);
+
+            assert_eq!(double.get(), 0);
+            assert_eq!(counter.get(), 1);
+
+            state.set(1);
+            state.set(2);
+            assert_eq!(counter.get(), 1);
+            assert_eq!(double.get(), 4);
+            assert_eq!(double.get(), 4);
+            assert_eq!(counter.get(), 2);
+        });
+    }
+
+    #[test]
+    fn lazy_memo_updates_dependents() {
+        let _ = create_root(|| {
+            let state = create_signal(0);
+            let double = create_lazy_memo(move || state.get() * 2);
+            let quadruple = create_memo(move || double.get() * 2);
+            let effect_value = create_signal(0);
+            create_effect(move || effect_value.set(double.get() + quadruple.get()));
+
+            assert_eq!(effect_value.get(), 0);
+            state.set(1);
+            assert_eq!(quadruple.get(), 4);
+            assert_eq!(effect_value.get(), 6);
+        });
+    }
+
+    #[test]
+    fn lazy_memo_read_before_its_turn() {
+        let _ = create_root(|| {
+            let state = create_signal(0);
+            let double = create_lazy_memo(move || state.get() * 2);
+            let dependent = create_memo(move || double.get());
+            // This memo runs before `double` during propagation since it does not depend on it.
+            let reader = create_memo(move || {
+                state.track();
+                double.get_untracked()
+            });
+
+            state.set(1);
+            assert_eq!(reader.get(), 2);
+            assert_eq!(dependent.get(), 2);
+        });
+    }
+
+    #[test]
+    fn lazy_memo_disposed_with_scope() {
+        let _ = create_root(|| {
+            let state = create_signal(0);
+            let counter = create_signal(0);
+            let mut double = None;
+            let scope = create_child_scope(|| {
+                double = Some(create_lazy_memo(move || {
+                    counter.set_silent(counter.get_untracked() + 1);
+                    state.get() * 2
+                }));
+            });
+            let double = double.unwrap();
+            assert_eq!(double.get(), 0);
+
+            scope.dispose();
+            assert!(!double.is_alive());
+            state.set(1);
+            assert_eq!(counter.get(), 1);
+        });
+    }
+
     #[test]
     fn selector() {
         let _ = create_root(|| {
@@ -1,24 +1,26 @@
 //! Reactive nodes.
 
-use std::any::Any;
-
 use slotmap::new_key_type;
 use smallvec::SmallVec;
 
-use crate::{untrack_in_scope, Root};
+#[cfg(feature = "inspect")]
+use crate::NodeKind;
+use crate::{untrack_in_scope, AnyValue, Box, CleanupFn, NodeCallback, Root, Vec};
 
 new_key_type! {
     pub(crate) struct NodeId;
 }
 
+/// Clones the value of a signal into a new box.
+pub(crate) type CloneFn = fn(&AnyValue) -> Box<AnyValue>;
+
 /// A reactive node inside the reactive grpah.
 pub(crate) struct ReactiveNode {
     /// Value of the node, if any. If this node is a signal, should have a value.
-    pub value: Option<Box<dyn Any>>,
+    pub value: Option<Box<AnyValue>>,
     /// Callback when node needs to be updated. Returns a `bool` indicating whether the value has
     /// changed or not.
-    #[allow(clippy::type_complexity)]
-    pub callback: Option<Box<dyn FnMut(&mut Box<dyn Any>) -> bool>>,
+    pub callback: Option<Box<NodeCallback>>,
     /// Nodes that are owned by this node.
     pub children: Vec<NodeId>,
     /// The parent of this node (i.e. the node that owns this node). If there is no parent, then
@@ -29,17 +31,32 @@ pub(crate) struct ReactiveNode {
     /// Nodes that this node depends on.
     pub dependencies: SmallVec<[NodeId; 1]>,
     /// Callbacks called when node is disposed.
-    pub cleanups: Vec<Box<dyn FnOnce()>>,
+    pub cleanups: Vec<Box<CleanupFn>>,
     /// Context values stored in this node.
-    pub context: Vec<Box<dyn Any>>,
+    pub context: Vec<Box<AnyValue>>,
+    /// The type names of the context values, in the same order as `context`.
+    #[cfg(feature = "inspect")]
+    pub context_types: Vec<&'static str>,
+    /// What kind of node this is. Only used for inspecting the reactive graph.
+    #[cfg(feature = "inspect")]
+    pub kind: NodeKind,
+    /// How many times the callback was run. Only used for inspecting the reactive graph.
+    #[cfg(feature = "inspect")]
+    pub runs: u64,
+    /// Clones the value of a signal, so that a transaction can save the value before it is updated
+    /// in place. This is set when the type of the value is known to implement `Clone`, i.e. for
+    /// signals of primitive types and `String`s, [`History`] and the signals that it tracks.
+    pub clone_value: Option<CloneFn>,
+    /// Whether this node is a lazy memo, i.e. it is only updated once it is read.
+    pub lazy: bool,
     /// Used for keeping track of dirty state of node value.
     pub state: NodeState,
     /// Used for DFS traversal of the reactive graph.
     pub mark: Mark,
     /// Keep track of where the signal was created for diagnostics.
     #[cfg(debug_assertions)]
-    #[allow(dead_code)]
-    pub created_at: &'static std::panic::Location<'static>,
+    #[cfg_attr(not(feature = "inspect"), allow(dead_code))]
+    pub created_at: &'static core::panic::Location<'static>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -91,8 +108,8 @@ impl NodeHandle {
         if self.1.nodes.borrow().get(self.0).is_none() {
             return;
         }
-        let cleanup = std::mem::take(&mut self.1.nodes.borrow_mut()[self.0].cleanups);
-        let children = std::mem::take(&mut self.1.nodes.borrow_mut()[self.0].children);
+        let cleanup = core::mem::take(&mut self.1.nodes.borrow_mut()[self.0].cleanups);
+        let children = core::mem::take(&mut self.1.nodes.borrow_mut()[self.0].children);
 
         // Run the cleanup functions in an untracked scope so that we don't track dependencies.
         untrack_in_scope(
@@ -111,6 +128,7 @@ impl NodeHandle {
     /// Run a closure under this reactive node.
     pub fn run_in<T>(&self, f: impl FnOnce() -> T) -> T {
         let root = self.1;
+        let _guard = root.lock.lock();
         let prev_root = Root::set_global(Some(root));
         let prev_node = root.current_node.replace(self.0);
         let ret = f();
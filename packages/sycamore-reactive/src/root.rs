@@ -1,10 +1,12 @@
 //! [`Root`] and [`Scope`].
 
-use std::cell::{Cell, RefCell};
+#[cfg(not(feature = "std"))]
+use core::sync::atomic::{AtomicPtr, Ordering};
 
 use slotmap::{Key, SlotMap};
 use smallvec::SmallVec;
 
+use crate::sync::{Cell, RefCell, RootLock};
 use crate::*;
 
 /// The struct managing the state of the reactive system. Only one should be created per running
@@ -16,6 +18,9 @@ use crate::*;
 /// itself. Finally, the `Root` is expected to live for the whole duration of the app so this is
 /// not a problem.
 pub(crate) struct Root {
+    /// The lock that is held while the state of the root is accessed. This is a no-op unless the
+    /// `sync` feature is enabled.
+    pub lock: RootLock,
     /// If this is `Some`, that means we are tracking signal accesses.
     pub tracker: RefCell<Option<DependencyTracker>>,
     /// A temporary buffer used in `propagate_updates` to prevent allocating a new Vec every time
@@ -33,35 +38,61 @@ pub(crate) struct Root {
     /// Whether we are currently batching signal updatse. If this is true, we do not run
     /// `effect_queue` and instead wait until the end of the batch.
     pub batching: Cell<bool>,
+    /// How many calls to `propagate_node_updates` are currently running. Updates made while
+    /// propagating are part of the same top-level propagation.
+    pub propagation_depth: Cell<u32>,
+    /// Incremented at the start of every top-level propagation. Used by [`History`] to group the
+    /// changes of a single update or batch together.
+    pub propagation_epoch: Cell<u64>,
+    /// The previous values of the signals that were written in the active [`transaction`]s.
+    pub transaction_log: RefCell<TransactionLog>,
+    /// The number of error handlers that are registered in this root. Effects and memos only catch
+    /// panics if there is at least one.
+    pub error_handlers: Cell<usize>,
 }
 
+#[cfg(feature = "std")]
 thread_local! {
     /// The current reactive root.
-    static GLOBAL_ROOT: Cell<Option<&'static Root>> = const { Cell::new(None) };
+    static GLOBAL_ROOT: core::cell::Cell<Option<&'static Root>> = const { core::cell::Cell::new(None) };
 }
 
 impl Root {
     /// Get the current reactive root. Panics if no root is found.
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn global() -> &'static Root {
-        GLOBAL_ROOT.with(|root| root.get()).expect("no root found")
+        #[cfg(feature = "std")]
+        let root = GLOBAL_ROOT.with(|root| root.get());
+        #[cfg(not(feature = "std"))]
+        let root = RootStorage::installed().get();
+        root.expect("no root found")
     }
 
     /// Sets the current reactive root. Returns the previous root.
     pub fn set_global(root: Option<&'static Root>) -> Option<&'static Root> {
-        GLOBAL_ROOT.with(|r| r.replace(root))
+        #[cfg(feature = "std")]
+        let prev = GLOBAL_ROOT.with(|r| r.replace(root));
+        #[cfg(not(feature = "std"))]
+        let prev = RootStorage::installed().replace(root);
+        prev
     }
 
     /// Create a new reactive root. This root is leaked and so lives until the end of the program.
     pub fn new_static() -> &'static Self {
+        let lock = RootLock::new();
         let this = Self {
-            tracker: RefCell::new(None),
-            rev_sorted_buf: RefCell::new(Vec::new()),
-            current_node: Cell::new(NodeId::null()),
-            root_node: Cell::new(NodeId::null()),
-            nodes: RefCell::new(SlotMap::default()),
-            node_update_queue: RefCell::new(Vec::new()),
-            batching: Cell::new(false),
+            tracker: RefCell::new(None, &lock),
+            rev_sorted_buf: RefCell::new(Vec::new(), &lock),
+            current_node: Cell::new(NodeId::null(), &lock),
+            root_node: Cell::new(NodeId::null(), &lock),
+            nodes: RefCell::new(SlotMap::default(), &lock),
+            node_update_queue: RefCell::new(Vec::new(), &lock),
+            batching: Cell::new(false, &lock),
+            propagation_depth: Cell::new(0, &lock),
+            propagation_epoch: Cell::new(0, &lock),
+            transaction_log: RefCell::new(TransactionLog::default(), &lock),
+            error_handlers: Cell::new(0, &lock),
+            lock,
         };
         let _ref = Box::leak(Box::new(this));
         _ref.reinit();
@@ -79,7 +110,10 @@ impl Root {
         let _ = self.current_node.take();
         let _ = self.root_node.take();
         let _ = self.nodes.take();
+        let _ = self.transaction_log.take();
         self.batching.set(false);
+        self.propagation_depth.set(0);
+        self.error_handlers.set(0);
 
         // Create a new root node.
         Root::set_global(Some(self));
@@ -92,6 +126,10 @@ impl Root {
     /// Create a new child scope. Implementation detail for [`create_child_scope`].
     pub fn create_child_scope(&'static self, f: impl FnOnce()) -> NodeHandle {
         let node = create_signal(()).id;
+        #[cfg(feature = "inspect")]
+        {
+            self.nodes.borrow_mut()[node].kind = NodeKind::Scope;
+        }
         let prev = self.current_node.replace(node);
         f();
         self.current_node.set(prev);
@@ -109,19 +147,20 @@ impl Root {
     /// Run the update callback of the signal, also recreating any dependencies found by
     /// tracking signal accesses inside the function.
     ///
-    /// Also marks all the dependencies as dirty and marks the current node as clean.
+    /// Also marks all the dependents as dirty if `mark_dependents` is `true` and the value changed,
+    /// and marks the current node as clean.
     ///
     /// # Params
     /// * `root` - The reactive root.
     /// * `id` - The id associated with the reactive node. `SignalId` inside the state itself.
-    fn run_node_update(&'static self, current: NodeId) {
+    fn run_node_update(&'static self, current: NodeId, mark_dependents: bool) {
         debug_assert_eq!(
             self.nodes.borrow()[current].state,
             NodeState::Dirty,
             "should only update when dirty"
         );
         // Remove old dependency links.
-        let dependencies = std::mem::take(&mut self.nodes.borrow_mut()[current].dependencies);
+        let dependencies = core::mem::take(&mut self.nodes.borrow_mut()[current].dependencies);
         for dependency in dependencies {
             self.nodes.borrow_mut()[dependency]
                 .dependents
@@ -136,8 +175,18 @@ impl Root {
 
         NodeHandle(current, self).dispose_children(); // Destroy anything created in a previous update.
 
+        // Panics are only caught if there is an error handler that they can be passed to. The node
+        // is then left in a consistent state, with its previous value.
+        let handler = if self.error_handlers.get() > 0 {
+            error_handler_of(NodeHandle(current, self))
+        } else {
+            None
+        };
         let prev = self.current_node.replace(current);
-        let (changed, tracker) = self.tracked_scope(|| callback(&mut value));
+        let (result, tracker) = self.tracked_scope(|| match handler {
+            Some(_) => catch_unwind_in(self, || callback(&mut value)),
+            None => Ok(callback(&mut value)),
+        });
         self.current_node.set(prev);
 
         tracker.create_dependency_link(self, current);
@@ -145,20 +194,29 @@ impl Root {
         let mut nodes_mut = self.nodes.borrow_mut();
         nodes_mut[current].callback = Some(callback); // Put the callback back in.
         nodes_mut[current].value = Some(value);
+        #[cfg(feature = "inspect")]
+        {
+            nodes_mut[current].runs += 1;
+        }
 
         // Mark this node as clean.
         nodes_mut[current].state = NodeState::Clean;
         drop(nodes_mut);
 
-        if changed {
-            self.mark_dependents_dirty(current);
+        match result {
+            Ok(true) if mark_dependents => self.mark_dependents_dirty(current),
+            Ok(_) => {}
+            Err(payload) => {
+                let handler = handler.expect("panics are only caught with an error handler");
+                handler.handle(CapturedError::from_panic(payload));
+            }
         }
     }
 
     // Mark any dependent node of the current node as dirty.
     fn mark_dependents_dirty(&self, current: NodeId) {
         let mut nodes_mut = self.nodes.borrow_mut();
-        let dependents = std::mem::take(&mut nodes_mut[current].dependents);
+        let dependents = core::mem::take(&mut nodes_mut[current].dependents);
         for &dependent in &dependents {
             if let Some(dependent) = nodes_mut.get_mut(dependent) {
                 dependent.state = NodeState::Dirty;
@@ -173,6 +231,12 @@ impl Root {
     /// We then go through every node in this topological sorting and update only those nodes which
     /// have dependencies that were updated.
     fn propagate_node_updates(&'static self, start_nodes: &[NodeId]) {
+        let depth = self.propagation_depth.get();
+        if depth == 0 {
+            self.propagation_epoch.set(self.propagation_epoch.get() + 1);
+        }
+        self.propagation_depth.set(depth + 1);
+
         // Try to reuse the shared buffer if possible.
         let mut rev_sorted = Vec::new();
         let mut rev_sorted_buf = self.rev_sorted_buf.try_borrow_mut();
@@ -198,12 +262,40 @@ impl Root {
             let node_state = &mut nodes_mut[node];
             node_state.mark = Mark::None; // Reset value.
 
-            // Check if this node needs to be updated.
+            // Check if this node needs to be updated. Lazy memos are only marked as dirty and are
+            // updated once they are read. Since their value might have changed, their dependents
+            // are updated as well.
             if nodes_mut[node].state == NodeState::Dirty {
+                let lazy = nodes_mut[node].lazy;
                 drop(nodes_mut); // End RefMut borrow.
-                self.run_node_update(node)
+                if lazy {
+                    self.mark_dependents_dirty(node);
+                } else {
+                    self.run_node_update(node, true);
+                }
             };
         }
+        self.propagation_depth.set(depth);
+    }
+
+    /// Updates the node if it is a dirty lazy memo. This is called before reading the value of a
+    /// node that was marked as a dirty lazy memo.
+    pub fn update_if_lazy(&'static self, id: NodeId) {
+        let _guard = self.lock.lock();
+        let dirty = self
+            .nodes
+            .borrow()
+            .get(id)
+            .is_some_and(|node| node.lazy && node.state == NodeState::Dirty);
+        if dirty {
+            // Outside of a propagation, the dependents were already updated when the memo was
+            // marked as dirty. During a propagation, the dependents that come after the memo still
+            // need to be updated.
+            let mark_dependents = self.propagation_depth.get() > 0;
+            let prev = Root::set_global(Some(self));
+            self.run_node_update(id, mark_dependents);
+            Root::set_global(prev);
+        }
     }
 
     /// Call this if `start_node` has been updated manually. This will automatically update all
@@ -214,6 +306,7 @@ impl Root {
         if self.batching.get() {
             self.node_update_queue.borrow_mut().push(start_node);
         } else {
+            let _guard = self.lock.lock();
             // Set the global root.
             let prev = Root::set_global(Some(self));
             // Propagate any signal updates.
@@ -237,7 +330,7 @@ impl Root {
         current.mark = Mark::Temp;
 
         // Take the `dependents` field out temporarily to avoid borrow checker.
-        let children = std::mem::take(&mut current.dependents);
+        let children = core::mem::take(&mut current.dependents);
         for child in &children {
             Self::dfs(*child, nodes, buf);
         }
@@ -247,19 +340,139 @@ impl Root {
         buf.push(current_id);
     }
 
-    /// Sets the batch flag to `true`.
-    fn start_batch(&self) {
-        self.batching.set(true);
+    /// Sets the batch flag to `true`. Returns whether we were already batching.
+    pub(crate) fn start_batch(&self) -> bool {
+        self.batching.replace(true)
     }
 
     /// Sets the batch flag to `false` and run all the queued effects.
-    fn end_batch(&'static self) {
+    pub(crate) fn end_batch(&'static self) {
         self.batching.set(false);
         let nodes = self.node_update_queue.take();
         self.propagate_node_updates(&nodes);
     }
 }
 
+/// A slot that stores the current root. This is used instead of a thread-local when the `std`
+/// feature is disabled. See [`RootStorage`].
+#[cfg(not(feature = "std"))]
+pub struct RootSlot(AtomicPtr<Root>);
+
+#[cfg(not(feature = "std"))]
+impl RootSlot {
+    /// Creates a new empty slot.
+    pub const fn new() -> Self {
+        Self(AtomicPtr::new(core::ptr::null_mut()))
+    }
+
+    fn get(&self) -> Option<&'static Root> {
+        // SAFETY: Only `&'static Root`s are stored in the slot.
+        unsafe { self.0.load(Ordering::Acquire).as_ref() }
+    }
+
+    fn replace(&self, root: Option<&'static Root>) -> Option<&'static Root> {
+        let ptr = root.map_or(core::ptr::null_mut(), |root| {
+            root as *const Root as *mut Root
+        });
+        // SAFETY: Only `&'static Root`s are stored in the slot.
+        unsafe { self.0.swap(ptr, Ordering::AcqRel).as_ref() }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl Default for RootSlot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl core::fmt::Debug for RootSlot {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RootSlot")
+            .field("is_set", &self.get().is_some())
+            .finish()
+    }
+}
+
+/// Decides where the current root is stored when the `std` feature is disabled.
+///
+/// Without `std`, there are no thread-locals, so the storage must be installed with
+/// [`set_root_storage`] before a root is created. For single-threaded environments, use
+/// [`RootStorage::SINGLE_THREADED`]. Environments with multiple threads of execution (e.g. an RTOS)
+/// can return a different slot for every thread.
+///
+/// # Example
+/// ```ignore
+/// static CORE0: RootSlot = RootSlot::new();
+/// static CORE1: RootSlot = RootSlot::new();
+/// static STORAGE: RootStorage = RootStorage {
+///     slot: || if current_core() == 0 { &CORE0 } else { &CORE1 },
+/// };
+///
+/// // SAFETY: Roots are never shared between cores.
+/// unsafe { set_root_storage(&STORAGE) };
+/// ```
+#[cfg(not(feature = "std"))]
+#[derive(Clone, Copy, Debug)]
+pub struct RootStorage {
+    /// Returns the slot of the current thread of execution.
+    pub slot: fn() -> &'static RootSlot,
+}
+
+#[cfg(not(feature = "std"))]
+static ROOT_STORAGE: AtomicPtr<RootStorage> = AtomicPtr::new(core::ptr::null_mut());
+
+#[cfg(not(feature = "std"))]
+impl RootStorage {
+    /// Stores the current root in a single global slot. This must only be used if the reactive
+    /// system is only ever used from a single thread of execution, e.g. not from interrupt
+    /// handlers.
+    pub const SINGLE_THREADED: Self = Self {
+        slot: || {
+            static SLOT: RootSlot = RootSlot::new();
+            &SLOT
+        },
+    };
+
+    /// Stores the current root in a thread-local since the tests run in parallel. This is used by
+    /// the tests of this crate if no other storage is installed.
+    #[cfg(test)]
+    const TEST: Self = Self {
+        slot: || {
+            std::thread_local! {
+                static SLOT: &'static RootSlot = Box::leak(Box::new(RootSlot::new()));
+            }
+            SLOT.with(|slot| *slot)
+        },
+    };
+
+    /// Returns the slot of the installed storage. Panics if no storage is installed.
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn installed() -> &'static RootSlot {
+        // SAFETY: Only `&'static RootStorage`s are stored.
+        let storage = unsafe { ROOT_STORAGE.load(Ordering::Acquire).as_ref() };
+        #[cfg(test)]
+        let storage = storage.or(Some(&Self::TEST));
+        let storage = storage.expect("no root storage installed, see `set_root_storage`");
+        (storage.slot)()
+    }
+}
+
+/// Installs the [`RootStorage`] that is used for storing the current root. This is only needed
+/// when the `std` feature is disabled, and must be called before any root is created.
+///
+/// # Safety
+/// The reactive system is not thread-safe. The installed storage must never return a slot that
+/// can be used by multiple threads of execution at the same time, including interrupt handlers.
+#[cfg(not(feature = "std"))]
+pub unsafe fn set_root_storage(storage: &'static RootStorage) {
+    ROOT_STORAGE.store(
+        storage as *const RootStorage as *mut RootStorage,
+        Ordering::Release,
+    );
+}
+
 /// A handle to a root. This lets you reinitialize or dispose the root for resource cleanup.
 ///
 /// This is generally obtained from [`create_root`].
@@ -271,11 +484,13 @@ pub struct RootHandle {
 impl RootHandle {
     /// Destroy everything that was created in this scope.
     pub fn dispose(&self) {
+        let _guard = self._ref.lock.lock();
         self._ref.reinit();
     }
 
     /// Runs the closure in the current scope of the root.
     pub fn run_in<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _guard = self._ref.lock.lock();
         let prev = Root::set_global(Some(self._ref));
         let ret = f();
         Root::set_global(prev);
@@ -320,7 +535,7 @@ impl DependencyTracker {
 #[must_use = "root should be disposed"]
 pub fn create_root(f: impl FnOnce()) -> RootHandle {
     let _ref = Root::new_static();
-    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(all(feature = "std", not(target_arch = "wasm32")))]
     {
         /// An unsafe wrapper around a raw pointer which we promise to never touch, effectively
         /// making it thread-safe.
@@ -367,7 +582,7 @@ pub fn create_child_scope(f: impl FnOnce()) -> NodeHandle {
 /// child_scope.dispose(); // Executes the on_cleanup callback.
 /// # });
 /// ```
-pub fn on_cleanup(f: impl FnOnce() + 'static) {
+pub fn on_cleanup(f: impl FnOnce() + MaybeSend + 'static) {
     let root = Root::global();
     if !root.current_node.get().is_null() {
         root.nodes.borrow_mut()[root.current_node.get()]
@@ -395,9 +610,13 @@ pub fn on_cleanup(f: impl FnOnce() + 'static) {
 /// ```
 pub fn batch<T>(f: impl FnOnce() -> T) -> T {
     let root = Root::global();
-    root.start_batch();
+    let _guard = root.lock.lock();
+    // Nested batches are part of the outermost batch.
+    let nested = root.start_batch();
     let ret = f();
-    root.end_batch();
+    if !nested {
+        root.end_batch();
+    }
     ret
 }
 
@@ -545,4 +764,23 @@ mod tests {
             assert_eq!(counter.get(), 4);
         });
     }
+
+    #[test]
+    fn nested_batch_updates_effects_at_end_of_outer_batch() {
+        let _ = create_root(|| {
+            let state1 = create_signal(1);
+            let state2 = create_signal(2);
+            let counter = create_signal(0);
+            create_effect(move || {
+                counter.set(counter.get_untracked() + 1);
+                let _ = state1.get() + state2.get();
+            });
+            batch(move || {
+                batch(move || state1.set(2));
+                assert_eq!(counter.get(), 1);
+                state2.set(3);
+            });
+            assert_eq!(counter.get(), 2);
+        });
+    }
 }
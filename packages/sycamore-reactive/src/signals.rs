@@ -1,15 +1,16 @@
 //! Reactive signals.
 
-use std::cell::{Ref, RefMut};
-use std::fmt;
-use std::fmt::Formatter;
-use std::hash::Hash;
-use std::marker::PhantomData;
-use std::ops::{AddAssign, Deref, DivAssign, MulAssign, RemAssign, SubAssign};
+use core::any::TypeId;
+use core::fmt;
+use core::fmt::Formatter;
+use core::hash::Hash;
+use core::marker::PhantomData;
+use core::ops::{AddAssign, Deref, DivAssign, MulAssign, RemAssign, SubAssign};
 
 use slotmap::Key;
 use smallvec::SmallVec;
 
+use crate::sync::{Ref, RefMut};
 use crate::*;
 
 /// A read-only reactive value.
@@ -37,13 +38,15 @@ use crate::*;
 /// See [`create_signal`] for more information.
 pub struct ReadSignal<T: 'static> {
     pub(crate) id: NodeId,
-    root: &'static Root,
+    pub(crate) root: &'static Root,
     /// Keep track of where the signal was created for diagnostics.
     /// This is also stored in the Node but we want to have access to this when accessing a
     /// disposed node so we store it here as well.
     #[cfg(debug_assertions)]
-    created_at: &'static std::panic::Location<'static>,
-    _phantom: PhantomData<T>,
+    created_at: &'static core::panic::Location<'static>,
+    /// The value is owned by the root, so the handle can be sent between threads as long as the
+    /// root can.
+    _phantom: PhantomData<fn() -> T>,
 }
 
 /// A reactive value that can be read and written to.
@@ -82,7 +85,7 @@ pub struct Signal<T: 'static>(pub(crate) ReadSignal<T>);
 ///
 /// # Reactivity
 /// What makes signals so powerful, as opposed to some other wrapper type like
-/// [`RefCell`](std::cell::RefCell) is the automatic dependency tracking. This means that accessing
+/// [`RefCell`](core::cell::RefCell) is the automatic dependency tracking. This means that accessing
 /// a signal will automatically add it as a dependency in certain contexts (such as inside a
 /// [`create_memo`](crate::create_memo)) which allows us to update related state whenever the signal
 /// is changed.
@@ -112,12 +115,61 @@ pub struct Signal<T: 'static>(pub(crate) ReadSignal<T>);
 /// This is why in the above example, we could access `signal` even after it was moved in to the
 /// closure of the `create_memo`.
 #[cfg_attr(debug_assertions, track_caller)]
-pub fn create_signal<T>(value: T) -> Signal<T> {
+pub fn create_signal<T: MaybeSend>(value: T) -> Signal<T> {
     let signal = create_empty_signal();
-    signal.get_mut().value = Some(Box::new(value));
+    let mut node = signal.get_mut();
+    node.value = Some(Box::new(value));
+    node.clone_value = builtin_clone_fn::<T>();
+    drop(node);
     signal
 }
 
+/// Returns the function for cloning values of type `T` if `T` is a primitive type or a `String`.
+/// These are known to implement `Clone`, so a [`transaction`] can save their value before it is
+/// updated in place.
+fn builtin_clone_fn<T: 'static>() -> Option<CloneFn> {
+    macro_rules! builtin_clone_fn {
+        ($($ty:ty),*) => {
+            $(
+                if TypeId::of::<T>() == TypeId::of::<$ty>() {
+                    return Some(clone_value::<$ty>);
+                }
+            )*
+        };
+    }
+    builtin_clone_fn!(
+        bool,
+        char,
+        u8,
+        u16,
+        u32,
+        u64,
+        u128,
+        usize,
+        i8,
+        i16,
+        i32,
+        i64,
+        i128,
+        isize,
+        f32,
+        f64,
+        String,
+        &'static str
+    );
+    None
+}
+
+/// Clones a value of type `T` that is stored in a reactive node.
+fn clone_value<T: Clone + MaybeSend + 'static>(value: &AnyValue) -> Box<AnyValue> {
+    Box::new(
+        value
+            .downcast_ref::<T>()
+            .expect("wrong signal type")
+            .clone(),
+    )
+}
+
 /// Creates a new [`Signal`] with the `value` field set to `None`.
 #[cfg_attr(debug_assertions, track_caller)]
 pub(crate) fn create_empty_signal<T>() -> Signal<T> {
@@ -131,11 +183,20 @@ pub(crate) fn create_empty_signal<T>() -> Signal<T> {
         dependencies: SmallVec::new(),
         cleanups: Vec::new(),
         context: Vec::new(),
+        #[cfg(feature = "inspect")]
+        context_types: Vec::new(),
+        #[cfg(feature = "inspect")]
+        kind: NodeKind::Signal,
+        #[cfg(feature = "inspect")]
+        runs: 0,
+        clone_value: None,
+        lazy: false,
         state: NodeState::Clean,
         mark: Mark::None,
         #[cfg(debug_assertions)]
-        created_at: std::panic::Location::caller(),
+        created_at: core::panic::Location::caller(),
     });
+    root.record_created(id);
     // Add the signal to the parent's `children` list.
     let current_node = root.current_node.get();
     if !current_node.is_null() {
@@ -146,7 +207,7 @@ pub(crate) fn create_empty_signal<T>() -> Signal<T> {
         id,
         root,
         #[cfg(debug_assertions)]
-        created_at: std::panic::Location::caller(),
+        created_at: core::panic::Location::caller(),
         _phantom: PhantomData,
     })
 }
@@ -210,7 +271,7 @@ impl<T> ReadSignal<T> {
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn get_untracked(self) -> T
     where
-        T: Copy,
+        T: Copy + MaybeSend,
     {
         self.with_untracked(|value| *value)
     }
@@ -221,7 +282,7 @@ impl<T> ReadSignal<T> {
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn get_clone_untracked(self) -> T
     where
-        T: Clone,
+        T: Clone + MaybeSend,
     {
         self.with_untracked(Clone::clone)
     }
@@ -248,7 +309,7 @@ impl<T> ReadSignal<T> {
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn get(self) -> T
     where
-        T: Copy,
+        T: Copy + MaybeSend,
     {
         self.track();
         self.get_untracked()
@@ -279,16 +340,31 @@ impl<T> ReadSignal<T> {
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn get_clone(self) -> T
     where
-        T: Clone,
+        T: Clone + MaybeSend,
     {
         self.track();
         self.get_clone_untracked()
     }
 
+    /// Remembers how to clone the value of the signal, so that a [`transaction`] can save the value
+    /// before it is updated in place.
+    pub(crate) fn enable_snapshots(self)
+    where
+        T: Clone + MaybeSend,
+    {
+        self.get_mut().clone_value = Some(clone_value::<T>);
+    }
+
     /// Get a value from the signal without tracking it.
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn with_untracked<U>(self, f: impl FnOnce(&T) -> U) -> U {
-        let node = self.get_ref();
+        let mut node = self.get_ref();
+        // Only dirty lazy memos need to be updated before they are read.
+        if node.lazy && node.state == NodeState::Dirty {
+            drop(node);
+            self.root.update_if_lazy(self.id);
+            node = self.get_ref();
+        }
         let value = node.value.as_ref().expect("value updating");
         let ret = f(value.downcast_ref().expect("wrong signal type"));
         ret
@@ -318,7 +394,13 @@ impl<T> ReadSignal<T> {
     /// # });
     /// ```
     #[cfg_attr(debug_assertions, track_caller)]
-    pub fn map<U>(self, mut f: impl FnMut(&T) -> U + 'static) -> ReadSignal<U> {
+    pub fn map<U: MaybeSend>(
+        self,
+        mut f: impl FnMut(&T) -> U + MaybeSend + 'static,
+    ) -> ReadSignal<U>
+    where
+        T: MaybeSend,
+    {
         create_memo(move || self.with(&mut f))
     }
 
@@ -361,8 +443,14 @@ impl<T> Signal<T> {
     /// # });
     /// ```
     #[cfg_attr(debug_assertions, track_caller)]
-    pub fn set_silent(self, new: T) {
-        self.replace_silent(new);
+    pub fn set_silent(self, new: T)
+    where
+        T: MaybeSend,
+    {
+        let prev = self.get_mut().value.replace(Box::new(new));
+        let prev = prev.expect("value updating");
+        // Inside a transaction, the previous value is kept so that it can be restored.
+        drop(self.0.root.record_write(self.0.id, prev));
     }
 
     /// Set a new value for the signal and automatically update any dependents.
@@ -379,8 +467,12 @@ impl<T> Signal<T> {
     /// # });
     /// ```
     #[cfg_attr(debug_assertions, track_caller)]
-    pub fn set(self, new: T) {
-        self.replace(new);
+    pub fn set(self, new: T)
+    where
+        T: MaybeSend,
+    {
+        self.set_silent(new);
+        self.0.root.propagate_updates(self.0.id);
     }
 
     /// Silently set a new value for the signal and return the previous value.
@@ -388,7 +480,7 @@ impl<T> Signal<T> {
     /// This is the silent version of [`Signal::replace`].
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn replace_silent(self, new: T) -> T {
-        self.update_silent(|val| std::mem::replace(val, new))
+        self.update_silent(|val| core::mem::replace(val, new))
     }
 
     /// Set a new value for the signal and return the previous value.
@@ -405,7 +497,7 @@ impl<T> Signal<T> {
     /// ```
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn replace(self, new: T) -> T {
-        self.update(|val| std::mem::replace(val, new))
+        self.update(|val| core::mem::replace(val, new))
     }
 
     /// Silently gets the value of the signal and sets the new value to the default value.
@@ -446,6 +538,13 @@ impl<T> Signal<T> {
     /// This is the silent version of [`Signal::update`].
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn update_silent<U>(self, f: impl FnOnce(&mut T) -> U) -> U {
+        self.0.root.save_in_place_write(self.0.id);
+        self.update_silent_unrestored(f)
+    }
+
+    /// Updates the value of the signal silently without saving it in a [`transaction`]. This is used
+    /// for internal state that is not restored, or that was already saved with a custom snapshot.
+    pub(crate) fn update_silent_unrestored<U>(self, f: impl FnOnce(&mut T) -> U) -> U {
         let mut value = self.get_mut().value.take().expect("value updating");
         let ret = f(value.downcast_mut().expect("wrong signal type"));
         self.get_mut().value = Some(value);
@@ -474,12 +573,26 @@ impl<T> Signal<T> {
         ret
     }
 
+    /// Updates the value of the signal without saving it in a [`transaction`]. This is used for
+    /// internal state that is not restored, or that was already saved with a custom snapshot.
+    pub(crate) fn update_unrestored<U>(self, f: impl FnOnce(&mut T) -> U) -> U {
+        let ret = self.update_silent_unrestored(f);
+        self.0.root.propagate_updates(self.0.id);
+        ret
+    }
+
     /// Use a function to produce a new value and sets the value silently.
     ///
     /// This is the silent version of [`Signal::set_fn`].
     #[cfg_attr(debug_assertions, track_caller)]
-    pub fn set_fn_silent(self, f: impl FnOnce(&T) -> T) {
-        self.update_silent(move |val| *val = f(val));
+    pub fn set_fn_silent(self, f: impl FnOnce(&T) -> T)
+    where
+        T: MaybeSend,
+    {
+        let prev = self.get_mut().value.take().expect("value updating");
+        let new = f(prev.downcast_ref().expect("wrong signal type"));
+        self.get_mut().value = Some(Box::new(new));
+        drop(self.0.root.record_write(self.0.id, prev));
     }
 
     /// Use a function to produce a new value and sets the value.
@@ -494,8 +607,12 @@ impl<T> Signal<T> {
     /// # });
     /// ```
     #[cfg_attr(debug_assertions, track_caller)]
-    pub fn set_fn(self, f: impl FnOnce(&T) -> T) {
-        self.update(move |val| *val = f(val));
+    pub fn set_fn(self, f: impl FnOnce(&T) -> T)
+    where
+        T: MaybeSend,
+    {
+        self.set_fn_silent(f);
+        self.0.root.propagate_updates(self.0.id);
     }
 
     /// Split the signal into a reader/writter pair.
@@ -531,12 +648,12 @@ impl<T> Clone for Signal<T> {
 impl<T> Copy for Signal<T> {}
 
 // Implement `Default` for `ReadSignal` and `Signal`.
-impl<T: Default> Default for ReadSignal<T> {
+impl<T: Default + MaybeSend> Default for ReadSignal<T> {
     fn default() -> Self {
         *create_signal(Default::default())
     }
 }
-impl<T: Default> Default for Signal<T> {
+impl<T: Default + MaybeSend> Default for Signal<T> {
     fn default() -> Self {
         create_signal(Default::default())
     }
@@ -551,19 +668,19 @@ impl<T: PartialEq> PartialEq for ReadSignal<T> {
 impl<T: Eq> Eq for ReadSignal<T> {}
 impl<T: PartialOrd> PartialOrd for ReadSignal<T> {
     #[cfg_attr(debug_assertions, track_caller)]
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         self.with(|value| other.with(|other| value.partial_cmp(other)))
     }
 }
 impl<T: Ord> Ord for ReadSignal<T> {
     #[cfg_attr(debug_assertions, track_caller)]
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.with(|value| other.with(|other| value.cmp(other)))
     }
 }
 impl<T: Hash> Hash for ReadSignal<T> {
     #[cfg_attr(debug_assertions, track_caller)]
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         self.with(|value| value.hash(state))
     }
 }
@@ -577,19 +694,19 @@ impl<T: PartialEq> PartialEq for Signal<T> {
 impl<T: Eq> Eq for Signal<T> {}
 impl<T: PartialOrd> PartialOrd for Signal<T> {
     #[cfg_attr(debug_assertions, track_caller)]
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         self.with(|value| other.with(|other| value.partial_cmp(other)))
     }
 }
 impl<T: Ord> Ord for Signal<T> {
     #[cfg_attr(debug_assertions, track_caller)]
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.with(|value| other.with(|other| value.cmp(other)))
     }
 }
 impl<T: Hash> Hash for Signal<T> {
     #[cfg_attr(debug_assertions, track_caller)]
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         self.with(|value| value.hash(state))
     }
 }
@@ -637,7 +754,7 @@ impl<T: serde::Serialize> serde::Serialize for ReadSignal<T> {
     }
 }
 #[cfg(feature = "serde")]
-impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for ReadSignal<T> {
+impl<'de, T: serde::Deserialize<'de> + MaybeSend> serde::Deserialize<'de> for ReadSignal<T> {
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         Ok(*create_signal(T::deserialize(deserializer)?))
     }
@@ -649,14 +766,14 @@ impl<T: serde::Serialize> serde::Serialize for Signal<T> {
     }
 }
 #[cfg(feature = "serde")]
-impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Signal<T> {
+impl<'de, T: serde::Deserialize<'de> + MaybeSend> serde::Deserialize<'de> for Signal<T> {
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         Ok(create_signal(T::deserialize(deserializer)?))
     }
 }
 
 #[cfg(feature = "nightly")]
-impl<T: Copy> FnOnce<()> for ReadSignal<T> {
+impl<T: Copy + MaybeSend> FnOnce<()> for ReadSignal<T> {
     type Output = T;
 
     extern "rust-call" fn call_once(self, _args: ()) -> Self::Output {
@@ -693,7 +810,7 @@ impl<T: RemAssign<Rhs>, Rhs> RemAssign<Rhs> for Signal<T> {
 // We need to implement this again for `Signal` despite `Signal` deref-ing to `ReadSignal` since
 // we also have another implementation of `FnOnce` for `Signal`.
 #[cfg(feature = "nightly")]
-impl<T: Copy> FnOnce<()> for Signal<T> {
+impl<T: Copy + MaybeSend> FnOnce<()> for Signal<T> {
     type Output = T;
 
     extern "rust-call" fn call_once(self, _args: ()) -> Self::Output {
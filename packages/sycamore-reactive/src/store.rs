@@ -0,0 +1,38 @@
+//! Fine-grained reactive stores.
+
+/// A struct that can be split into a store, with a [`Signal`](crate::Signal) for every field.
+///
+/// Unlike putting the whole struct in a single `Signal`, updating a field of a store only
+/// re-runs the effects that depend on that field. Nested structs that also implement `Store` can be
+/// split recursively.
+///
+/// This trait should not be implemented manually. Use the `Store` derive macro from `sycamore`
+/// instead, which also generates the store type.
+pub trait Store: Sized + 'static {
+    /// The store type, with a [`Signal`](crate::Signal) for every field. Nested stores are stored
+    /// as their own store type.
+    type Store: Copy + 'static;
+
+    /// Creates a new store containing the value of every field.
+    fn into_store(self) -> Self::Store;
+
+    /// Returns a snapshot of all the fields of the `store`. The fields are tracked.
+    fn get_clone(store: Self::Store) -> Self;
+
+    /// Returns a snapshot of all the fields of the `store` without tracking them.
+    fn get_clone_untracked(store: Self::Store) -> Self;
+
+    /// Sets all the fields of the `store`. The updates are batched, so effects that depend on more
+    /// than one field only run once.
+    fn set(store: Self::Store, value: Self);
+}
+
+/// Creates a new store from `value`. See [`Store`].
+///
+/// Every field of the store is a [`Signal`](crate::Signal) that can be read and updated on its
+/// own, e.g. `store.user.name.set(...)`. Since the fields are plain signals, they can be passed
+/// directly to props that accept a [`MaybeDyn`](crate::MaybeDyn) and can be updated inside a
+/// [`batch`](crate::batch).
+pub fn create_store<T: Store>(value: T) -> T::Store {
+    value.into_store()
+}
@@ -0,0 +1,372 @@
+//! Support for sharing a reactive root between threads.
+//!
+//! By default, the reactive system is single threaded and uses [`core::cell`] types internally.
+//! With the `sync` feature, every [`Root`] has a reentrant lock that is held whenever the state of
+//! the root is accessed. This makes the root (and the signals in it) `Send` and `Sync`, as long as
+//! all the values and closures that are stored in it are `Send`.
+
+use core::any::Any;
+
+use crate::Box;
+
+/// A marker trait for values and closures that are stored in the reactive graph.
+///
+/// With the `sync` feature, this requires the type to be [`Send`] so that the reactive root can be
+/// moved between threads. Otherwise, this is implemented for all types.
+#[cfg(sycamore_sync)]
+pub trait MaybeSend: Send {}
+#[cfg(sycamore_sync)]
+impl<T: Send + ?Sized> MaybeSend for T {}
+
+/// A marker trait for values and closures that are stored in the reactive graph.
+///
+/// With the `sync` feature, this requires the type to be [`Send`] so that the reactive root can be
+/// moved between threads. Otherwise, this is implemented for all types.
+#[cfg(not(sycamore_sync))]
+pub trait MaybeSend {}
+#[cfg(not(sycamore_sync))]
+impl<T: ?Sized> MaybeSend for T {}
+
+/// A marker trait for closures that are shared between nodes of the reactive graph.
+///
+/// With the `sync` feature, this requires the type to be [`Sync`]. Otherwise, this is implemented
+/// for all types.
+#[cfg(sycamore_sync)]
+pub trait MaybeSync: Sync {}
+#[cfg(sycamore_sync)]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+/// A marker trait for closures that are shared between nodes of the reactive graph.
+///
+/// With the `sync` feature, this requires the type to be [`Sync`]. Otherwise, this is implemented
+/// for all types.
+#[cfg(not(sycamore_sync))]
+pub trait MaybeSync {}
+#[cfg(not(sycamore_sync))]
+impl<T: ?Sized> MaybeSync for T {}
+
+// A reference counted pointer for state that is captured by closures in the reactive graph. This
+// is an `Arc` with the `sync` feature and an `Rc` otherwise.
+#[cfg(not(sycamore_sync))]
+pub(crate) use alloc::rc::{Rc as Shared, Weak as WeakShared};
+#[cfg(sycamore_sync)]
+pub(crate) use alloc::sync::{Arc as Shared, Weak as WeakShared};
+
+/// A mutable cell for state that is captured by closures in the reactive graph. This is a `Mutex`
+/// with the `sync` feature and a `RefCell` otherwise. The cell must not be borrowed again while it
+/// is borrowed.
+pub(crate) struct SharedCell<T>(
+    #[cfg(sycamore_sync)] parking_lot::Mutex<T>,
+    #[cfg(not(sycamore_sync))] core::cell::RefCell<T>,
+);
+
+impl<T> SharedCell<T> {
+    pub fn new(value: T) -> Self {
+        Self(value.into())
+    }
+
+    #[cfg(sycamore_sync)]
+    pub fn borrow_mut(&self) -> impl core::ops::DerefMut<Target = T> + '_ {
+        self.0.lock()
+    }
+
+    #[cfg(not(sycamore_sync))]
+    pub fn borrow_mut(&self) -> impl core::ops::DerefMut<Target = T> + '_ {
+        self.0.borrow_mut()
+    }
+
+    pub fn take(&self) -> T
+    where
+        T: Default,
+    {
+        core::mem::take(&mut *self.borrow_mut())
+    }
+}
+
+/// The type of the values stored in the reactive graph.
+#[cfg(sycamore_sync)]
+pub(crate) type AnyValue = dyn Any + Send;
+#[cfg(not(sycamore_sync))]
+pub(crate) type AnyValue = dyn Any;
+
+/// The update callback of a reactive node.
+#[cfg(sycamore_sync)]
+pub(crate) type NodeCallback = dyn FnMut(&mut Box<AnyValue>) -> bool + Send;
+#[cfg(not(sycamore_sync))]
+pub(crate) type NodeCallback = dyn FnMut(&mut Box<AnyValue>) -> bool;
+
+/// Restores the saved state of a signal when a transaction is rolled back. Returns the state that
+/// was replaced, so that it can be dropped later.
+#[cfg(sycamore_sync)]
+pub(crate) type RestoreFn = dyn FnOnce(&mut AnyValue) -> Box<AnyValue> + Send;
+#[cfg(not(sycamore_sync))]
+pub(crate) type RestoreFn = dyn FnOnce(&mut AnyValue) -> Box<AnyValue>;
+
+/// A cleanup callback of a reactive node.
+#[cfg(sycamore_sync)]
+pub(crate) type CleanupFn = dyn FnOnce() + Send;
+#[cfg(not(sycamore_sync))]
+pub(crate) type CleanupFn = dyn FnOnce();
+
+#[cfg(sycamore_sync)]
+pub(crate) use self::locked::*;
+#[cfg(not(sycamore_sync))]
+pub(crate) use self::unlocked::*;
+
+/// Cells that hold the lock of the root while they are borrowed.
+#[cfg(sycamore_sync)]
+mod locked {
+    use alloc::sync::Arc;
+    use core::cell::BorrowMutError;
+    use core::ops::{Deref, DerefMut};
+
+    use parking_lot::{ReentrantMutex, ReentrantMutexGuard};
+
+    /// The lock of a root. This is shared between all the cells of the root.
+    #[derive(Clone, Default)]
+    pub(crate) struct RootLock(Arc<ReentrantMutex<()>>);
+
+    pub(crate) type RootGuard<'a> = ReentrantMutexGuard<'a, ()>;
+
+    impl RootLock {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn lock(&self) -> RootGuard<'_> {
+            self.0.lock()
+        }
+    }
+
+    pub(crate) struct RefCell<T> {
+        lock: RootLock,
+        cell: core::cell::RefCell<T>,
+    }
+
+    // SAFETY: The inner `RefCell`, including its borrow flag, is only accessed while the lock is
+    // held, so it is never accessed by more than one thread at a time. A `Ref` or `RefMut` keeps
+    // the lock until the borrow is released.
+    unsafe impl<T: Send> Sync for RefCell<T> {}
+
+    impl<T> RefCell<T> {
+        pub fn new(value: T, lock: &RootLock) -> Self {
+            Self {
+                lock: lock.clone(),
+                cell: core::cell::RefCell::new(value),
+            }
+        }
+
+        pub fn borrow(&self) -> Ref<'_, T> {
+            let guard = self.lock.lock();
+            Ref {
+                value: self.cell.borrow(),
+                _guard: guard,
+            }
+        }
+
+        pub fn borrow_mut(&self) -> RefMut<'_, T> {
+            let guard = self.lock.lock();
+            RefMut {
+                value: self.cell.borrow_mut(),
+                _guard: guard,
+            }
+        }
+
+        pub fn try_borrow_mut(&self) -> Result<RefMut<'_, T>, BorrowMutError> {
+            let guard = self.lock.lock();
+            Ok(RefMut {
+                value: self.cell.try_borrow_mut()?,
+                _guard: guard,
+            })
+        }
+
+        pub fn replace(&self, value: T) -> T {
+            let _guard = self.lock.lock();
+            self.cell.replace(value)
+        }
+
+        pub fn take(&self) -> T
+        where
+            T: Default,
+        {
+            self.replace(T::default())
+        }
+    }
+
+    // The borrow is declared first so that it is released before the lock.
+    pub(crate) struct Ref<'a, T: ?Sized> {
+        value: core::cell::Ref<'a, T>,
+        _guard: RootGuard<'a>,
+    }
+
+    impl<'a, T: ?Sized> Ref<'a, T> {
+        pub fn map<U: ?Sized>(orig: Self, f: impl FnOnce(&T) -> &U) -> Ref<'a, U> {
+            Ref {
+                value: core::cell::Ref::map(orig.value, f),
+                _guard: orig._guard,
+            }
+        }
+    }
+
+    impl<T: ?Sized> Deref for Ref<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            &self.value
+        }
+    }
+
+    pub(crate) struct RefMut<'a, T: ?Sized> {
+        value: core::cell::RefMut<'a, T>,
+        _guard: RootGuard<'a>,
+    }
+
+    impl<'a, T: ?Sized> RefMut<'a, T> {
+        pub fn map<U: ?Sized>(orig: Self, f: impl FnOnce(&mut T) -> &mut U) -> RefMut<'a, U> {
+            RefMut {
+                value: core::cell::RefMut::map(orig.value, f),
+                _guard: orig._guard,
+            }
+        }
+    }
+
+    impl<T: ?Sized> Deref for RefMut<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            &self.value
+        }
+    }
+
+    impl<T: ?Sized> DerefMut for RefMut<'_, T> {
+        fn deref_mut(&mut self) -> &mut T {
+            &mut self.value
+        }
+    }
+
+    pub(crate) struct Cell<T> {
+        lock: RootLock,
+        cell: core::cell::Cell<T>,
+    }
+
+    // SAFETY: The inner `Cell` is only accessed while the lock is held.
+    unsafe impl<T: Send> Sync for Cell<T> {}
+
+    impl<T> Cell<T> {
+        pub fn new(value: T, lock: &RootLock) -> Self {
+            Self {
+                lock: lock.clone(),
+                cell: core::cell::Cell::new(value),
+            }
+        }
+
+        pub fn get(&self) -> T
+        where
+            T: Copy,
+        {
+            let _guard = self.lock.lock();
+            self.cell.get()
+        }
+
+        pub fn set(&self, value: T) {
+            let _guard = self.lock.lock();
+            self.cell.set(value);
+        }
+
+        pub fn replace(&self, value: T) -> T {
+            let _guard = self.lock.lock();
+            self.cell.replace(value)
+        }
+
+        pub fn take(&self) -> T
+        where
+            T: Default,
+        {
+            self.replace(T::default())
+        }
+    }
+}
+
+/// Plain [`core::cell`] types for when the root is not shared between threads.
+#[cfg(not(sycamore_sync))]
+mod unlocked {
+    use core::cell::BorrowMutError;
+    pub(crate) use core::cell::{Ref, RefMut};
+    use core::marker::PhantomData;
+
+    #[derive(Clone, Default)]
+    pub(crate) struct RootLock;
+
+    pub(crate) struct RootGuard<'a>(PhantomData<&'a ()>);
+
+    impl RootLock {
+        pub fn new() -> Self {
+            Self
+        }
+
+        pub fn lock(&self) -> RootGuard<'_> {
+            RootGuard(PhantomData)
+        }
+    }
+
+    pub(crate) struct RefCell<T>(core::cell::RefCell<T>);
+
+    impl<T> RefCell<T> {
+        pub fn new(value: T, _lock: &RootLock) -> Self {
+            Self(core::cell::RefCell::new(value))
+        }
+
+        pub fn borrow(&self) -> Ref<'_, T> {
+            self.0.borrow()
+        }
+
+        pub fn borrow_mut(&self) -> RefMut<'_, T> {
+            self.0.borrow_mut()
+        }
+
+        pub fn try_borrow_mut(&self) -> Result<RefMut<'_, T>, BorrowMutError> {
+            self.0.try_borrow_mut()
+        }
+
+        pub fn replace(&self, value: T) -> T {
+            self.0.replace(value)
+        }
+
+        pub fn take(&self) -> T
+        where
+            T: Default,
+        {
+            self.0.take()
+        }
+    }
+
+    pub(crate) struct Cell<T>(core::cell::Cell<T>);
+
+    impl<T> Cell<T> {
+        pub fn new(value: T, _lock: &RootLock) -> Self {
+            Self(core::cell::Cell::new(value))
+        }
+
+        pub fn get(&self) -> T
+        where
+            T: Copy,
+        {
+            self.0.get()
+        }
+
+        pub fn set(&self, value: T) {
+            self.0.set(value);
+        }
+
+        pub fn replace(&self, value: T) -> T {
+            self.0.replace(value)
+        }
+
+        pub fn take(&self) -> T
+        where
+            T: Default,
+        {
+            self.0.take()
+        }
+    }
+}
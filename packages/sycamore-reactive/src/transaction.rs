@@ -0,0 +1,543 @@
+//! Transactional batches.
+
+use core::fmt;
+
+use crate::*;
+
+/// The saved state of a signal that is restored when a transaction is rolled back.
+pub(crate) enum Snapshot {
+    /// The previous value of the signal.
+    Value(Box<AnyValue>),
+    /// Restores the previous state of the signal in place. This is used by [`SignalVec`] and
+    /// [`SignalMap`], which also need to restore the diffs that were sent to their subscribers.
+    Restore(Box<RestoreFn>),
+    /// The previous value could not be saved because the signal was updated in place and its type
+    /// is not known to implement `Clone`. The signal keeps its new value and its dependents are
+    /// still updated, so that they stay consistent with it.
+    Unsaved,
+}
+
+/// The previous values of the signals that were written in the active transactions of a root.
+#[derive(Default)]
+pub(crate) struct TransactionLog {
+    /// The states of the signals from before they were first written in a transaction, in the
+    /// order in which they were written.
+    snapshots: Vec<(NodeId, Snapshot)>,
+    /// The active transactions, from the outermost to the innermost. For every transaction, this
+    /// stores the index of its first snapshot and the signals that were written or created in it.
+    levels: Vec<(usize, HashSet<NodeId>)>,
+}
+
+impl TransactionLog {
+    /// Returns `true` if a transaction is active and `id` was not written or created in it yet.
+    /// Marks `id` as written.
+    fn needs_snapshot(&mut self, id: NodeId) -> bool {
+        self.levels
+            .last_mut()
+            .is_some_and(|(_, written)| written.insert(id))
+    }
+}
+
+impl Root {
+    /// Called when a signal is created. Signals that are created inside a transaction do not need
+    /// to be restored by it.
+    pub(crate) fn record_created(&self, id: NodeId) {
+        if let Some((_, written)) = self.transaction_log.borrow_mut().levels.last_mut() {
+            written.insert(id);
+        }
+    }
+
+    /// Called when the value of a signal is replaced. If this is the first write of the signal in
+    /// the current transaction, `prev` is saved so that it can be restored. Otherwise, `prev` is
+    /// returned so that it can be dropped by the caller.
+    pub(crate) fn record_write(&self, id: NodeId, prev: Box<AnyValue>) -> Option<Box<AnyValue>> {
+        let mut log = self.transaction_log.borrow_mut();
+        if log.needs_snapshot(id) {
+            log.snapshots.push((id, Snapshot::Value(prev)));
+            None
+        } else {
+            Some(prev)
+        }
+    }
+
+    /// Called before the state of a signal is changed. If this is the first write of the signal in
+    /// the current transaction, the snapshot returned by `f` is saved so that it can be restored.
+    pub(crate) fn save_snapshot(&self, id: NodeId, f: impl FnOnce() -> Snapshot) {
+        if self.transaction_log.borrow_mut().needs_snapshot(id) {
+            let snapshot = f();
+            self.transaction_log
+                .borrow_mut()
+                .snapshots
+                .push((id, snapshot));
+        }
+    }
+
+    /// Called before the value of a signal is updated in place. The previous value can only be
+    /// saved by cloning it. If the type of the value is not known to implement `Clone`, the signal
+    /// is not restored if the transaction is rolled back.
+    pub(crate) fn save_in_place_write(&self, id: NodeId) {
+        if !self.nodes.borrow().contains_key(id) {
+            // Writing to the disposed signal panics.
+            return;
+        }
+        self.save_snapshot(id, || {
+            let nodes = self.nodes.borrow();
+            let node = &nodes[id];
+            match node.clone_value {
+                Some(clone_value) => {
+                    Snapshot::Value(clone_value(node.value.as_deref().expect("value updating")))
+                }
+                None => Snapshot::Unsaved,
+            }
+        });
+    }
+}
+
+/// A handle for updating signals in place inside a [`transaction`].
+pub struct Transaction {
+    root: &'static Root,
+}
+
+impl Transaction {
+    /// Sets a new value for the signal. This is the same as [`Signal::set`].
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn set<T: MaybeSend>(&self, signal: Signal<T>, value: T) {
+        signal.set(value);
+    }
+
+    /// Updates the value of the signal in place. This is the same as [`Signal::update`], except that
+    /// the previous value can be saved because the type is known to implement `Clone`. The
+    /// previous value is cloned the first time the signal is written in this transaction.
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn update<T: Clone + MaybeSend, U>(
+        &self,
+        signal: Signal<T>,
+        f: impl FnOnce(&mut T) -> U,
+    ) -> U {
+        self.root.save_snapshot(signal.0.id, || {
+            Snapshot::Value(Box::new(signal.get_clone_untracked()))
+        });
+        signal.update_unrestored(f)
+    }
+
+    /// Uses a function to produce a new value for the signal. This is the same as
+    /// [`Signal::set_fn`].
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn set_fn<T: MaybeSend>(&self, signal: Signal<T>, f: impl FnOnce(&T) -> T) {
+        signal.set_fn(f);
+    }
+}
+
+impl fmt::Debug for Transaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let log = self.root.transaction_log.borrow();
+        let written = log.levels.last().map_or(0, |(_, written)| written.len());
+        f.debug_struct("Transaction")
+            .field("written", &written)
+            .finish()
+    }
+}
+
+/// Ends the innermost transaction. If `rollback` is `true`, every signal that was written in it is
+/// restored and the updates that were queued after `queue_start` for these signals are discarded.
+fn end_transaction(root: &Root, rollback: bool, queue_start: usize) {
+    let mut log = root.transaction_log.borrow_mut();
+    let (start, _) = log.levels.pop().expect("no active transaction");
+    let snapshots = if rollback {
+        log.snapshots.split_off(start)
+    } else if log.levels.is_empty() {
+        core::mem::take(&mut log.snapshots)
+    } else {
+        // The snapshots are kept in case the outer transaction is rolled back.
+        Vec::new()
+    };
+    drop(log);
+    if !rollback {
+        return;
+    }
+
+    let mut restored = HashSet::new();
+    let mut replaced = Vec::new();
+    let mut nodes = root.nodes.borrow_mut();
+    // Restore the oldest values last.
+    for (id, snapshot) in snapshots.into_iter().rev() {
+        if let Some(node) = nodes.get_mut(id) {
+            match snapshot {
+                Snapshot::Value(value) => replaced.extend(node.value.replace(value)),
+                Snapshot::Restore(restore) => {
+                    replaced.push(restore(node.value.as_deref_mut().expect("value updating")));
+                }
+                Snapshot::Unsaved => continue,
+            }
+            restored.insert(id);
+        }
+    }
+    drop(nodes);
+    // Drop the values that were written in the transaction outside of the borrow.
+    drop(replaced);
+
+    let mut queue = root.node_update_queue.borrow_mut();
+    let mut index = 0;
+    queue.retain(|id| {
+        index += 1;
+        index <= queue_start || !restored.contains(id)
+    });
+}
+
+/// Runs `f` in a [`batch`] that is rolled back if `f` fails.
+///
+/// If `f` returns `Ok`, the transaction is committed and the dependents of the signals are
+/// updated, just like in a [`batch`]. If `f` returns `Err` or panics, every signal that was
+/// written inside the transaction is silently restored to the value that it had before the
+/// transaction, and none of the effects that depend on these signals are run. Panics are resumed
+/// after the signals are restored.
+///
+/// Writes that replace the value of a signal, such as [`Signal::set`] and [`Signal::set_fn`], are
+/// restored without cloning. Updating a signal in place, e.g. with [`Signal::update`], would
+/// destroy the previous value, so the previous value is cloned first. This works for signals of
+/// primitive types and `String`s, for signals that are tracked by a [`History`] and for signals
+/// that are updated using the [`Transaction`] handle passed to `f`, which knows that the type
+/// implements `Clone`. Signals of other types that are updated in place with [`Signal::update`]
+/// keep their new value when the transaction is rolled back, so prefer [`Transaction::update`] for
+/// them. [`SignalVec`], [`SignalMap`] and [`History`] are restored as well, including the diffs
+/// that were sent to the subscribers of the collections.
+///
+/// Transactions can be nested in other batches and transactions. Rolling back a nested transaction
+/// only reverts the writes of that transaction.
+///
+/// # Example
+/// ```
+/// # use sycamore_reactive::*;
+/// # let _ = create_root(|| {
+/// let name = create_signal(String::from("Alice"));
+/// let tags = create_signal(vec!["admin"]);
+///
+/// let result: Result<(), &str> = transaction(|tx| {
+///     name.set("Bob".to_string());
+///     tx.update(tags, |tags| tags.push("guest"));
+///     if tags.with(|tags| tags.contains(&"admin")) {
+///         return Err("a guest can not be an admin");
+///     }
+///     Ok(())
+/// });
+/// assert!(result.is_err());
+/// assert_eq!(name.get_clone(), "Alice");
+/// assert_eq!(tags.get_clone(), ["admin"]);
+/// # });
+/// ```
+pub fn transaction<T, E>(f: impl FnOnce(&Transaction) -> Result<T, E>) -> Result<T, E> {
+    let root = Root::global();
+    // Writes from other threads would otherwise be recorded in this transaction.
+    let _guard = root.lock.lock();
+    let tx = Transaction { root };
+    let nested = root.start_batch();
+    let queue_start = root.node_update_queue.borrow().len();
+    {
+        let mut log = root.transaction_log.borrow_mut();
+        let start = log.snapshots.len();
+        log.levels.push((start, HashSet::new()));
+    }
+
+    let ret = catch_unwind_in(root, || f(&tx));
+    end_transaction(root, !matches!(ret, Ok(Ok(_))), queue_start);
+    if !nested {
+        root.end_batch();
+    }
+    match ret {
+        Ok(ret) => ret,
+        Err(payload) => resume_unwind(payload),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "sync"))]
+    use std::cell::Cell;
+    #[cfg(all(feature = "std", not(feature = "sync")))]
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    #[cfg(not(feature = "sync"))]
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    #[cfg(not(feature = "sync"))]
+    fn commit() {
+        let _ = create_root(|| {
+            let a = create_signal(1);
+            let b = create_signal(2);
+            let runs = Rc::new(Cell::new(0));
+            create_effect({
+                let runs = Rc::clone(&runs);
+                move || {
+                    a.track();
+                    b.track();
+                    runs.set(runs.get() + 1);
+                }
+            });
+
+            let ret = transaction(|tx| {
+                tx.set(a, 10);
+                tx.update(b, |b| *b += 1);
+                Ok::<_, ()>(a.get() + b.get())
+            });
+            assert_eq!(ret, Ok(13));
+            assert_eq!((a.get(), b.get()), (10, 3));
+            assert_eq!(runs.get(), 2);
+        });
+    }
+
+    #[test]
+    #[cfg(not(feature = "sync"))]
+    fn rollback_on_err() {
+        let _ = create_root(|| {
+            let a = create_signal(1);
+            let b = create_signal(String::from("b"));
+            let double = create_memo(move || a.get() * 2);
+            let runs = Rc::new(Cell::new(0));
+            create_effect({
+                let runs = Rc::clone(&runs);
+                move || {
+                    a.track();
+                    b.track();
+                    runs.set(runs.get() + 1);
+                }
+            });
+
+            let ret = transaction(|tx| {
+                tx.set(a, 2);
+                tx.set(a, 3);
+                tx.update(b, |b| b.push('!'));
+                assert_eq!(a.get(), 3);
+                Err::<(), _>("failed")
+            });
+            assert_eq!(ret, Err("failed"));
+            assert_eq!((a.get(), b.get_clone()), (1, "b".to_string()));
+            assert_eq!(double.get(), 2);
+            assert_eq!(runs.get(), 1, "effects are not run");
+        });
+    }
+
+    #[test]
+    #[cfg(not(feature = "sync"))]
+    #[cfg(feature = "std")]
+    fn rollback_on_panic() {
+        let _ = create_root(|| {
+            let scope = use_current_scope();
+            let a = create_signal(1);
+            let runs = Rc::new(Cell::new(0));
+            create_effect({
+                let runs = Rc::clone(&runs);
+                move || {
+                    a.track();
+                    runs.set(runs.get() + 1);
+                }
+            });
+
+            let ret = catch_unwind(AssertUnwindSafe(|| {
+                transaction(|tx| {
+                    tx.set(a, 2);
+                    panic!("oops");
+                    #[allow(unreachable_code)]
+                    Ok::<(), ()>(())
+                })
+            }));
+            assert!(ret.is_err());
+            assert_eq!(a.get(), 1);
+            assert_eq!(runs.get(), 1);
+            assert_eq!(use_current_scope().0, scope.0);
+
+            // Updates are not batched anymore.
+            a.set(2);
+            assert_eq!(runs.get(), 2);
+        });
+    }
+
+    #[test]
+    fn nested_rollback_keeps_outer_writes() {
+        let _ = create_root(|| {
+            let a = create_signal(0);
+            let b = create_signal(0);
+            let sum = create_memo(move || a.get() + b.get());
+
+            let ret = transaction(|tx| {
+                tx.set(a, 1);
+                let inner = transaction(|tx| {
+                    tx.set(a, 2);
+                    tx.set(b, 2);
+                    Err::<(), _>(())
+                });
+                assert!(inner.is_err());
+                assert_eq!((a.get(), b.get()), (1, 0));
+                Ok::<_, ()>(())
+            });
+            assert!(ret.is_ok());
+            assert_eq!(sum.get(), 1);
+        });
+    }
+
+    #[test]
+    fn direct_writes_are_restored() {
+        let _ = create_root(|| {
+            let a = create_signal(0);
+            let b = create_signal(String::from("b"));
+            let double = create_memo(move || a.get() * 2);
+            let _ = transaction(|_| {
+                a.set(1);
+                a.set_fn(|a| a + 1);
+                b.set_silent("c".to_string());
+                Err::<(), _>(())
+            });
+            assert_eq!((a.get(), b.get_clone()), (0, "b".to_string()));
+            assert_eq!(double.get(), 0);
+        });
+    }
+
+    #[test]
+    fn update_in_place() {
+        let _ = create_root(|| {
+            let mut a = create_signal(0);
+            let b = create_signal(String::from("b"));
+            let double = create_memo(move || a.get() * 2);
+            let ret = transaction(|_| {
+                a += 1;
+                a.update(|a| *a += 1);
+                b.update(|b| b.push('!'));
+                assert_eq!(a.get(), 2);
+                Err::<(), _>(())
+            });
+            assert!(ret.is_err());
+            assert_eq!((a.get(), b.get_clone()), (0, "b".to_string()));
+            assert_eq!(double.get(), 0);
+
+            let ret = transaction(|_| {
+                a += 1;
+                Ok::<_, ()>(())
+            });
+            assert!(ret.is_ok());
+            assert_eq!(double.get(), 2);
+
+            // `Vec` is not known to implement `Clone`, so it is restored using the handle.
+            let c = create_signal(vec![1]);
+            let ret = transaction(|tx| {
+                tx.update(c, |c| c.push(2));
+                // The previous value was saved by the handle.
+                c.update(|c| c.push(3));
+                let d = create_signal(vec![1]);
+                d.update(|d| d.push(2));
+                Err::<(), _>(())
+            });
+            assert!(ret.is_err());
+            assert_eq!(c.get_clone(), [1]);
+        });
+    }
+
+    #[test]
+    fn unsaved_update_keeps_dependents_consistent() {
+        let _ = create_root(|| {
+            let a = create_signal(vec![1]);
+            let len = create_memo(move || a.with(Vec::len));
+            let ret = transaction(|_| {
+                a.update(|a| a.push(2));
+                Err::<(), _>(())
+            });
+            assert!(ret.is_err());
+            // The previous value could not be saved, but the memo is still updated.
+            assert_eq!(a.get_clone(), [1, 2]);
+            assert_eq!(len.get(), 2);
+        });
+    }
+
+    #[test]
+    fn update_never_read_signal() {
+        let _ = create_root(|| {
+            let a = create_signal(vec![1]);
+            let ret = transaction(|tx| {
+                tx.update(a, |a| a.push(2));
+                a.update(|a| a.push(3));
+                Err::<(), _>(())
+            });
+            assert!(ret.is_err());
+            assert_eq!(a.get_clone(), [1]);
+
+            let b = create_signal(vec![1]);
+            let ret = transaction(|tx| {
+                tx.update(b, |b| b.push(2));
+                Ok::<_, ()>(())
+            });
+            assert!(ret.is_ok());
+            assert_eq!(b.get_clone(), [1, 2]);
+        });
+    }
+
+    #[test]
+    fn history_signals_are_restored() {
+        let _ = create_root(|| {
+            let history = create_history(10);
+            let a = history.create_signal(vec![1]);
+            let ret = transaction(|_| {
+                a.update(|a| a.push(2));
+                Err::<(), _>(())
+            });
+            assert!(ret.is_err());
+            assert_eq!(a.get_clone(), [1]);
+            assert!(!history.can_undo());
+        });
+    }
+
+    #[test]
+    fn collections_are_restored() {
+        let _ = create_root(|| {
+            let list = create_signal_vec(vec![1]);
+            let map = create_signal_map([(1, "a")]);
+            let list_diffs = list.subscribe();
+            let map_diffs = map.subscribe();
+            list.push(2);
+
+            let ret = transaction(|_| {
+                list.push(3);
+                list.swap(0, 1);
+                map.insert(2, "b");
+                map.remove(&1);
+                Err::<(), _>(())
+            });
+            assert!(ret.is_err());
+            assert_eq!(list.get_clone(), [1, 2]);
+            assert_eq!(map.get_clone(&1), Some("a"));
+            assert!(!map.contains_key(&2));
+            // The diffs of the failed transaction are discarded.
+            assert_eq!(list_diffs.take(), [VecDiff::Push(2)]);
+            assert!(map_diffs.take().is_empty());
+
+            // Subscribers that took the diffs in the meantime start over.
+            let ret = transaction(|_| {
+                list.push(3);
+                assert_eq!(list_diffs.take(), [VecDiff::Push(3)]);
+                Err::<(), _>(())
+            });
+            assert!(ret.is_err());
+            assert_eq!(list_diffs.take(), [VecDiff::Replace(vec![1, 2])]);
+        });
+    }
+
+    #[test]
+    fn history_is_restored() {
+        let _ = create_root(|| {
+            let history = create_history(10);
+            let a = history.create_signal(0);
+            a.set(1);
+
+            let ret = transaction(|_| {
+                assert!(history.undo());
+                assert_eq!(a.get(), 0);
+                Err::<(), _>(())
+            });
+            assert!(ret.is_err());
+            assert_eq!(a.get(), 1);
+            assert!(history.can_undo());
+            assert!(!history.can_redo());
+            assert!(history.undo());
+            assert_eq!(a.get(), 0);
+        });
+    }
+}
@@ -0,0 +1,59 @@
+//! Tests for using the reactive system without the `std` feature. Run with
+//! `cargo test --no-default-features --tests`.
+
+#![cfg(not(feature = "std"))]
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use sycamore_reactive::*;
+
+/// Stores the current root in a thread-local, just like with `std`, since tests run in parallel.
+static STORAGE: RootStorage = RootStorage {
+    slot: || {
+        thread_local! {
+            static SLOT: &'static RootSlot = Box::leak(Box::new(RootSlot::new()));
+        }
+        SLOT.with(|slot| *slot)
+    },
+};
+
+fn install_storage() {
+    // SAFETY: Every thread has its own slot.
+    unsafe { set_root_storage(&STORAGE) };
+}
+
+#[test]
+fn signals_memos_and_effects() {
+    install_storage();
+    let runs = Rc::new(Cell::new(0));
+    let root = create_root(|| {
+        let count = create_signal(1);
+        let double = create_memo(move || count.get() * 2);
+        create_effect({
+            let runs = Rc::clone(&runs);
+            move || {
+                double.track();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        count.set(2);
+        assert_eq!(double.get(), 4);
+        batch(|| {
+            count.set(3);
+            count.set(4);
+        });
+        assert_eq!(double.get(), 8);
+    });
+    assert_eq!(runs.get(), 3);
+    root.dispose();
+}
+
+#[test]
+fn panics_are_not_caught() {
+    install_storage();
+    let _ = create_root(|| {
+        assert!(catch_panic(|| 1).is_ok());
+    });
+}
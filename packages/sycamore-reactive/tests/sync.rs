@@ -0,0 +1,78 @@
+#![cfg(feature = "sync")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use sycamore_reactive::*;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn handles_are_send_and_sync() {
+    assert_send_sync::<RootHandle>();
+    assert_send_sync::<NodeHandle>();
+    assert_send_sync::<Signal<i32>>();
+    assert_send_sync::<ReadSignal<String>>();
+    assert_send_sync::<MaybeDyn<i32>>();
+    assert_send_sync::<CapturedError>();
+}
+
+#[test]
+fn update_signal_from_other_thread() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let mut state = None;
+    let mut double = None;
+    let root = create_root(|| {
+        let signal = create_signal(1);
+        let memo = create_memo(move || signal.get() * 2);
+        let runs = Arc::clone(&runs);
+        create_effect(move || {
+            memo.track();
+            runs.fetch_add(1, Ordering::SeqCst);
+        });
+        state = Some(signal);
+        double = Some(memo);
+    });
+    let (state, double) = (state.unwrap(), double.unwrap());
+
+    thread::spawn(move || state.set(2)).join().unwrap();
+    assert_eq!(double.get(), 4);
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+    thread::spawn(move || {
+        root.run_in(|| {
+            let triple = create_memo(move || state.get() * 3);
+            state.set(3);
+            assert_eq!(triple.get(), 9);
+        });
+    })
+    .join()
+    .unwrap();
+    assert_eq!(double.get(), 6);
+
+    root.dispose();
+}
+
+#[test]
+fn concurrent_updates() {
+    let mut counter = None;
+    let root = create_root(|| counter = Some(create_signal(0)));
+    let counter = counter.unwrap();
+    let sum = root.run_in(|| create_memo(move || counter.get()));
+
+    let threads = (0..4)
+        .map(|_| {
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    root.run_in(|| batch(|| counter.update(|value| *value += 1)));
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(counter.get(), 400);
+    assert_eq!(sum.get(), 400);
+}
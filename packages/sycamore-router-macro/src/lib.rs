@@ -12,7 +12,21 @@ use syn::{parse_macro_input, DeriveInput};
 /// The `Route` procedural macro.
 ///
 /// This macro derives the `Route` trait for the given `enum`.
-#[proc_macro_derive(Route, attributes(to, not_found))]
+///
+/// Each variant is annotated with one of the following attributes:
+/// - `#[to("/path")]` matches the given path.
+/// - `#[sub_route("/prefix")]` matches the given prefix and delegates the remaining segments to the
+///   nested `Route` type stored in the last field of the variant.
+/// - `#[not_found]` is the fallback route.
+///
+/// For every `#[sub_route]` variant, a `select_*` method named after the variant in snake case is
+/// generated as well (e.g. `select_admin` for `Admin`). It returns a reference to the nested route
+/// if the route is that variant and can be passed to `SubRouter` as its `select` prop.
+///
+/// Routes are matched in declaration order. A route that is shadowed by a route declared before it
+/// (i.e. it can never be matched) is a compile error. Only captures of type `String` and
+/// `Vec<String>` are assumed to always match, since other captures can fail to convert.
+#[proc_macro_derive(Route, attributes(to, sub_route, not_found))]
 pub fn route(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -8,6 +8,8 @@ pub enum SegmentAst {
 #[derive(Debug)]
 pub struct RoutePathAst {
     pub(crate) segments: Vec<SegmentAst>,
+    /// Names of the captured query parameters, in declaration order.
+    pub(crate) query: Vec<String>,
 }
 
 impl RoutePathAst {
@@ -18,6 +20,62 @@ impl RoutePathAst {
             .cloned()
             .collect()
     }
+
+    /// Returns whether this route matches every path that is matched by `other`, ignoring query
+    /// parameters and whether captures can be converted. If this route is tried first, `other` can
+    /// then never be matched.
+    ///
+    /// This is conservative: if it cannot be decided whether a path of `other` is matched by this
+    /// route, it returns `false`.
+    pub fn shadows(&self, other: &RoutePathAst) -> bool {
+        same_structure(&self.segments, &other.segments) || shadows(&self.segments, &other.segments)
+    }
+}
+
+/// Whether the routes have the same segments, ignoring the names of the dynamic segments. Such
+/// routes match exactly the same paths.
+fn same_structure(a: &[SegmentAst], b: &[SegmentAst]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|segments| match segments {
+            (SegmentAst::Param(a), SegmentAst::Param(b)) => a == b,
+            (SegmentAst::DynParam(_), SegmentAst::DynParam(_))
+            | (SegmentAst::DynSegments(_), SegmentAst::DynSegments(_)) => true,
+            _ => false,
+        })
+}
+
+fn shadows(a: &[SegmentAst], b: &[SegmentAst]) -> bool {
+    match (a, b) {
+        ([], b) => b.is_empty(),
+        // A trailing catch-all matches all the remaining segments.
+        ([SegmentAst::DynSegments(_)], _) => true,
+        // A catch-all followed by a static segment captures everything up to the first occurrence
+        // of the static segment. This only works if the segments of `b` are known up to there.
+        // Both catch-alls stop at the first occurrence of the same static segment.
+        (
+            [SegmentAst::DynSegments(_), SegmentAst::Param(a_next), a_rest @ ..],
+            [SegmentAst::DynSegments(_), SegmentAst::Param(b_next), b_rest @ ..],
+        ) if a_next == b_next => shadows(a_rest, b_rest),
+        ([SegmentAst::DynSegments(_), SegmentAst::Param(next), a_rest @ ..], b) => {
+            for (i, segment) in b.iter().enumerate() {
+                match segment {
+                    SegmentAst::Param(param) if param == next => {
+                        return shadows(a_rest, &b[i + 1..]);
+                    }
+                    SegmentAst::Param(_) => {}
+                    SegmentAst::DynParam(_) | SegmentAst::DynSegments(_) => return false,
+                }
+            }
+            false
+        }
+        ([SegmentAst::DynSegments(_), ..], _) => false,
+        (_, [SegmentAst::DynSegments(_), ..]) | (_, []) => false,
+        ([SegmentAst::Param(a_param), a_rest @ ..], [SegmentAst::Param(b_param), b_rest @ ..]) => {
+            a_param == b_param && shadows(a_rest, b_rest)
+        }
+        ([SegmentAst::Param(_), ..], [SegmentAst::DynParam(_), ..]) => false,
+        ([SegmentAst::DynParam(_), a_rest @ ..], [_, b_rest @ ..]) => shadows(a_rest, b_rest),
+    }
 }
 
 #[derive(Debug)]
@@ -28,6 +86,10 @@ pub struct ParseError {
 type Result<T, E = ParseError> = std::result::Result<T, E>;
 
 pub fn parse_route(i: &str) -> Result<RoutePathAst> {
+    let (i, query) = match i.split_once('?') {
+        Some((i, query)) => (i, parse_query(query)?),
+        None => (i, Vec::new()),
+    };
     let i = i.trim_matches('/');
     let segments = i.split('/');
     let mut segments_ast = Vec::with_capacity(segments.size_hint().0);
@@ -59,9 +121,32 @@ pub fn parse_route(i: &str) -> Result<RoutePathAst> {
 
     Ok(RoutePathAst {
         segments: segments_ast,
+        query,
     })
 }
 
+fn parse_query(i: &str) -> Result<Vec<String>> {
+    let mut query = Vec::new();
+    for param in i.split('&') {
+        match param.strip_prefix('<').and_then(|p| p.strip_suffix('>')) {
+            Some(name) if !name.is_empty() && !name.ends_with("..") => {
+                if query.iter().any(|q| q == name) {
+                    return Err(ParseError {
+                        message: format!("duplicate query parameter `{name}`"),
+                    });
+                }
+                query.push(name.to_string());
+            }
+            _ => {
+                return Err(ParseError {
+                    message: "query parameters must be of the form `<name>`".to_string(),
+                });
+            }
+        }
+    }
+    Ok(query)
+}
+
 #[cfg(test)]
 mod tests {
     use expect_test::{expect, Expect};
@@ -74,6 +159,42 @@ mod tests {
         expect.assert_eq(&actual);
     }
 
+    #[track_caller]
+    fn check_shadows(a: &str, b: &str, expected: bool) {
+        let a = parse_route(a).expect("could not parse route");
+        let b = parse_route(b).expect("could not parse route");
+        assert_eq!(a.shadows(&b), expected);
+    }
+
+    #[test]
+    fn shadowed_routes() {
+        check_shadows("/", "/", true);
+        check_shadows("/new", "/new", true);
+        check_shadows("/<id>", "/new", true);
+        check_shadows("/<id>", "/<name>", true);
+        check_shadows("/<id>/edit", "/new/edit", true);
+        check_shadows("/<path..>", "/", true);
+        check_shadows("/<path..>", "/a/<b>/<c..>", true);
+        check_shadows("/files/<path..>", "/files", true);
+        check_shadows("/<path..>/raw", "/a/b/raw", true);
+        check_shadows("/search?<q>", "/search", true);
+        check_shadows("/<path..>/raw", "/<path..>/raw", true);
+        check_shadows("/<a..>/<b>", "/<c..>/<d>", true);
+        check_shadows("/<path..>/raw/<id>", "/<path..>/raw/new", true);
+
+        check_shadows("/new", "/<id>", false);
+        check_shadows("/<id>", "/", false);
+        check_shadows("/<id>", "/a/b", false);
+        check_shadows("/a/b", "/a", false);
+        check_shadows("/<a>/<b>", "/<a..>", false);
+        check_shadows("/<path..>/raw", "/a/<b>/raw", false);
+        check_shadows("/<path..>/raw", "/a/b", false);
+        check_shadows("/<path..>/raw", "/<path..>/edit", false);
+        check_shadows("/<path..>/raw/new", "/<path..>/raw/<id>", false);
+        // The catch-all stops at the first `raw` segment, so the last segment is left over.
+        check_shadows("/<path..>/raw", "/a/raw/raw", false);
+    }
+
     #[test]
     fn index_route() {
         check(
@@ -81,6 +202,7 @@ mod tests {
             expect![[r#"
                 RoutePathAst {
                     segments: [],
+                    query: [],
                 }"#]],
         );
     }
@@ -102,6 +224,7 @@ mod tests {
                             "path",
                         ),
                     ],
+                    query: [],
                 }"#]],
         );
     }
@@ -117,6 +240,7 @@ mod tests {
                             "path",
                         ),
                     ],
+                    query: [],
                 }"#]],
         );
     }
@@ -138,6 +262,7 @@ mod tests {
                             "path",
                         ),
                     ],
+                    query: [],
                 }"#]],
         );
     }
@@ -153,6 +278,7 @@ mod tests {
                             "path",
                         ),
                     ],
+                    query: [],
                 }"#]],
         );
     }
@@ -171,6 +297,7 @@ mod tests {
                             "id",
                         ),
                     ],
+                    query: [],
                 }"#]],
         );
     }
@@ -189,6 +316,7 @@ mod tests {
                             "_",
                         ),
                     ],
+                    query: [],
                 }"#]],
         );
     }
@@ -207,6 +335,40 @@ mod tests {
                             "path",
                         ),
                     ],
+                    query: [],
+                }"#]],
+        );
+    }
+
+    #[test]
+    fn query_params() {
+        check(
+            "/search?<q>&<page>",
+            expect![[r#"
+                RoutePathAst {
+                    segments: [
+                        Param(
+                            "search",
+                        ),
+                    ],
+                    query: [
+                        "q",
+                        "page",
+                    ],
+                }"#]],
+        );
+    }
+
+    #[test]
+    fn query_params_at_root() {
+        check(
+            "/?<q>",
+            expect![[r#"
+                RoutePathAst {
+                    segments: [],
+                    query: [
+                        "q",
+                    ],
                 }"#]],
         );
     }
@@ -225,6 +387,7 @@ mod tests {
                             "segments",
                         ),
                     ],
+                    query: [],
                 }"#]],
         );
     }
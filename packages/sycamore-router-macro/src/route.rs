@@ -1,8 +1,10 @@
-use proc_macro2::TokenStream;
-use quote::{quote, quote_spanned, ToTokens};
-use syn::punctuated::Punctuated;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
-use syn::{DeriveInput, Fields, Ident, LitStr, Token, Variant};
+use syn::{
+    DeriveInput, Field, Fields, GenericArgument, Ident, LitStr, PathArguments, PathSegment, Type,
+    Variant, Visibility,
+};
 
 use crate::parser::{parse_route, RoutePathAst, SegmentAst};
 
@@ -12,6 +14,19 @@ pub fn route_impl(input: DeriveInput) -> syn::Result<TokenStream> {
     // When the `#[not_found]` handler is found, this will store its name so we can use that as the
     // `Default` implementation
     let mut error_handler_name = None;
+    // Whether any of the routes capture query parameters.
+    let mut has_query = false;
+    // Match arms for `Route::to_path`.
+    let mut to_path_arms = Vec::new();
+    // Match arms for `Route::is_not_found`.
+    let mut is_not_found_arms = Vec::new();
+    // Statements that push the routes without dynamic parameters for `Route::static_routes`.
+    let mut static_routes = Vec::new();
+    // Methods that select the nested route of the `#[sub_route]` variants, for `SubRouter`.
+    let mut select_fns = Vec::new();
+    // The routes of the previous variants, used for detecting shadowed routes.
+    let mut previous_routes: Vec<(&Ident, Span, RoutePathAst, bool)> = Vec::new();
+    let mut shadowed_err: Option<syn::Error> = None;
 
     match &input.data {
         syn::Data::Enum(de) => {
@@ -22,8 +37,11 @@ pub fn route_impl(input: DeriveInput) -> syn::Result<TokenStream> {
 
                 let mut quote_capture_vars = TokenStream::new();
                 let mut route_path_ast = None;
+                let mut route_span = None;
 
                 let mut is_to_route = false;
+                let mut is_sub_route = false;
+                let mut is_not_found = false;
 
                 for attr in &variant.attrs {
                     let attr_name = match attr.path().get_ident() {
@@ -43,9 +61,92 @@ pub fn route_impl(input: DeriveInput) -> syn::Result<TokenStream> {
                                 }
                             };
                             // endregion
-                            quote_capture_vars.extend(impl_to(variant, variant_id, &route)?);
+                            if is_to_route {
+                                return Err(syn::Error::new(
+                                    attr.span(),
+                                    "cannot have more than one route attribute on a variant",
+                                ));
+                            }
+                            quote_capture_vars.extend(impl_to(variant, variant_id, &route, false)?);
+                            route_path_ast = Some(route);
+                            route_span = Some(route_litstr.span());
+                            is_to_route = true;
+                        }
+                        "sub_route" => {
+                            // region: parse route prefix
+                            let route_litstr: LitStr = attr.parse_args()?;
+                            let route_str = route_litstr.value();
+                            let mut route = match parse_route(&route_str) {
+                                Ok(route_ast) => route_ast,
+                                Err(err) => {
+                                    return Err(syn::Error::new(route_litstr.span(), err.message));
+                                }
+                            };
+                            if route
+                                .segments
+                                .iter()
+                                .any(|s| matches!(s, SegmentAst::DynSegments(_)))
+                            {
+                                return Err(syn::Error::new(
+                                    route_litstr.span(),
+                                    "sub route prefix cannot contain dynamic segments",
+                                ));
+                            }
+                            if !route.query.is_empty() {
+                                return Err(syn::Error::new(
+                                    route_litstr.span(),
+                                    "sub route prefix cannot contain query parameters",
+                                ));
+                            }
+                            // endregion
+                            if is_to_route {
+                                return Err(syn::Error::new(
+                                    attr.span(),
+                                    "cannot have more than one route attribute on a variant",
+                                ));
+                            }
+                            // The remaining segments are captured by the last field, which holds
+                            // the nested route.
+                            let nested_field = match variant.fields.iter().last() {
+                                Some(field) => field,
+                                None => {
+                                    return Err(syn::Error::new(
+                                        variant.span(),
+                                        "sub route must have a field for the nested route",
+                                    ));
+                                }
+                            };
+                            let nested_name = nested_field
+                                .ident
+                                .as_ref()
+                                .map(ToString::to_string)
+                                .unwrap_or_else(|| "_".to_string());
+                            route.segments.push(SegmentAst::DynSegments(nested_name));
+                            // The route is not found if the nested route is not found.
+                            let pattern = match &variant.fields {
+                                Fields::Named(_) => {
+                                    let name = nested_field.ident.as_ref().unwrap();
+                                    quote! { Self::#variant_id { #name: __nested, .. } }
+                                }
+                                _ => {
+                                    let skipped = (1..variant.fields.len()).map(|_| quote!(_));
+                                    quote! { Self::#variant_id(#(#skipped,)* __nested) }
+                                }
+                            };
+                            is_not_found_arms.push(quote! {
+                                #pattern => ::sycamore_router::Route::is_not_found(__nested),
+                            });
+                            select_fns.push(impl_select(
+                                &input.vis,
+                                variant_id,
+                                &nested_field.ty,
+                                &pattern,
+                            ));
+                            quote_capture_vars.extend(impl_to(variant, variant_id, &route, true)?);
                             route_path_ast = Some(route);
+                            route_span = Some(route_litstr.span());
                             is_to_route = true;
+                            is_sub_route = true;
                         }
                         "not_found" => {
                             if error_handler_name.is_some() {
@@ -63,13 +164,18 @@ pub fn route_impl(input: DeriveInput) -> syn::Result<TokenStream> {
                             err_quoted = quote! {
                                 return Self::#variant_id;
                             };
+                            is_not_found_arms.push(quote! {
+                                Self::#variant_id => true,
+                            });
                             error_handler_name = Some(quote!(Self::#variant_id));
+                            is_not_found = true;
                         }
                         _ => {}
                     }
                 }
                 if is_to_route {
                     let route_path_ast = route_path_ast.unwrap();
+                    has_query |= !route_path_ast.query.is_empty();
                     quoted.extend(quote! {
                         let __route = #route_path_ast;
                         if let Some(__captures) = __route.match_path(__segments) {
@@ -77,9 +183,65 @@ pub fn route_impl(input: DeriveInput) -> syn::Result<TokenStream> {
                             #quote_capture_vars
                         }
                     });
+                    to_path_arms.push(impl_to_path(variant, &route_path_ast, is_sub_route));
+                    if !is_not_found {
+                        static_routes.extend(impl_static_routes(variant, is_sub_route));
+                    }
+
+                    // Routes are tried in declaration order, so a route that only matches paths
+                    // that are already matched by a previous route can never be reached.
+                    let route_span = route_span.unwrap();
+                    let shadowed_by =
+                        previous_routes
+                            .iter()
+                            .find(|(_, _, previous, always_matches)| {
+                                *always_matches && previous.shadows(&route_path_ast)
+                            });
+                    if let Some((previous_id, previous_span, _, _)) = shadowed_by {
+                        let mut err = syn::Error::new(
+                            route_span,
+                            format!(
+                                "route `{variant_id}` is unreachable because `{previous_id}` is \
+                                 declared before it and matches all of its paths"
+                            ),
+                        );
+                        err.combine(syn::Error::new(
+                            *previous_span,
+                            format!(
+                                "`{previous_id}` is declared here, consider moving `{variant_id}` \
+                                 before it"
+                            ),
+                        ));
+                        match &mut shadowed_err {
+                            Some(shadowed_err) => shadowed_err.combine(err),
+                            None => shadowed_err = Some(err),
+                        }
+                    }
+                    // A route with required query parameters does not match paths without them,
+                    // and a route whose captures can fail to convert (e.g. `<id>` captured as a
+                    // `u32`) falls through to the next routes. The nested route of a sub route
+                    // always matches, because it falls back to its own not found route.
+                    let dyn_segments = route_path_ast.dyn_segments().len();
+                    let always_matches = variant.fields.iter().enumerate().all(|(i, field)| {
+                        if is_sub_route && i == variant.fields.len() - 1 {
+                            true
+                        } else if i < dyn_segments {
+                            is_infallible_capture(&field.ty)
+                        } else {
+                            is_option(&field.ty)
+                        }
+                    });
+                    previous_routes.push((variant_id, route_span, route_path_ast, always_matches));
+                } else {
+                    to_path_arms.push(quote! {
+                        Self::#variant_id { .. } => ::std::option::Option::None,
+                    });
                 }
             }
 
+            if let Some(err) = shadowed_err {
+                return Err(err);
+            }
             if error_handler_name.is_none() {
                 return Err(syn::Error::new(
                     input.span(),
@@ -87,12 +249,52 @@ pub fn route_impl(input: DeriveInput) -> syn::Result<TokenStream> {
                 ));
             }
 
+            // Only parse the query string if it is actually needed.
+            let parse_query = if has_query {
+                quote! {
+                    let __query_params = ::sycamore_router::QueryParams::parse(__query);
+                }
+            } else {
+                quote! {
+                    let _ = __query;
+                }
+            };
+
             Ok(quote! {
                 impl ::sycamore_router::Route for #ty_name {
                     fn match_route(&self, __segments: &[&str]) -> Self {
+                        let __query = ::sycamore_router::query_from_segments(__segments);
+                        ::sycamore_router::Route::match_route_with_query(self, __segments, __query)
+                    }
+
+                    fn match_route_with_query(&self, __segments: &[&str], __query: &str) -> Self {
+                        #parse_query
                         #quoted
                         #err_quoted
                     }
+
+                    fn to_path(&self) -> ::std::option::Option<::std::string::String> {
+                        match self {
+                            #(#to_path_arms)*
+                        }
+                    }
+
+                    fn static_routes() -> ::std::vec::Vec<Self> {
+                        let mut __routes = ::std::vec::Vec::new();
+                        #(#static_routes)*
+                        __routes
+                    }
+
+                    fn is_not_found(&self) -> bool {
+                        #[allow(unreachable_patterns)]
+                        match self {
+                            #(#is_not_found_arms)*
+                            _ => false,
+                        }
+                    }
+                }
+                impl #ty_name {
+                    #(#select_fns)*
                 }
                 // We implement `Default` as well here for the `Router`/`RouterBase` distinction (`Router` needs to pass a default `impl Route` to `RouterBase`)
                 impl ::std::default::Default for #ty_name {
@@ -109,14 +311,55 @@ pub fn route_impl(input: DeriveInput) -> syn::Result<TokenStream> {
     }
 }
 
-/// Implementation for `#[to(_)]` attribute.
+/// Generates the `select_*` method of a `#[sub_route(_)]` variant, which returns the nested route
+/// if the route is that variant.
+fn impl_select(
+    vis: &Visibility,
+    variant_id: &Ident,
+    nested_ty: &Type,
+    pattern: &TokenStream,
+) -> TokenStream {
+    let fn_name = format_ident!("select_{}", to_snake_case(&variant_id.to_string()));
+    let doc = format!("Returns the nested route if this is [`Self::{variant_id}`].");
+    quote! {
+        #[doc = #doc]
+        #[allow(unreachable_patterns)]
+        #vis fn #fn_name(&self) -> ::std::option::Option<&#nested_ty> {
+            match self {
+                #pattern => ::std::option::Option::Some(__nested),
+                _ => ::std::option::Option::None,
+            }
+        }
+    }
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i != 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// Implementation for `#[to(_)]` and `#[sub_route(_)]` attributes.
+///
+/// If `sub_route` is `true`, the last capture is matched against the nested route type instead of
+/// using [`TryFromSegments`](sycamore_router::TryFromSegments).
 fn impl_to(
     variant: &Variant,
     variant_id: &Ident,
     route: &RoutePathAst,
+    sub_route: bool,
 ) -> Result<TokenStream, syn::Error> {
     let dyn_segments = route.dyn_segments();
-    let expected_fields_len = dyn_segments.len();
+    let expected_fields_len = dyn_segments.len() + route.query.len();
     if expected_fields_len != variant.fields.len() {
         return Err(syn::Error::new(
             variant.fields.span(),
@@ -125,107 +368,291 @@ fn impl_to(
         ));
     }
 
-    Ok(match &variant.fields {
-        // For named fields, captures must match the field name.
-        Fields::Named(f) => {
-            let mut captures = Vec::new();
-
-            for (i, (field, segment)) in f.named.iter().zip(dyn_segments.iter()).enumerate() {
-                match segment {
-                    SegmentAst::Param(_) => unreachable!("not a dynamic segment"),
-                    SegmentAst::DynParam(param) => {
-                        if param != &field.ident.as_ref().unwrap().to_string() {
-                            return Err(syn::Error::new(
-                                field.ident.span(),
-                                format!(
-                                    "capture field name mismatch (expected `{}`, found `{}`)",
-                                    param,
-                                    field.ident.as_ref().unwrap()
-                                ),
-                            ));
-                        }
-                        let param_id: Ident = syn::parse_str(param)?;
-                        captures.push(quote! {
-                            let #param_id = match ::sycamore_router::TryFromParam::try_from_param(
-                                __captures[#i].as_dyn_param().unwrap()
-                            ) {
-                                ::std::option::Option::Some(__value) => __value,
-                                ::std::option::Option::None => break,
-                            };
-                        })
-                    }
-                    SegmentAst::DynSegments(param) => {
-                        if param != &field.ident.as_ref().unwrap().to_string() {
-                            return Err(syn::Error::new(
-                                field.ident.span(),
-                                format!(
-                                    "capture field name mismatch (expected `{}`, found `{}`)",
-                                    param,
-                                    field.ident.as_ref().unwrap()
-                                ),
-                            ));
+    // Captures from path segments come first, followed by the query parameters.
+    let mut captures = Vec::new();
+    for (i, (field, segment)) in variant.fields.iter().zip(dyn_segments.iter()).enumerate() {
+        let name = match segment {
+            SegmentAst::Param(_) => unreachable!("not a dynamic segment"),
+            SegmentAst::DynParam(param) | SegmentAst::DynSegments(param) => param,
+        };
+        check_field_name(field, name)?;
+        captures.push(match segment {
+            SegmentAst::Param(_) => unreachable!("not a dynamic segment"),
+            SegmentAst::DynParam(_) => quote! {
+                match ::sycamore_router::TryFromParam::try_from_param(
+                    &::sycamore_router::decode_path_segment(__captures[#i].as_dyn_param().unwrap())
+                ) {
+                    ::std::option::Option::Some(__value) => __value,
+                    ::std::option::Option::None => break,
+                }
+            },
+            SegmentAst::DynSegments(_) if sub_route && i == expected_fields_len - 1 => quote! {
+                ::sycamore_router::Route::match_route_with_query(
+                    &::std::default::Default::default(),
+                    __captures[#i].as_dyn_segments().unwrap(),
+                    __query,
+                )
+            },
+            SegmentAst::DynSegments(_) => quote! {
+                match ::sycamore_router::TryFromSegments::try_from_segments(
+                    __captures[#i].as_dyn_segments().unwrap()
+                ) {
+                    ::std::option::Option::Some(__value) => __value,
+                    ::std::option::Option::None => break,
+                }
+            },
+        });
+    }
+    for (field, param) in variant
+        .fields
+        .iter()
+        .skip(dyn_segments.len())
+        .zip(route.query.iter())
+    {
+        check_field_name(field, param)?;
+        // Optional query parameters are allowed to be missing. Everything else must be present
+        // for the route to match.
+        captures.push(if is_option(&field.ty) {
+            quote! {
+                match __query_params.get(#param) {
+                    ::std::option::Option::Some(__param) => {
+                        match ::sycamore_router::TryFromParam::try_from_param(__param) {
+                            ::std::option::Option::Some(__value) => ::std::option::Option::Some(__value),
+                            ::std::option::Option::None => break,
                         }
-                        let param_id: Ident = syn::parse_str(param)?;
-                        captures.push(quote! {
-                            let #param_id = match ::sycamore_router::TryFromSegments::try_from_segments(
-                                __captures[#i].as_dyn_segments().unwrap()
-                            ) {
-                                ::std::option::Option::Some(__value) => __value,
-                                ::std::option::Option::None => break,
-                            };
-                        })
                     }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                }
+            }
+        } else {
+            quote! {
+                match __query_params
+                    .get(#param)
+                    .and_then(::sycamore_router::TryFromParam::try_from_param)
+                {
+                    ::std::option::Option::Some(__value) => __value,
+                    ::std::option::Option::None => break,
                 }
             }
-            let named: Punctuated<&Option<Ident>, Token![,]> =
-                f.named.iter().map(|x| &x.ident).collect();
+        });
+    }
+
+    Ok(match &variant.fields {
+        // For named fields, captures must match the field name.
+        Fields::Named(f) => {
+            let named = f.named.iter().map(|x| &x.ident);
             quote_spanned! {variant.span()=>
                 #[allow(clippy::never_loop)]
                 #[allow(clippy::while_let_loop)]
                 loop {
-                    #(#captures)*
                     return Self::#variant_id {
-                        #named
+                        #(#named: #captures),*
                     };
                 }
             }
         }
         // For unnamed fields, captures must be in right order.
-        Fields::Unnamed(_) => {
-            let mut captures = Vec::new();
-
-            for (i, segment) in dyn_segments.iter().enumerate() {
-                match segment {
-                    SegmentAst::Param(_) => unreachable!("not a dynamic segment"),
-                    SegmentAst::DynParam(_) => captures.push(quote! {{
-                        match ::sycamore_router::TryFromParam::try_from_param(
-                            __captures[#i].as_dyn_param().unwrap()
-                        ) {
-                            ::std::option::Option::Some(__value) => __value,
-                            ::std::option::Option::None => break,
-                        }
-                    }}),
-                    SegmentAst::DynSegments(_) => captures.push(quote! {{
-                        match ::sycamore_router::TryFromSegments::try_from_segments(
-                            __captures[#i].as_dyn_segments().unwrap()
-                        ) {
-                            ::std::option::Option::Some(__value) => __value,
-                            ::std::option::Option::None => break,
-                        }
-                    }}),
+        Fields::Unnamed(_) => quote! {
+            // Run captures inside a loop in order to allow early break inside the expression.
+            #[allow(clippy::never_loop)]
+            loop {
+                return Self::#variant_id(#(#captures),*);
+            }
+        },
+        Fields::Unit => quote! {
+            return Self::#variant_id;
+        },
+    })
+}
+
+/// Implementation of `Route::static_routes` for a `#[to(_)]` or `#[sub_route(_)]` variant. Returns
+/// a statement that pushes the routes of the variant to `__routes`, or `None` if the variant has
+/// dynamic parameters.
+///
+/// The routes of a sub route are the static routes of the nested route, as long as the prefix does
+/// not have dynamic parameters.
+fn impl_static_routes(variant: &Variant, sub_route: bool) -> Option<TokenStream> {
+    let variant_id = &variant.ident;
+    if !sub_route {
+        return variant
+            .fields
+            .is_empty()
+            .then(|| quote! { __routes.push(Self::#variant_id); });
+    }
+    if variant.fields.len() != 1 {
+        return None;
+    }
+    let nested = variant.fields.iter().next().unwrap();
+    let nested_ty = &nested.ty;
+    let construct = match &nested.ident {
+        Some(name) => quote! { Self::#variant_id { #name: __nested } },
+        None => quote! { Self::#variant_id(__nested) },
+    };
+    Some(quote! {
+        __routes.extend(
+            <#nested_ty as ::sycamore_router::Route>::static_routes()
+                .into_iter()
+                .filter(|__nested| !::sycamore_router::Route::is_not_found(__nested))
+                .map(|__nested| #construct),
+        );
+    })
+}
+
+/// Implementation of `Route::to_path` for a `#[to(_)]` or `#[sub_route(_)]` variant. Returns a
+/// match arm.
+///
+/// Fields are bound to captures in the same order as in [`impl_to`].
+fn impl_to_path(variant: &Variant, route: &RoutePathAst, sub_route: bool) -> TokenStream {
+    let variant_id = &variant.ident;
+    let bindings = (0..variant.fields.len())
+        .map(|i| Ident::new(&format!("__field{i}"), variant.span()))
+        .collect::<Vec<_>>();
+    let pattern = match &variant.fields {
+        Fields::Named(f) => {
+            let named = f.named.iter().map(|x| &x.ident);
+            quote! { Self::#variant_id { #(#named: #bindings),* } }
+        }
+        Fields::Unnamed(_) => quote! { Self::#variant_id(#(#bindings),*) },
+        Fields::Unit => quote! { Self::#variant_id },
+    };
+
+    let mut bindings_iter = bindings.iter();
+    let mut push_segments = Vec::new();
+    for segment in &route.segments {
+        push_segments.push(match segment {
+            SegmentAst::Param(param) => quote! {
+                __path.push('/');
+                __path.push_str(#param);
+            },
+            SegmentAst::DynParam(_) => {
+                let binding = bindings_iter.next().unwrap();
+                quote! {
+                    __path.push('/');
+                    __path.push_str(&::sycamore_router::encode_path_segment(
+                        &::sycamore_router::ToParam::to_param(#binding)
+                    ));
                 }
             }
+            SegmentAst::DynSegments(_) if sub_route && bindings_iter.len() == 1 => {
+                let binding = bindings_iter.next().unwrap();
+                quote! {
+                    let __nested = ::sycamore_router::Route::to_path(#binding)?;
+                    let __nested = __nested.strip_prefix('/').unwrap_or(&__nested);
+                    if !__nested.is_empty() && !__nested.starts_with('?') {
+                        __path.push('/');
+                    }
+                    __path.push_str(__nested);
+                }
+            }
+            SegmentAst::DynSegments(_) => {
+                let binding = bindings_iter.next().unwrap();
+                quote! {
+                    for __segment in ::sycamore_router::ToSegments::to_segments(#binding)? {
+                        __path.push('/');
+                        __path.push_str(&__segment);
+                    }
+                }
+            }
+        });
+    }
+
+    let mut push_query = Vec::new();
+    for ((field, binding), param) in variant
+        .fields
+        .iter()
+        .zip(bindings_iter)
+        .zip(route.query.iter())
+    {
+        let push_param = quote! {
+            __path.push(if __has_query { '&' } else { '?' });
+            __has_query = true;
+            __path.push_str(&::sycamore_router::encode_query_component(#param));
+            __path.push('=');
+            __path.push_str(&::sycamore_router::encode_query_component(
+                &::sycamore_router::ToParam::to_param(__value)
+            ));
+        };
+        // Missing optional query parameters are omitted.
+        push_query.push(if is_option(&field.ty) {
             quote! {
-                // Run captures inside a loop in order to allow early break inside the expression.
-                loop {
-                    return Self::#variant_id(#(#captures),*);
+                if let ::std::option::Option::Some(__value) = #binding {
+                    #push_param
                 }
             }
+        } else {
+            quote! {
+                let __value = #binding;
+                #push_param
+            }
+        });
+    }
+    let query = if push_query.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            let mut __has_query = false;
+            #(#push_query)*
         }
-        Fields::Unit => quote! {
-            return Self::#variant_id;
+    };
+
+    quote! {
+        #pattern => {
+            let mut __path = ::std::string::String::new();
+            #(#push_segments)*
+            if __path.is_empty() {
+                __path.push('/');
+            }
+            #query
+            ::std::option::Option::Some(__path)
+        }
+    }
+}
+
+/// Checks that the name of a named field matches the name of its capture.
+fn check_field_name(field: &Field, capture: &str) -> Result<(), syn::Error> {
+    match &field.ident {
+        Some(ident) if ident != capture => Err(syn::Error::new(
+            ident.span(),
+            format!("capture field name mismatch (expected `{capture}`, found `{ident}`)"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Returns whether the type is syntactically an `Option<_>`.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(ty) => ty
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+/// Returns whether converting a captured segment into `ty` can never fail, i.e. `ty` is `String` or
+/// `Vec<String>`. Other types are assumed to be fallible.
+fn is_infallible_capture(ty: &Type) -> bool {
+    let is_string =
+        |ty: &Type| matches!(last_path_segment(ty), Some(segment) if segment.ident == "String");
+    match last_path_segment(ty) {
+        Some(segment) if segment.ident == "String" => true,
+        Some(segment) if segment.ident == "Vec" => match &segment.arguments {
+            PathArguments::AngleBracketed(args) => {
+                matches!(args.args.first(), Some(GenericArgument::Type(ty)) if is_string(ty))
+            }
+            _ => false,
         },
-    })
+        _ => false,
+    }
+}
+
+fn last_path_segment(ty: &Type) -> Option<&PathSegment> {
+    match ty {
+        Type::Path(ty) => ty.path.segments.last(),
+        _ => None,
+    }
 }
 
 impl ToTokens for SegmentAst {
@@ -45,4 +45,44 @@ enum Routes7 {
     NotFound,
 }
 
+#[derive(Route)]
+enum Routes8 {
+    #[to("/files/<path..>")]
+    Files { path: Vec<String> },
+    #[to("/files/readme")]
+    Readme, // Shadowed by catch-all
+    #[to("/files/<name>/raw")]
+    Raw { name: String }, // Shadowed by catch-all
+    #[not_found]
+    NotFound,
+}
+
+#[derive(Route)]
+enum AdminRoutes {
+    #[to("/")]
+    Index,
+    #[not_found]
+    NotFound,
+}
+
+#[derive(Route)]
+enum Routes9 {
+    #[sub_route("/admin")]
+    Admin(AdminRoutes),
+    #[to("/admin/users")]
+    Users, // Shadowed by sub route
+    #[not_found]
+    NotFound,
+}
+
+#[derive(Route)]
+enum Routes10 {
+    #[to("/<path..>/raw")]
+    Raw { path: Vec<String> },
+    #[to("/<path..>/raw")]
+    RawAgain { path: Vec<String> }, // Shadowed by identical route
+    #[not_found]
+    NotFound,
+}
+
 fn main() {}
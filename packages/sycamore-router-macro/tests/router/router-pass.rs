@@ -36,4 +36,57 @@ enum Routes4 {
     NotFound,
 }
 
+#[derive(Route)]
+enum Routes5 {
+    #[to("/")]
+    Home,
+    #[sub_route("/nested")]
+    Nested(Routes2),
+    #[sub_route("/org/<org>")]
+    Org { org: String, route: Routes2 },
+    #[not_found]
+    NotFound,
+}
+
+#[derive(Route)]
+enum Routes6 {
+    #[to("/search?<q>&<page>")]
+    Search { q: String, page: Option<u32> },
+    #[to("/tag/<tag>?<sort>")]
+    Tag(String, String),
+    #[not_found]
+    NotFound,
+}
+
+#[derive(Route)]
+enum Routes7 {
+    // Static routes are declared before the dynamic routes that would also match them.
+    #[to("/posts/new")]
+    NewPost,
+    #[to("/posts/<id>")]
+    Post { id: u32 },
+    #[to("/search?<q>")]
+    Search { q: String },
+    // Not shadowed because `Search` requires the `q` query parameter.
+    #[to("/search")]
+    SearchPage,
+    #[to("/files/<path..>/raw")]
+    Raw { path: Vec<String> },
+    #[to("/files/<path..>")]
+    Files { path: Vec<String> },
+    #[not_found]
+    NotFound,
+}
+
+#[derive(Route)]
+enum Routes8 {
+    // Not shadowed because `new` cannot be converted into a `u32`.
+    #[to("/posts/<id>")]
+    Post { id: u32 },
+    #[to("/posts/new")]
+    NewPost,
+    #[not_found]
+    NotFound,
+}
+
 fn main() {}
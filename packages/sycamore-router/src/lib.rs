@@ -6,11 +6,16 @@
 // Alias self to sycamore_router for proc-macros.
 extern crate self as sycamore_router;
 
+mod link;
 mod router;
+mod ssg;
 
+use std::fmt::Display;
 use std::str::FromStr;
 
+pub use link::*;
 pub use router::*;
+pub use ssg::*;
 pub use sycamore_router_macro::Route;
 
 /// Trait that is implemented for `enum`s that can match routes.
@@ -24,16 +29,178 @@ pub trait Route: Sized + Default {
     /// It is likely that you are looking for the [`Route::match_path`] method instead.
     fn match_route(&self, segments: &[&str]) -> Self;
 
-    /// Matches a route with the given path.
+    /// Matches a route with the given path segments and query string (without the leading `?`).
+    ///
+    /// By default, the query string is ignored.
+    fn match_route_with_query(&self, segments: &[&str], query: &str) -> Self {
+        let _ = query;
+        self.match_route(segments)
+    }
+
+    /// Matches a route with the given path. The path can include a query string and a hash.
     fn match_path(&self, path: &str) -> Self {
+        let path = path.split('#').next().unwrap();
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
         let segments = path
             .split('/')
             .filter(|s| !s.is_empty())
             .collect::<Vec<_>>();
-        self.match_route(&segments)
+        self.match_route_with_query(&segments, query)
+    }
+
+    /// Returns the path of this route, including the query string if the route captures query
+    /// parameters. This is the inverse of [`Route::match_path`].
+    ///
+    /// Dynamic parameters are converted using [`ToParam`] and [`ToSegments`] and are
+    /// percent-encoded.
+    ///
+    /// Returns `None` if the route does not have a path, e.g. the `#[not_found]` route when it is
+    /// not also marked with `#[to(_)]`, or a `#[sub_route(_)]` whose nested route does not have a
+    /// path. By default, this always returns `None`.
+    fn to_path(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns all the routes that do not have any dynamic parameters, in declaration order. Routes
+    /// of a `#[sub_route]` are included if the prefix does not have any dynamic parameters. The
+    /// `#[not_found]` route is never included.
+    ///
+    /// This is used for static site generation. See [`generate_static_site`].
+    fn static_routes() -> Vec<Self> {
+        Vec::new()
+    }
+
+    /// Returns `true` if this is the `#[not_found]` route, or if this route is a nested route whose
+    /// nested route is not found.
+    ///
+    /// This is used by [`StaticRouter`] to respond with a `404` status code when rendering on the
+    /// server.
+    fn is_not_found(&self) -> bool {
+        false
     }
 }
 
+/// Extracts the query string from the last segment of a path, if any. This is used by the
+/// [`Route`](derive@Route) derive macro to implement [`Route::match_route`].
+#[doc(hidden)]
+pub fn query_from_segments<'a>(segments: &[&'a str]) -> &'a str {
+    segments
+        .last()
+        .and_then(|last| last.split('#').next().unwrap().split_once('?'))
+        .map_or("", |(_, query)| query)
+}
+
+/// The parsed key/value pairs of a query string.
+///
+/// Keys and values are percent-decoded and `+` is decoded as a space.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryParams {
+    params: Vec<(String, String)>,
+}
+
+impl QueryParams {
+    /// Parses a query string. A leading `?` is ignored.
+    pub fn parse(query: &str) -> Self {
+        let query = query.strip_prefix('?').unwrap_or(query);
+        let params = query
+            .split('&')
+            .filter(|s| !s.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (decode_query_component(key), decode_query_component(value))
+            })
+            .collect();
+        Self { params }
+    }
+
+    /// Returns the value of the first parameter with the given `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the values of all the parameters with the given `key`.
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.params
+            .iter()
+            .filter(move |(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns an iterator over all the key/value pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+/// Decodes a `application/x-www-form-urlencoded` component. Invalid escape sequences are left
+/// as is.
+fn decode_query_component(s: &str) -> String {
+    percent_decode(s, true)
+}
+
+/// Decodes a percent-encoded path segment. This is used by the [`Route`](derive@Route) derive
+/// macro before converting captures using [`TryFromParam`].
+#[doc(hidden)]
+pub fn decode_path_segment(s: &str) -> String {
+    percent_decode(s, false)
+}
+
+/// Percent-encodes a path segment. This is used by the [`Route`](derive@Route) derive macro to
+/// implement [`Route::to_path`].
+#[doc(hidden)]
+pub fn encode_path_segment(s: &str) -> String {
+    // Characters that are allowed in a path segment, in addition to alphanumeric characters.
+    percent_encode(s, b"-._~!$&'()*+,;=:@")
+}
+
+/// Percent-encodes a query string key or value. This is used by the [`Route`](derive@Route)
+/// derive macro to implement [`Route::to_path`].
+#[doc(hidden)]
+pub fn encode_query_component(s: &str) -> String {
+    percent_encode(s, b"-._~!$'()*,;:@/?")
+}
+
+fn percent_encode(s: &str, allowed: &[u8]) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for &byte in s.as_bytes() {
+        if byte.is_ascii_alphanumeric() || allowed.contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn percent_decode(s: &str, plus_as_space: bool) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' if plus_as_space => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                match hex {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
 /// Represents an URL segment or segments.
 #[derive(Clone, Debug)]
 pub enum Segment {
@@ -183,7 +350,7 @@ where
     fn try_from_segments(segments: &[&str]) -> Option<Self> {
         let mut tmp = Vec::with_capacity(segments.len());
         for segment in segments {
-            let value = T::try_from_param(segment)?;
+            let value = T::try_from_param(&decode_path_segment(segment))?;
             tmp.push(value);
         }
         Some(tmp)
@@ -198,6 +365,55 @@ impl<T: Route> TryFromSegments for T {
     }
 }
 
+/// Conversion of a value into a param. This is the inverse of [`TryFromParam`].
+///
+/// Implemented for all types that implement [`Display`] by default.
+pub trait ToParam {
+    /// Converts this value into a param. The result does not need to be percent-encoded.
+    fn to_param(&self) -> String;
+}
+
+impl<T> ToParam for T
+where
+    T: Display + ?Sized,
+{
+    fn to_param(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Conversion of a value into a list of segments. This is the inverse of [`TryFromSegments`].
+pub trait ToSegments {
+    /// Converts this value into a list of percent-encoded segments. Returns `None` if the value
+    /// cannot be converted.
+    fn to_segments(&self) -> Option<Vec<String>>;
+}
+
+impl<T> ToSegments for Vec<T>
+where
+    T: ToParam,
+{
+    fn to_segments(&self) -> Option<Vec<String>> {
+        Some(
+            self.iter()
+                .map(|value| encode_path_segment(&value.to_param()))
+                .collect(),
+        )
+    }
+}
+
+impl<T: Route> ToSegments for T {
+    fn to_segments(&self) -> Option<Vec<String>> {
+        let path = self.to_path()?;
+        Some(
+            path.split('/')
+                .filter(|s| !s.is_empty())
+                .map(ToString::to_string)
+                .collect(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use Segment::*;
@@ -392,6 +608,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn query_params_decode() {
+        let params = QueryParams::parse("?q=hello+world&tag=a%26b&tag=c&empty&bad=%zz");
+        assert_eq!(params.get("q"), Some("hello world"));
+        assert_eq!(params.get("tag"), Some("a&b"));
+        assert_eq!(params.get_all("tag").collect::<Vec<_>>(), vec!["a&b", "c"]);
+        assert_eq!(params.get("empty"), Some(""));
+        assert_eq!(params.get("bad"), Some("%zz"));
+        assert_eq!(params.get("missing"), None);
+    }
+
     mod integration {
         use crate::*;
 
@@ -450,6 +677,39 @@ mod tests {
             );
         }
 
+        #[test]
+        fn router_dyn_params_are_decoded() {
+            #[derive(Debug, PartialEq, Eq, Route)]
+            enum Routes {
+                #[to("/hello/<name>")]
+                Hello { name: String },
+                #[to("/files/<path..>")]
+                Files { path: Vec<String> },
+                #[not_found]
+                NotFound,
+            }
+
+            assert_eq!(
+                Routes::default().match_path("/hello/hello%20world%2F%C3%BC"),
+                Routes::Hello {
+                    name: "hello world/ü".to_string()
+                }
+            );
+            // `+` is only a space in query strings.
+            assert_eq!(
+                Routes::default().match_path("/hello/a+b"),
+                Routes::Hello {
+                    name: "a+b".to_string()
+                }
+            );
+            assert_eq!(
+                Routes::default().match_path("/files/a/b%20c"),
+                Routes::Files {
+                    path: vec!["a".to_string(), "b c".to_string()]
+                }
+            );
+        }
+
         #[test]
         fn router_multiple_dyn_params() {
             #[derive(Debug, PartialEq, Eq, Route)]
@@ -587,5 +847,236 @@ mod tests {
                 Routes::NotFound
             );
         }
+
+        #[test]
+        fn router_query_params() {
+            #[derive(Debug, PartialEq, Eq, Route)]
+            enum Routes {
+                #[to("/search?<q>&<page>")]
+                Search { q: String, page: Option<u32> },
+                #[to("/tag/<tag>?<sort>")]
+                Tag(String, String),
+                #[not_found]
+                NotFound,
+            }
+
+            assert_eq!(
+                Routes::default().match_path("/search?q=sycamore+router&page=2"),
+                Routes::Search {
+                    q: "sycamore router".to_string(),
+                    page: Some(2)
+                }
+            );
+            assert_eq!(
+                Routes::default().match_path("/search?page=2&q=rust#results"),
+                Routes::Search {
+                    q: "rust".to_string(),
+                    page: Some(2)
+                }
+            );
+            assert_eq!(
+                Routes::default().match_path("/search?q=rust"),
+                Routes::Search {
+                    q: "rust".to_string(),
+                    page: None
+                }
+            );
+            assert_eq!(
+                Routes::default().match_path("/search?q=rust&page=abc"),
+                Routes::NotFound
+            );
+            assert_eq!(Routes::default().match_path("/search"), Routes::NotFound);
+            assert_eq!(
+                Routes::match_route(&Routes::default(), &["tag", "rust?sort=new"]),
+                Routes::Tag("rust".to_string(), "new".to_string())
+            );
+        }
+
+        #[test]
+        fn to_path() {
+            #[derive(Debug, PartialEq, Eq, Route)]
+            enum Nested {
+                #[to("/")]
+                Index,
+                #[to("/page/<id>")]
+                Page(u32),
+                #[not_found]
+                NotFound,
+            }
+
+            #[derive(Debug, PartialEq, Eq, Route)]
+            enum Routes {
+                #[to("/")]
+                Home,
+                #[to("/hello/<name>")]
+                Hello { name: String },
+                #[to("/files/<path..>/raw")]
+                Files { path: Vec<String> },
+                #[to("/search?<q>&<page>")]
+                Search { q: String, page: Option<u32> },
+                #[sub_route("/nested")]
+                Nested(Nested),
+                #[to("/404")]
+                #[not_found]
+                NotFound,
+            }
+
+            let routes = [
+                (Routes::Home, "/"),
+                (
+                    Routes::Hello {
+                        name: "hello world/ü".to_string(),
+                    },
+                    "/hello/hello%20world%2F%C3%BC",
+                ),
+                (
+                    Routes::Files {
+                        path: vec!["a".to_string(), "b c".to_string()],
+                    },
+                    "/files/a/b%20c/raw",
+                ),
+                (
+                    Routes::Search {
+                        q: "a&b".to_string(),
+                        page: Some(2),
+                    },
+                    "/search?q=a%26b&page=2",
+                ),
+                (
+                    Routes::Search {
+                        q: "rust".to_string(),
+                        page: None,
+                    },
+                    "/search?q=rust",
+                ),
+                (Routes::Nested(Nested::Index), "/nested"),
+                (Routes::Nested(Nested::Page(1)), "/nested/page/1"),
+                (Routes::NotFound, "/404"),
+            ];
+            for (route, path) in routes {
+                assert_eq!(route.to_path().as_deref(), Some(path));
+                assert_eq!(Routes::default().match_path(path), route);
+            }
+        }
+
+        #[test]
+        fn is_not_found() {
+            #[derive(Debug, PartialEq, Eq, Route)]
+            enum Nested {
+                #[to("/")]
+                Index,
+                #[not_found]
+                NotFound,
+            }
+
+            #[derive(Debug, PartialEq, Eq, Route)]
+            enum Routes {
+                #[to("/")]
+                Home,
+                #[sub_route("/nested")]
+                Nested(Nested),
+                #[sub_route("/named/<id>")]
+                Named { id: u32, nested: Nested },
+                #[not_found]
+                NotFound,
+            }
+
+            assert!(!Routes::Home.is_not_found());
+            assert!(Routes::NotFound.is_not_found());
+            assert!(!Routes::Nested(Nested::Index).is_not_found());
+            assert!(Routes::Nested(Nested::NotFound).is_not_found());
+            assert!(Routes::default()
+                .match_path("/named/1/missing")
+                .is_not_found());
+            assert!(!Routes::Named {
+                id: 1,
+                nested: Nested::Index
+            }
+            .is_not_found());
+        }
+
+        #[test]
+        fn to_path_not_found() {
+            #[derive(Debug, PartialEq, Eq, Route)]
+            enum Nested {
+                #[to("/")]
+                Index,
+                #[not_found]
+                NotFound,
+            }
+
+            #[derive(Debug, PartialEq, Eq, Route)]
+            enum Routes {
+                #[to("/")]
+                Home,
+                #[sub_route("/nested")]
+                Nested(Nested),
+                #[not_found]
+                NotFound,
+            }
+
+            assert_eq!(Routes::NotFound.to_path(), None);
+            assert_eq!(Routes::Nested(Nested::NotFound).to_path(), None);
+            assert_eq!(
+                Routes::Nested(Nested::Index).to_path().as_deref(),
+                Some("/nested")
+            );
+        }
+
+        #[test]
+        fn sub_route() {
+            #[derive(Debug, PartialEq, Eq, Route)]
+            enum Admin {
+                #[to("/")]
+                Dashboard,
+                #[to("/users/<id>")]
+                User { id: u32 },
+                #[not_found]
+                NotFound,
+            }
+
+            #[derive(Debug, PartialEq, Eq, Route)]
+            enum Routes {
+                #[to("/")]
+                Home,
+                #[sub_route("/admin")]
+                Admin(Admin),
+                #[sub_route("/org/<org>")]
+                Org { org: String, route: Admin },
+                #[not_found]
+                NotFound,
+            }
+
+            assert_eq!(Routes::match_route(&Routes::default(), &[]), Routes::Home);
+            assert_eq!(
+                Routes::match_route(&Routes::default(), &["admin"]),
+                Routes::Admin(Admin::Dashboard)
+            );
+            assert_eq!(
+                Routes::match_route(&Routes::default(), &["admin", "users", "1"]),
+                Routes::Admin(Admin::User { id: 1 })
+            );
+            assert_eq!(
+                Routes::match_route(&Routes::default(), &["admin", "404"]),
+                Routes::Admin(Admin::NotFound)
+            );
+            assert_eq!(
+                Routes::match_route(&Routes::default(), &["org", "sycamore", "users", "2"]),
+                Routes::Org {
+                    org: "sycamore".to_string(),
+                    route: Admin::User { id: 2 }
+                }
+            );
+            assert_eq!(
+                Routes::match_route(&Routes::default(), &["org"]),
+                Routes::NotFound
+            );
+
+            // The derive generates a selector for the nested route of every sub route.
+            let org = Routes::match_route(&Routes::default(), &["org", "sycamore"]);
+            assert_eq!(org.select_org(), Some(&Admin::Dashboard));
+            assert_eq!(org.select_admin(), None);
+            assert_eq!(Routes::Home.select_org(), None);
+        }
     }
 }
@@ -0,0 +1,336 @@
+use std::fmt;
+
+use sycamore::prelude::*;
+use sycamore::web::{use_ssr_response, StringAttribute};
+use wasm_bindgen::prelude::*;
+use web_sys::HtmlAnchorElement;
+
+use crate::router::{is_browser_click, split_url, try_use_router_state};
+use crate::{navigate_replace, navigate_with_options, prefetch, NavigateOptions, Route};
+
+/// The destination of a [`Link`]. This can be created from an url or from a [`Route`].
+///
+/// Urls are relative to the base path of the app, just like with [`navigate`](crate::navigate).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinkTo(String);
+
+impl From<&str> for LinkTo {
+    fn from(url: &str) -> Self {
+        Self(url.to_string())
+    }
+}
+
+impl From<String> for LinkTo {
+    fn from(url: String) -> Self {
+        Self(url)
+    }
+}
+
+/// # Panics
+/// This will `panic!()` if the route does not have a path (see [`Route::to_path`]).
+impl<R: Route> From<R> for LinkTo {
+    fn from(route: R) -> Self {
+        Self(
+            route
+                .to_path()
+                .expect("a Link can only point to a route that has a path"),
+        )
+    }
+}
+
+/// Props for [`Link`].
+#[derive(Props)]
+pub struct LinkProps {
+    /// The url or route to link to.
+    #[prop(setter(into))]
+    to: LinkTo,
+    /// The class of the link. The `active_class` is appended to this class.
+    #[prop(default = StringAttribute::Static(None), setter(into))]
+    class: StringAttribute,
+    /// A class that is added to the link when it points to the current pathname.
+    #[prop(default, setter(strip_option, into))]
+    active_class: Option<String>,
+    /// Only consider the link active if the pathname matches exactly. By default, the link is also
+    /// active for all pathnames nested below it.
+    #[prop(default)]
+    exact: bool,
+    /// Replace the current history entry instead of adding a new one.
+    #[prop(default)]
+    replace: bool,
+    /// State to attach to the new history entry. See [`NavigateOptions::state`].
+    #[prop(default, setter(strip_option, into))]
+    state: Option<String>,
+    /// Run the loader of the route when the link is hovered or focused. See
+    /// [`prefetch`](crate::prefetch()).
+    #[prop(default)]
+    prefetch: bool,
+    #[prop(attributes(html, a))]
+    attributes: Attributes,
+    children: Children,
+}
+
+impl fmt::Debug for LinkProps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LinkProps")
+            .field("to", &self.to)
+            .field("active_class", &self.active_class)
+            .field("exact", &self.exact)
+            .field("replace", &self.replace)
+            .field("state", &self.state)
+            .field("prefetch", &self.prefetch)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A link to a route of the app.
+///
+/// Unlike a plain `<a>` element, a `Link` can be styled when it points to the current pathname,
+/// can pass [`NavigateOptions`] to the router and can prefetch the data of the route. All other
+/// attributes are passed on to the `<a>` element. Clicks on links with a `target` other than
+/// `_self` or with a `download` attribute are left to the browser.
+///
+/// Outside of a [`Router`](crate::Router) (e.g. inside a [`StaticRouter`](crate::StaticRouter)),
+/// the link is rendered with the url as its `href`, is never active and clicks are left to the
+/// browser.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore_router::*;
+/// #[derive(Route, Clone)]
+/// enum Routes {
+///     #[to("/")]
+///     Home,
+///     #[to("/posts/<id>")]
+///     Post { id: u32 },
+///     #[not_found]
+///     NotFound,
+/// }
+///
+/// # fn nav() -> View {
+/// view! {
+///     nav {
+///         Link(to=Routes::Home, active_class="active", exact=true) { "Home" }
+///         Link(to=Routes::Post { id: 1 }, active_class="active", prefetch=true) { "First post" }
+///         Link(to="/about", target="_blank") { "About" }
+///     }
+/// }
+/// # }
+/// ```
+#[component]
+pub fn Link(props: LinkProps) -> View {
+    let LinkProps {
+        to: LinkTo(url),
+        class,
+        active_class,
+        exact,
+        replace,
+        state,
+        prefetch: should_prefetch,
+        attributes,
+        children,
+    } = props;
+
+    let router = try_use_router_state();
+    let has_router = router.is_some();
+    let href = router
+        .as_ref()
+        .map_or_else(|| url.clone(), |router| router.integration.href(&url));
+    let is_active = match router {
+        Some(router) => {
+            let pathname = router.location.pathname;
+            let url = url.clone();
+            create_selector(move || pathname.with(|pathname| is_active(pathname, &url, exact)))
+        }
+        None => *create_signal(false),
+    };
+
+    let class = move || {
+        let class = class.get_clone();
+        match (class, &active_class) {
+            (class, Some(active)) if is_active.get() => Some(match class {
+                Some(class) if !class.is_empty() => format!("{class} {active}"),
+                _ => active.clone(),
+            }),
+            (class, _) => class.map(Into::into),
+        }
+    };
+    let aria_current = move || is_active.get().then_some("page");
+
+    let on_click = {
+        let url = url.clone();
+        move |ev: web_sys::MouseEvent| {
+            let a = ev.current_target().unwrap_throw();
+            // Without a router, the link is followed by the browser.
+            if !has_router
+                || ev.default_prevented()
+                || is_browser_click(&ev, a.unchecked_ref::<HtmlAnchorElement>())
+            {
+                return;
+            }
+            ev.prevent_default();
+            navigate_with_options(
+                &url,
+                NavigateOptions {
+                    replace,
+                    state: state.clone(),
+                },
+            );
+        }
+    };
+    let on_prefetch = move || {
+        if should_prefetch {
+            prefetch(&url);
+        }
+    };
+
+    view! {
+        a(
+            href=href,
+            class=class,
+            aria-current=aria_current,
+            on:click=on_click,
+            on:mouseenter={
+                let on_prefetch = on_prefetch.clone();
+                move |_: web_sys::MouseEvent| on_prefetch()
+            },
+            on:focus=move |_: web_sys::FocusEvent| on_prefetch(),
+            ..attributes
+        ) {
+            (children)
+        }
+    }
+}
+
+/// Props for [`Redirect`].
+#[derive(Props, Debug)]
+pub struct RedirectProps {
+    /// The url or route to redirect to.
+    #[prop(setter(into))]
+    to: LinkTo,
+    /// Use the `301 Moved Permanently` status code instead of `302 Found` when rendering on the
+    /// server.
+    #[prop(default)]
+    permanent: bool,
+}
+
+/// Redirects to another route when rendered.
+///
+/// When rendering on the server, the redirect is written to the
+/// [`SsrResponse`](sycamore::web::SsrResponse) so that the server can respond with a redirect. On
+/// the client, the current history entry is replaced with the new url once the component is
+/// mounted. This renders nothing.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::render_to_string_with_response;
+/// # use sycamore_router::*;
+/// #[derive(Route, Clone)]
+/// enum Routes {
+///     #[to("/")]
+///     Home,
+///     #[to("/old-home")]
+///     OldHome,
+///     #[not_found]
+///     NotFound,
+/// }
+///
+/// let (_, res) = render_to_string_with_response(|| view! {
+///     StaticRouter(route=Routes::OldHome, view=|route: ReadSignal<Routes>| match route.get_clone() {
+///         Routes::Home => view! { "Home" },
+///         Routes::OldHome => view! { Redirect(to=Routes::Home, permanent=true) },
+///         Routes::NotFound => view! { "Not found" },
+///     })
+/// });
+/// assert_eq!(res.status, Some(301));
+/// assert_eq!(res.redirect.as_deref(), Some("/"));
+/// ```
+#[component]
+pub fn Redirect(props: RedirectProps) -> View {
+    let RedirectProps {
+        to: LinkTo(url),
+        permanent,
+    } = props;
+
+    let router = try_use_router_state();
+    if is_not_ssr!() {
+        if router.is_some() {
+            on_mount(move || navigate_replace(&url));
+        }
+    } else if let Some(response) = use_ssr_response() {
+        if permanent {
+            response.set_status(301);
+        }
+        let href = router.map_or_else(|| url.clone(), |router| router.integration.href(&url));
+        response.redirect(href);
+    }
+    View::default()
+}
+
+/// Returns whether a link to `url` is active when the current pathname is `pathname`.
+fn is_active(pathname: &str, url: &str, exact: bool) -> bool {
+    let segments = |path: &str| {
+        path.split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(crate::decode_path_segment)
+            .collect::<Vec<_>>()
+    };
+    let current = segments(pathname);
+    let link = segments(split_url(url).0);
+    if exact {
+        current == link
+    } else {
+        current.starts_with(&link)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemoryIntegration, Router};
+
+    #[test]
+    fn active_links() {
+        assert!(is_active("/", "/", true));
+        assert!(is_active("/posts/1", "/posts", false));
+        assert!(!is_active("/posts/1", "/posts", true));
+        assert!(is_active("/posts/", "/posts?page=2#top", true));
+        assert!(!is_active("/postsx", "/posts", false));
+        assert!(is_active("/hello%20world", "/hello world", true));
+    }
+
+    #[test]
+    fn link_active_class() {
+        #[derive(Route, Clone)]
+        enum Routes {
+            #[to("/")]
+            Home,
+            #[to("/posts/<id>")]
+            Post { id: u32 },
+            #[not_found]
+            NotFound,
+        }
+
+        let html = sycamore::render_to_string(|| {
+            view! {
+                Router(
+                    integration=MemoryIntegration::new("/posts/1"),
+                    view=|_: ReadSignal<Routes>| view! {
+                        Link(to=Routes::Home, class="nav", active_class="active", exact=true) {
+                            "Home"
+                        }
+                        Link(to="/posts", class="nav", active_class="active") { "Posts" }
+                        Link(to=Routes::Post { id: 1 }, download="post.html") { "Download" }
+                    },
+                )
+            }
+        });
+        assert_eq!(
+            html,
+            "<a href=\"/\" class=\"nav\" data-hk=\"0.0\">Home</a>\
+             <a href=\"/posts\" class=\"nav active\" aria-current=\"page\" data-hk=\"0.1\">Posts</a>\
+             <a href=\"/posts/1\" aria-current=\"page\" download=\"post.html\" data-hk=\"0.2\">Download</a>"
+        );
+    }
+}
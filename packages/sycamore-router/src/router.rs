@@ -1,29 +1,349 @@
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::future::Future;
 use std::marker::PhantomData;
+use std::pin::Pin;
 use std::rc::Rc;
+#[cfg(feature = "suspense")]
+use std::task::{Context, Poll};
 
+#[cfg(feature = "suspense")]
+use futures::future::{FutureExt, Shared};
+#[cfg(feature = "suspense")]
+use sycamore::futures::{create_suspense_task, spawn_local_scoped, SuspenseTaskGuard};
 use sycamore::prelude::*;
+use sycamore::web::utils::ThreadBound;
+use sycamore::web::{console_error, use_ssr_response};
 use wasm_bindgen::prelude::*;
-use web_sys::{Element, HtmlAnchorElement, HtmlBaseElement, KeyboardEvent};
+use web_sys::{js_sys, Element, HtmlAnchorElement, HtmlBaseElement, KeyboardEvent};
 
-use crate::Route;
+use crate::{QueryParams, Route, TryFromParam};
 
 /// A router integration provides the methods for adapting a router to a certain environment (e.g.
 /// history API).
+///
+/// All urls are relative to the base path of the app.
 pub trait Integration {
-    /// Get the current pathname.
+    /// Get the current pathname, relative to the base path of the app.
+    ///
+    /// If the page is served under a `<base href>`, implementations must strip the base pathname,
+    /// e.g. `/app/about` with `<base href="/app/">` is returned as `/about`.
     fn current_pathname(&self) -> String;
 
-    /// Add a callback for listening to the `popstate` event.
+    /// Get the current query string, without the leading `?`.
+    ///
+    /// By default, this returns an empty string.
+    fn current_query(&self) -> String {
+        String::new()
+    }
+
+    /// Get the current hash, without the leading `#`.
+    ///
+    /// By default, this returns an empty string.
+    fn current_hash(&self) -> String {
+        String::new()
+    }
+
+    /// Add a callback for listening to the `popstate` event, i.e. when the location is changed
+    /// outside of the router (e.g. by using the back button).
     fn on_popstate(&self, f: Box<dyn FnMut()>);
 
     /// Get the click handler that is run when links are clicked.
-
     fn click_handler(&self) -> Box<dyn Fn(web_sys::MouseEvent)>;
+
+    /// Get the `href` of a link to the given `url`.
+    ///
+    /// By default, this returns the `url` unchanged.
+    fn href(&self, url: &str) -> String {
+        url.to_string()
+    }
+
+    /// Add a new entry to the history with the given `url`.
+    ///
+    /// By default, this uses the
+    /// [HTML5 History API](https://developer.mozilla.org/en-US/docs/Web/API/History_API).
+    fn push(&self, url: &str) {
+        let history = window().history().unwrap_throw();
+        history
+            .push_state_with_url(&JsValue::UNDEFINED, "", Some(url))
+            .unwrap_throw();
+    }
+
+    /// Replace the current entry in the history with the given `url`.
+    ///
+    /// By default, this uses the
+    /// [HTML5 History API](https://developer.mozilla.org/en-US/docs/Web/API/History_API).
+    fn replace(&self, url: &str) {
+        let history = window().history().unwrap_throw();
+        history
+            .replace_state_with_url(&JsValue::UNDEFINED, "", Some(url))
+            .unwrap_throw();
+    }
+
+    /// Get the current scroll position of the page.
+    ///
+    /// By default, this returns the scroll position of the window.
+    fn scroll_position(&self) -> ScrollPosition {
+        let window = window();
+        ScrollPosition {
+            x: window.scroll_x().unwrap_throw(),
+            y: window.scroll_y().unwrap_throw(),
+        }
+    }
+
+    /// Scroll the page to the given `position`.
+    ///
+    /// By default, this scrolls the window.
+    fn scroll_to(&self, position: ScrollPosition) {
+        window().scroll_to_with_x_and_y(position.x, position.y);
+    }
+
+    /// Scroll the element that is referenced by the `hash` (without the leading `#`) into view.
+    /// Returns `false` if there is no such element.
+    ///
+    /// By default, this looks for an element with the (percent-decoded) `hash` as its id.
+    fn scroll_to_hash(&self, hash: &str) -> bool {
+        match document().get_element_by_id(&crate::decode_path_segment(hash)) {
+            Some(element) => {
+                element.scroll_into_view();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get the scroll position that was saved in the current history entry, if any.
+    ///
+    /// By default, this reads the position from the state object of the current history entry.
+    fn saved_scroll_position(&self) -> Option<ScrollPosition> {
+        let state = window().history().unwrap_throw().state().unwrap_throw();
+        let get = |key: &str| js_sys::Reflect::get(&state, &key.into()).ok()?.as_f64();
+        Some(ScrollPosition {
+            x: get("scrollX")?,
+            y: get("scrollY")?,
+        })
+    }
+
+    /// Save the scroll `position` in the current history entry.
+    ///
+    /// By default, this stores the position in the state object of the current history entry.
+    fn save_scroll_position(&self, position: ScrollPosition) {
+        update_history_state(&[
+            ("scrollX", position.x.into()),
+            ("scrollY", position.y.into()),
+        ]);
+    }
+
+    /// Get the state that was attached to the current history entry, if any. See
+    /// [`NavigateOptions::state`].
+    ///
+    /// By default, this reads the state from the state object of the current history entry.
+    fn current_state(&self) -> Option<String> {
+        let state = window().history().unwrap_throw().state().unwrap_throw();
+        js_sys::Reflect::get(&state, &"state".into())
+            .ok()?
+            .as_string()
+    }
+
+    /// Attach the `state` to the current history entry.
+    ///
+    /// By default, this stores the state in the state object of the current history entry.
+    fn set_state(&self, state: &str) {
+        update_history_state(&[("state", state.into())]);
+    }
+
+    /// Get the position of the current entry in the history, if it is known. The router uses the
+    /// position to undo going back or forward in the history when a navigation guard cancels the
+    /// navigation.
+    ///
+    /// By default, this reads the position from the state object of the current history entry.
+    fn history_position(&self) -> Option<usize> {
+        let state = window().history().unwrap_throw().state().unwrap_throw();
+        let position = js_sys::Reflect::get(&state, &"position".into())
+            .ok()?
+            .as_f64()?;
+        Some(position as usize)
+    }
+
+    /// Save the `position` of the current entry in the history. See
+    /// [`Integration::history_position`].
+    ///
+    /// By default, this stores the position in the state object of the current history entry.
+    fn save_history_position(&self, position: usize) {
+        update_history_state(&[("position", (position as f64).into())]);
+    }
+
+    /// Move `delta` entries forward (or backward if negative) in the history.
+    ///
+    /// By default, this uses the
+    /// [HTML5 History API](https://developer.mozilla.org/en-US/docs/Web/API/History_API).
+    fn go(&self, delta: isize) {
+        window()
+            .history()
+            .unwrap_throw()
+            .go_with_delta(delta as i32)
+            .unwrap_throw();
+    }
+}
+
+/// Sets the given keys of the state object of the current history entry, keeping the other keys.
+fn update_history_state(values: &[(&str, JsValue)]) {
+    let history = window().history().unwrap_throw();
+    let state = js_sys::Object::new();
+    let old = history.state().unwrap_throw();
+    if old.is_object() {
+        js_sys::Object::assign(&state, old.unchecked_ref());
+    }
+    for (key, value) in values {
+        let _ = js_sys::Reflect::set(&state, &(*key).into(), value);
+    }
+    history.replace_state(&state, "").unwrap_throw();
+}
+
+/// The scroll position of a page, in pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScrollPosition {
+    /// The horizontal scroll position.
+    pub x: f64,
+    /// The vertical scroll position.
+    pub y: f64,
+}
+
+/// The reactive state of the current location.
+#[derive(Clone, Copy)]
+pub(crate) struct Location {
+    pub(crate) pathname: Signal<String>,
+    query: Signal<String>,
+    hash: Signal<String>,
+    state: Signal<Option<String>>,
+}
+
+impl Location {
+    fn new(integration: &dyn Integration) -> Self {
+        Self {
+            pathname: create_signal(integration.current_pathname()),
+            query: create_signal(integration.current_query()),
+            hash: create_signal(integration.current_hash()),
+            state: create_signal(integration.current_state()),
+        }
+    }
+
+    /// Updates the location from the integration. Signals are only updated if their value changed.
+    fn sync(self, integration: &dyn Integration) {
+        if !self.pathname.is_alive() {
+            // The router has already been destroyed.
+            return;
+        }
+        let pathname = integration.current_pathname();
+        let query = integration.current_query();
+        let hash = integration.current_hash();
+        let state = integration.current_state();
+        batch(|| {
+            if self.pathname.with(|p| p != &pathname) {
+                self.pathname.set(pathname);
+            }
+            if self.query.with(|q| q != &query) {
+                self.query.set(query);
+            }
+            if self.hash.with(|h| h != &hash) {
+                self.hash.set(hash);
+            }
+            if self.state.with(|s| s != &state) {
+                self.state.set(state);
+            }
+        });
+    }
+
+    /// Returns the url of the location, without tracking.
+    fn url(self) -> String {
+        join_url(
+            &self.pathname.get_clone_untracked(),
+            &self.query.get_clone_untracked(),
+            &self.hash.get_clone_untracked(),
+        )
+    }
+}
+
+/// How a navigation is recorded in the history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum HistoryMode {
+    /// Add a new history entry.
+    Push,
+    /// Replace the current history entry.
+    Replace,
+    /// The history entry was already changed outside of the router (e.g. by using the back
+    /// button).
+    Traverse,
+}
+
+/// The state of the currently active router.
+#[derive(Clone)]
+pub(crate) struct RouterState {
+    pub(crate) location: Location,
+    /// Runs the navigation guards and loader of the router before committing a navigation to the
+    /// `url`.
+    navigator: Rc<NavigatorFn>,
+    /// Runs the loader of the route of the `url` ahead of a navigation.
+    prefetcher: Rc<dyn Fn(&str)>,
+    pub(crate) integration: Rc<dyn Integration>,
 }
 
+type NavigatorFn = dyn Fn(&str, HistoryMode, Option<String>);
+
+/// The maximum number of consecutive redirects by navigation guards before the router logs an
+/// error and cancels the navigation.
+const MAX_REDIRECTS: u32 = 16;
+
 thread_local! {
-    static PATHNAME: Cell<Option<Signal<String>>> = const { Cell::new(None) };
+    static ROUTER_STATE: RefCell<Option<RouterState>> = const { RefCell::new(None) };
+}
+
+/// Get the state of the currently active router.
+///
+/// # Panics
+/// This function will `panic!()` if a [`Router`] has not yet been created. `name` is the name of
+/// the function that is used in the panic message.
+fn use_router_state(name: &str) -> RouterState {
+    try_use_router_state().unwrap_or_else(|| panic!("{name} can only be used with a Router"))
+}
+
+/// Get the state of the currently active router, if any.
+pub(crate) fn try_use_router_state() -> Option<RouterState> {
+    ROUTER_STATE.with(|state| state.borrow().clone())
+}
+
+/// Returns the `<a>` element that was clicked if the click should be handled by the router.
+///
+/// Clicks that were already handled, or that should be handled by the browser (see
+/// [`is_browser_click`]), are ignored.
+fn clicked_anchor(ev: &web_sys::MouseEvent) -> Option<HtmlAnchorElement> {
+    if ev.default_prevented() {
+        return None;
+    }
+    let a = ev
+        .target()?
+        .unchecked_into::<Element>()
+        .closest("a[href]")
+        .unwrap_throw()?
+        .unchecked_into::<HtmlAnchorElement>();
+
+    if is_browser_click(ev, &a) || window().location().origin() != Ok(a.origin()) {
+        // Use default browser behaviour.
+        return None;
+    }
+    Some(a)
+}
+
+/// Returns whether a click on the anchor `a` should be left to the browser. This is the case for
+/// links with `rel="external"`, a `download` attribute or a `target` other than `_self`, and for
+/// clicks with a button other than the main button or with meta keys pressed.
+pub(crate) fn is_browser_click(ev: &web_sys::MouseEvent, a: &HtmlAnchorElement) -> bool {
+    let target = a.target();
+    a.rel() == "external"
+        || a.has_attribute("download")
+        || !(target.is_empty() || target == "_self")
+        || ev.button() != 0
+        || meta_keys_pressed(ev.unchecked_ref::<KeyboardEvent>())
 }
 
 /// A router integration that uses the
@@ -45,7 +365,18 @@ impl HistoryIntegration {
 
 impl Integration for HistoryIntegration {
     fn current_pathname(&self) -> String {
-        window().location().pathname().unwrap_throw()
+        let pathname = window().location().pathname().unwrap_throw();
+        strip_base_pathname(&pathname).to_string()
+    }
+
+    fn current_query(&self) -> String {
+        let search = window().location().search().unwrap_throw();
+        search.strip_prefix('?').unwrap_or(&search).to_string()
+    }
+
+    fn current_hash(&self) -> String {
+        let hash = window().location().hash().unwrap_throw();
+        hash.strip_prefix('#').unwrap_or(&hash).to_string()
     }
 
     fn on_popstate(&self, f: Box<dyn FnMut()>) {
@@ -58,56 +389,392 @@ impl Integration for HistoryIntegration {
 
     fn click_handler(&self) -> Box<dyn Fn(web_sys::MouseEvent)> {
         Box::new(|ev| {
-            if let Some(a) = ev
-                .target()
-                .unwrap_throw()
-                .unchecked_into::<Element>()
-                .closest("a[href]")
-                .unwrap_throw()
-            {
+            if let Some(a) = clicked_anchor(&ev) {
                 let location = window().location();
 
-                let a = a.unchecked_into::<HtmlAnchorElement>();
+                let a_pathname = a.pathname();
+                let a_search = a.search();
+                let hash = a.hash();
 
-                // Check if a has `rel="external"`.
-                if a.rel() == "external" {
-                    // Use default browser behaviour.
-                    return;
+                if location.pathname().as_ref() != Ok(&a_pathname)
+                    || location.search().as_ref() != Ok(&a_search)
+                {
+                    // Same origin, different path or query. Navigate to new page.
+                    ev.prevent_default();
+                    let pathname = strip_base_pathname(&a_pathname);
+                    navigate(&format!("{pathname}{a_search}{hash}"));
+                } else if location.hash().as_ref() != Ok(&hash) {
+                    // Same origin, same pathname, different hash. Use default browser behavior.
+                } else {
+                    // Same page. Do nothing.
+                    ev.prevent_default();
                 }
+            }
+        })
+    }
 
-                let origin = a.origin();
-                let a_pathname = a.pathname();
-                let hash = a.hash();
+    fn href(&self, url: &str) -> String {
+        with_base_pathname(url)
+    }
 
-                let meta_keys_pressed = meta_keys_pressed(ev.unchecked_ref::<KeyboardEvent>());
-                if !meta_keys_pressed && location.origin() == Ok(origin) {
-                    if location.pathname().as_ref() != Ok(&a_pathname) {
-                        // Same origin, different path. Navigate to new page.
-                        ev.prevent_default();
-                        PATHNAME.with(|pathname| {
-                            let pathname = pathname.get().unwrap_throw();
-                            let path = a_pathname
-                                .strip_prefix(&base_pathname())
-                                .unwrap_or(&a_pathname);
-                            pathname.set(path.to_string());
-
-                            // Update History API.
-                            let history = window().history().unwrap_throw();
-                            history
-                                .push_state_with_url(&JsValue::UNDEFINED, "", Some(&a_pathname))
-                                .unwrap_throw();
-                            window().scroll_to_with_x_and_y(0.0, 0.0);
-                        });
-                    } else if location.hash().as_ref() != Ok(&hash) {
-                        // Same origin, same pathname, different hash. Use default browser behavior.
-                    } else {
-                        // Same page. Do nothing.
-                        ev.prevent_default();
-                    }
+    fn push(&self, url: &str) {
+        let history = window().history().unwrap_throw();
+        history
+            .push_state_with_url(&JsValue::UNDEFINED, "", Some(&with_base_pathname(url)))
+            .unwrap_throw();
+    }
+
+    fn replace(&self, url: &str) {
+        let history = window().history().unwrap_throw();
+        history
+            .replace_state_with_url(&JsValue::UNDEFINED, "", Some(&with_base_pathname(url)))
+            .unwrap_throw();
+    }
+}
+
+/// A router integration that stores the route in the hash of the URL (e.g. `/#/about`).
+///
+/// This is useful for static hosts that cannot rewrite all urls to the same page. Links can either
+/// point to the hash directly (e.g. `href="#/about"`) or use an absolute path (e.g.
+/// `href="/about"`). Other fragments, such as `href="#section"`, are left to the browser.
+#[derive(Default, Debug)]
+pub struct HashIntegration {
+    /// This field is to prevent downstream users from creating a new `HashIntegration` without
+    /// the `new` method.
+    _internal: (),
+}
+
+impl HashIntegration {
+    /// Create a new [`HashIntegration`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the url stored in the hash, without the leading `#`.
+    fn url(&self) -> String {
+        let hash = window().location().hash().unwrap_throw();
+        hash.strip_prefix('#').unwrap_or(&hash).to_string()
+    }
+}
+
+impl Integration for HashIntegration {
+    fn current_pathname(&self) -> String {
+        let url = self.url();
+        let (pathname, _, _) = split_url(&url);
+        if pathname.starts_with('/') {
+            pathname.to_string()
+        } else {
+            format!("/{pathname}")
+        }
+    }
+
+    fn current_query(&self) -> String {
+        split_url(&self.url()).1.to_string()
+    }
+
+    fn current_hash(&self) -> String {
+        split_url(&self.url()).2.to_string()
+    }
+
+    fn on_popstate(&self, f: Box<dyn FnMut()>) {
+        let closure = Closure::wrap(f);
+        window()
+            .add_event_listener_with_callback("hashchange", closure.as_ref().unchecked_ref())
+            .unwrap_throw();
+        closure.forget();
+    }
+
+    fn click_handler(&self) -> Box<dyn Fn(web_sys::MouseEvent)> {
+        Box::new(|ev| {
+            if let Some(a) = clicked_anchor(&ev) {
+                let href = a.get_attribute("href").unwrap_or_default();
+                // Use default browser behaviour for links that are not routes.
+                if let Some(url) = hash_link_url(&href) {
+                    ev.prevent_default();
+                    navigate(url);
+                }
+            }
+        })
+    }
+
+    fn href(&self, url: &str) -> String {
+        format!("#{url}")
+    }
+
+    fn push(&self, url: &str) {
+        let history = window().history().unwrap_throw();
+        history
+            .push_state_with_url(&JsValue::UNDEFINED, "", Some(&format!("#{url}")))
+            .unwrap_throw();
+    }
+
+    fn replace(&self, url: &str) {
+        let history = window().history().unwrap_throw();
+        history
+            .replace_state_with_url(&JsValue::UNDEFINED, "", Some(&format!("#{url}")))
+            .unwrap_throw();
+    }
+}
+
+/// Returns the url of the route that a link with `href` points to when using [`HashIntegration`],
+/// or `None` if the link is not a route. Fragments that do not start with `#/`, such as
+/// `#section`, are in-page anchors and are not routes.
+fn hash_link_url(href: &str) -> Option<&str> {
+    if let Some(url) = href.strip_prefix('#') {
+        url.starts_with('/').then_some(url)
+    } else if href.starts_with('/') && !href.starts_with("//") {
+        Some(href)
+    } else {
+        None
+    }
+}
+
+/// A router integration that keeps its own history stack in memory.
+///
+/// This integration does not depend on a browser environment, which makes it useful for testing
+/// routing logic natively. `MemoryIntegration` is cheap to clone and all clones share the same
+/// history. Keep a clone around to go back and forward in the history after passing it to the
+/// router.
+///
+/// The scroll position of the page is simulated and can be changed with
+/// [`Integration::scroll_to`].
+#[derive(Clone)]
+pub struct MemoryIntegration {
+    history: Rc<RefCell<MemoryHistory>>,
+}
+
+struct MemoryHistory {
+    entries: Vec<MemoryEntry>,
+    index: usize,
+    listeners: Vec<Box<dyn FnMut()>>,
+    /// The simulated scroll position of the page.
+    scroll: ScrollPosition,
+}
+
+struct MemoryEntry {
+    url: String,
+    scroll: Option<ScrollPosition>,
+    state: Option<String>,
+}
+
+impl MemoryEntry {
+    fn new(url: String) -> Self {
+        Self {
+            url,
+            scroll: None,
+            state: None,
+        }
+    }
+}
+
+impl MemoryIntegration {
+    /// Create a new [`MemoryIntegration`] starting at the given `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            history: Rc::new(RefCell::new(MemoryHistory {
+                entries: vec![MemoryEntry::new(url.into())],
+                index: 0,
+                listeners: Vec::new(),
+                scroll: ScrollPosition::default(),
+            })),
+        }
+    }
+
+    /// Get the url of the current history entry.
+    pub fn current_url(&self) -> String {
+        let history = self.history.borrow();
+        history.entries[history.index].url.clone()
+    }
+
+    /// Returns whether there is a previous entry in the history.
+    pub fn can_go_back(&self) -> bool {
+        self.history.borrow().index > 0
+    }
+
+    /// Returns whether there is a next entry in the history.
+    pub fn can_go_forward(&self) -> bool {
+        let history = self.history.borrow();
+        history.index + 1 < history.entries.len()
+    }
+
+    /// Go to the previous entry in the history, if any.
+    pub fn back(&self) {
+        self.go(-1);
+    }
+
+    /// Go to the next entry in the history, if any.
+    pub fn forward(&self) {
+        self.go(1);
+    }
+
+    /// Move `delta` entries forward (or backward if negative) in the history. The index is clamped
+    /// to the bounds of the history.
+    pub fn go(&self, delta: isize) {
+        let changed = {
+            let mut history = self.history.borrow_mut();
+            let index = history
+                .index
+                .saturating_add_signed(delta)
+                .min(history.entries.len() - 1);
+            let changed = index != history.index;
+            if changed {
+                // Like a browser, remember the scroll position of the entry that is left.
+                let scroll = history.scroll;
+                let current = history.index;
+                history.entries[current].scroll = Some(scroll);
+            }
+            history.index = index;
+            changed
+        };
+        if changed {
+            // Take the listeners out so that they can access the history.
+            let mut listeners = std::mem::take(&mut self.history.borrow_mut().listeners);
+            for listener in &mut listeners {
+                listener();
+            }
+            let mut history = self.history.borrow_mut();
+            listeners.append(&mut history.listeners);
+            history.listeners = listeners;
+        }
+    }
+}
+
+impl Default for MemoryIntegration {
+    fn default() -> Self {
+        Self::new("/")
+    }
+}
+
+impl fmt::Debug for MemoryIntegration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let history = self.history.borrow();
+        let entries: Vec<_> = history.entries.iter().map(|entry| &entry.url).collect();
+        f.debug_struct("MemoryIntegration")
+            .field("entries", &entries)
+            .field("index", &history.index)
+            .finish()
+    }
+}
+
+impl Integration for MemoryIntegration {
+    fn current_pathname(&self) -> String {
+        split_url(&self.current_url()).0.to_string()
+    }
+
+    fn current_query(&self) -> String {
+        split_url(&self.current_url()).1.to_string()
+    }
+
+    fn current_hash(&self) -> String {
+        split_url(&self.current_url()).2.to_string()
+    }
+
+    fn on_popstate(&self, f: Box<dyn FnMut()>) {
+        self.history.borrow_mut().listeners.push(f);
+    }
+
+    fn click_handler(&self) -> Box<dyn Fn(web_sys::MouseEvent)> {
+        Box::new(|ev| {
+            if let Some(a) = clicked_anchor(&ev) {
+                let href = a.get_attribute("href").unwrap_or_default();
+                if href.starts_with('/') && !href.starts_with("//") {
+                    ev.prevent_default();
+                    navigate(&href);
                 }
             }
         })
     }
+
+    fn push(&self, url: &str) {
+        let mut history = self.history.borrow_mut();
+        let index = history.index + 1;
+        history.entries.truncate(index);
+        history.entries.push(MemoryEntry::new(url.to_string()));
+        history.index = index;
+    }
+
+    fn replace(&self, url: &str) {
+        let mut history = self.history.borrow_mut();
+        let index = history.index;
+        history.entries[index] = MemoryEntry::new(url.to_string());
+    }
+
+    fn scroll_position(&self) -> ScrollPosition {
+        self.history.borrow().scroll
+    }
+
+    fn scroll_to(&self, position: ScrollPosition) {
+        self.history.borrow_mut().scroll = position;
+    }
+
+    fn scroll_to_hash(&self, _hash: &str) -> bool {
+        // There are no elements to scroll to.
+        false
+    }
+
+    fn saved_scroll_position(&self) -> Option<ScrollPosition> {
+        let history = self.history.borrow();
+        history.entries[history.index].scroll
+    }
+
+    fn save_scroll_position(&self, position: ScrollPosition) {
+        let mut history = self.history.borrow_mut();
+        let index = history.index;
+        history.entries[index].scroll = Some(position);
+    }
+
+    fn current_state(&self) -> Option<String> {
+        let history = self.history.borrow();
+        history.entries[history.index].state.clone()
+    }
+
+    fn set_state(&self, state: &str) {
+        let mut history = self.history.borrow_mut();
+        let index = history.index;
+        history.entries[index].state = Some(state.to_string());
+    }
+
+    fn history_position(&self) -> Option<usize> {
+        Some(self.history.borrow().index)
+    }
+
+    fn save_history_position(&self, _position: usize) {
+        // The position is always known.
+    }
+
+    fn go(&self, delta: isize) {
+        MemoryIntegration::go(self, delta);
+    }
+}
+
+/// Splits an url into its pathname, query and hash (without the leading `?` and `#`).
+pub(crate) fn split_url(url: &str) -> (&str, &str, &str) {
+    let (url, hash) = url.split_once('#').unwrap_or((url, ""));
+    let (pathname, query) = url.split_once('?').unwrap_or((url, ""));
+    (pathname, query, hash)
+}
+
+/// The inverse of [`split_url`]. Empty query strings and hashes are omitted.
+fn join_url(pathname: &str, query: &str, hash: &str) -> String {
+    let mut url = pathname.to_string();
+    if !query.is_empty() {
+        url.push('?');
+        url.push_str(query);
+    }
+    if !hash.is_empty() {
+        url.push('#');
+        url.push_str(hash);
+    }
+    url
+}
+
+/// Gets the current url of the `integration`.
+fn integration_url(integration: &dyn Integration) -> String {
+    join_url(
+        &integration.current_pathname(),
+        &integration.current_query(),
+        &integration.current_hash(),
+    )
 }
 
 /// Gets the base pathname from `document.baseURI`.
@@ -127,6 +794,278 @@ fn base_pathname() -> String {
     }
 }
 
+/// Returns `true` if `url` is inside the base pathname `base`, i.e. `url` starts with `base` at a
+/// segment boundary. `/app/about` is inside `/app` but `/application` is not.
+fn is_in_base_pathname(url: &str, base: &str) -> bool {
+    match url.strip_prefix(base) {
+        Some(rest) => rest.is_empty() || rest.starts_with(['/', '?', '#']),
+        None => false,
+    }
+}
+
+/// Prepends the base pathname to an absolute `url` if it is not already present.
+fn with_base_pathname(url: &str) -> String {
+    let base_pathname = base_pathname();
+    if url.starts_with('/') && !is_in_base_pathname(url, &base_pathname) {
+        format!("{base_pathname}{url}")
+    } else {
+        url.to_string()
+    }
+}
+
+/// Strips the base pathname from `pathname` if it is present.
+fn strip_base_pathname(pathname: &str) -> &str {
+    let base_pathname = base_pathname();
+    if is_in_base_pathname(pathname, &base_pathname) {
+        &pathname[base_pathname.len()..]
+    } else {
+        pathname
+    }
+}
+
+/// The outcome of a [`NavigationGuard`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NavigationAction {
+    /// Continue with the navigation.
+    Continue,
+    /// Cancel the navigation and stay on the current route.
+    Cancel,
+    /// Cancel the navigation and navigate to the given url instead.
+    ///
+    /// If the guards keep redirecting (e.g. because of a redirect loop), the navigation is
+    /// cancelled after 16 redirects in a row and an error is logged.
+    Redirect(String),
+}
+
+/// A hook that is run before a navigation is committed. It is called with the current route and
+/// the route that is being navigated to, in that order, and decides whether the navigation should
+/// continue.
+///
+/// Any closure with the signature `Fn(&R, &R) -> NavigationAction` can be converted into a
+/// `NavigationGuard`.
+pub struct NavigationGuard<R> {
+    f: Option<Rc<GuardFn<R>>>,
+}
+
+type GuardFn<R> = dyn Fn(&R, &R) -> NavigationAction;
+
+impl<R> NavigationGuard<R> {
+    /// Create a new [`NavigationGuard`] from a closure.
+    pub fn new(f: impl Fn(&R, &R) -> NavigationAction + 'static) -> Self {
+        Self {
+            f: Some(Rc::new(f)),
+        }
+    }
+
+    /// Runs the guard. A guard that was created with [`Default`] always continues.
+    fn check(&self, from: &R, to: &R) -> NavigationAction {
+        self.f
+            .as_ref()
+            .map_or(NavigationAction::Continue, |f| f(from, to))
+    }
+}
+
+impl<R, F: Fn(&R, &R) -> NavigationAction + 'static> From<F> for NavigationGuard<R> {
+    fn from(f: F) -> Self {
+        Self::new(f)
+    }
+}
+
+impl<R> Default for NavigationGuard<R> {
+    fn default() -> Self {
+        Self { f: None }
+    }
+}
+
+impl<R> Clone for NavigationGuard<R> {
+    fn clone(&self) -> Self {
+        Self { f: self.f.clone() }
+    }
+}
+
+impl<R> fmt::Debug for NavigationGuard<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NavigationGuard").finish_non_exhaustive()
+    }
+}
+
+/// Loads the data of a route before it is rendered.
+///
+/// The loader is called with the route that is being navigated to. The router only switches to the
+/// new route once the returned future has resolved. While the future is pending, it is registered
+/// as a suspense task, so an enclosing `Suspense` or `Transition` can display the loading state.
+/// The loader of the initial route is also run as a suspense task, but the initial route is
+/// rendered right away.
+///
+/// Routes that do not need any data can simply return a future that is immediately ready, in which
+/// case the navigation is committed synchronously.
+///
+/// Creating a loader requires the `suspense` feature.
+pub struct RouteLoader<R> {
+    f: Option<Rc<LoaderFn<R>>>,
+}
+
+type LoaderFuture = Pin<Box<dyn Future<Output = ()>>>;
+type LoaderFn<R> = dyn Fn(&R) -> LoaderFuture;
+
+impl<R> RouteLoader<R> {
+    /// Create a new [`RouteLoader`] from a closure returning a future.
+    ///
+    /// This requires the `suspense` feature.
+    #[cfg(feature = "suspense")]
+    pub fn new<F, Fut>(f: F) -> Self
+    where
+        F: Fn(&R) -> Fut + 'static,
+        Fut: Future<Output = ()> + 'static,
+    {
+        Self {
+            f: Some(Rc::new(move |route| Box::pin(f(route)))),
+        }
+    }
+
+    /// Starts loading the `route`. Returns `None` if the loader was already finished after being
+    /// polled once.
+    #[cfg(feature = "suspense")]
+    fn load(&self, route: &R) -> Option<LoaderFuture> {
+        poll_once(self.f.as_ref()?(route))
+    }
+}
+
+/// The url and the pending loader of a prefetched route.
+#[cfg(feature = "suspense")]
+type Prefetched = (String, Option<Shared<LoaderFuture>>);
+
+/// Polls the future once. Returns `None` if the future is finished.
+#[cfg(feature = "suspense")]
+fn poll_once(mut fut: LoaderFuture) -> Option<LoaderFuture> {
+    let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+    match fut.as_mut().poll(&mut cx) {
+        Poll::Ready(()) => None,
+        Poll::Pending => Some(fut),
+    }
+}
+
+impl<R> Default for RouteLoader<R> {
+    fn default() -> Self {
+        Self { f: None }
+    }
+}
+
+impl<R> Clone for RouteLoader<R> {
+    fn clone(&self) -> Self {
+        Self { f: self.f.clone() }
+    }
+}
+
+impl<R> fmt::Debug for RouteLoader<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RouteLoader").finish_non_exhaustive()
+    }
+}
+
+/// Configures how the router scrolls the page after a navigation.
+///
+/// By default, the router:
+/// - restores the scroll position that was saved in the history entry when going back or forward,
+/// - scrolls to the element referenced by the hash of the url, if any,
+/// - scrolls to the top of the page otherwise.
+///
+/// The scroll position of the current history entry is saved whenever the user stops scrolling
+/// and right before navigating away from it.
+pub struct ScrollBehavior<R> {
+    enabled: bool,
+    restore: bool,
+    hash: bool,
+    skip: Option<Rc<RouteFilterFn<R>>>,
+}
+
+type RouteFilterFn<R> = dyn Fn(&R) -> bool;
+
+impl<R> ScrollBehavior<R> {
+    /// Create a new [`ScrollBehavior`] with the default behavior.
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            restore: true,
+            hash: true,
+            skip: None,
+        }
+    }
+
+    /// A [`ScrollBehavior`] that never scrolls the page and does not save scroll positions.
+    pub fn none() -> Self {
+        Self {
+            enabled: false,
+            ..Self::new()
+        }
+    }
+
+    /// Sets whether the saved scroll position should be restored when going back or forward in
+    /// the history. Defaults to `true`.
+    pub fn restore(mut self, restore: bool) -> Self {
+        self.restore = restore;
+        self
+    }
+
+    /// Sets whether the router should scroll to the element referenced by the hash of the url.
+    /// Defaults to `true`.
+    pub fn scroll_to_hash(mut self, hash: bool) -> Self {
+        self.hash = hash;
+        self
+    }
+
+    /// Opts routes out of scrolling. The router does not scroll the page when navigating to a route
+    /// for which `f` returns `true`, e.g. for tabs that should keep the scroll position.
+    pub fn skip(mut self, f: impl Fn(&R) -> bool + 'static) -> Self {
+        self.skip = Some(Rc::new(f));
+        self
+    }
+
+    /// Scrolls the page after navigating to `route`.
+    fn scroll(&self, integration: &dyn Integration, route: &R, mode: HistoryMode) {
+        if !self.enabled || self.skip.as_ref().is_some_and(|skip| skip(route)) {
+            return;
+        }
+        if mode == HistoryMode::Traverse && self.restore {
+            if let Some(position) = integration.saved_scroll_position() {
+                integration.scroll_to(position);
+                return;
+            }
+        }
+        let hash = integration.current_hash();
+        if !(self.hash && !hash.is_empty() && integration.scroll_to_hash(&hash)) {
+            integration.scroll_to(ScrollPosition::default());
+        }
+    }
+}
+
+impl<R> Default for ScrollBehavior<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R> Clone for ScrollBehavior<R> {
+    fn clone(&self) -> Self {
+        Self {
+            enabled: self.enabled,
+            restore: self.restore,
+            hash: self.hash,
+            skip: self.skip.clone(),
+        }
+    }
+}
+
+impl<R> fmt::Debug for ScrollBehavior<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScrollBehavior")
+            .field("enabled", &self.enabled)
+            .field("restore", &self.restore)
+            .field("hash", &self.hash)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Props for [`Router`].
 #[derive(Props, Debug)]
 pub struct RouterProps<R, F, I>
@@ -137,6 +1076,18 @@ where
 {
     view: F,
     integration: I,
+    /// Called before leaving the current route. See [`NavigationGuard`].
+    #[prop(default, setter(into))]
+    before_leave: NavigationGuard<R>,
+    /// Called before entering the new route. See [`NavigationGuard`].
+    #[prop(default, setter(into))]
+    before_enter: NavigationGuard<R>,
+    /// Loads the data of a route before it is rendered. See [`RouteLoader`].
+    #[prop(default)]
+    loader: RouteLoader<R>,
+    /// How the page is scrolled after a navigation. See [`ScrollBehavior`].
+    #[prop(default)]
+    scroll: ScrollBehavior<R>,
     #[prop(default, setter(skip))]
     _phantom: PhantomData<R>,
 }
@@ -152,6 +1103,10 @@ where
         Self {
             view,
             integration,
+            before_leave: NavigationGuard::default(),
+            before_enter: NavigationGuard::default(),
+            loader: RouteLoader::default(),
+            scroll: ScrollBehavior::default(),
             _phantom: PhantomData,
         }
     }
@@ -168,6 +1123,18 @@ where
     view: F,
     integration: I,
     route: R,
+    /// Called before leaving the current route. See [`NavigationGuard`].
+    #[prop(default, setter(into))]
+    before_leave: NavigationGuard<R>,
+    /// Called before entering the new route. See [`NavigationGuard`].
+    #[prop(default, setter(into))]
+    before_enter: NavigationGuard<R>,
+    /// Loads the data of a route before it is rendered. See [`RouteLoader`].
+    #[prop(default)]
+    loader: RouteLoader<R>,
+    /// How the page is scrolled after a navigation. See [`ScrollBehavior`].
+    #[prop(default)]
+    scroll: ScrollBehavior<R>,
 }
 
 impl<R, F, I> RouterBaseProps<R, F, I>
@@ -182,6 +1149,10 @@ where
             view,
             integration,
             route,
+            before_leave: NavigationGuard::default(),
+            before_enter: NavigationGuard::default(),
+            loader: RouteLoader::default(),
+            scroll: ScrollBehavior::default(),
         }
     }
 }
@@ -191,7 +1162,7 @@ where
 #[component]
 pub fn Router<R, F, I>(props: RouterProps<R, F, I>) -> View
 where
-    R: Route + 'static,
+    R: Route + MaybeSend + 'static,
     F: FnOnce(ReadSignal<R>) -> View + 'static,
     I: Integration + 'static,
 {
@@ -201,6 +1172,10 @@ where
             integration=props.integration,
             // The derive macro makes this the `#[not_found]` route (always present)
             route=R::default(),
+            before_leave=props.before_leave,
+            before_enter=props.before_enter,
+            loader=props.loader,
+            scroll=props.scroll,
         )
     }
 }
@@ -212,7 +1187,7 @@ where
 #[component]
 pub fn RouterBase<R, F, I>(props: RouterBaseProps<R, F, I>) -> View
 where
-    R: Route + 'static,
+    R: Route + MaybeSend + 'static,
     F: FnOnce(ReadSignal<R>) -> View + 'static,
     I: Integration + 'static,
 {
@@ -220,49 +1195,443 @@ where
         view,
         integration,
         route,
+        before_leave,
+        before_enter,
+        loader,
+        scroll,
+    } = props;
+    let integration: Rc<dyn Integration> = Rc::new(integration);
+    let route = Rc::new(route);
+
+    let location = Location::new(&*integration);
+    let route_signal = create_memo({
+        // The router is only used on the thread that created it, since its state is stored in a
+        // thread local.
+        let route = ThreadBound::new(route.clone());
+        move || {
+            location.pathname.with(|pathname| {
+                location
+                    .query
+                    .with(|query| route.match_path(&format!("{pathname}?{query}")))
+            })
+        }
+    });
+
+    // The initial route is rendered right away, but is still registered as a suspense task.
+    // The route is matched again instead of borrowing `route_signal`, so that the loader is free
+    // to update signals.
+    #[cfg(feature = "suspense")]
+    if let Some(fut) = loader.load(&route.match_path(&location.url())) {
+        create_suspense_task(fut);
+    }
+    #[cfg(not(feature = "suspense"))]
+    let _ = loader;
+
+    // The position of the current entry in the history. Used for undoing a cancelled traversal.
+    let initial_position = integration.history_position().unwrap_or_else(|| {
+        integration.save_history_position(0);
+        0
+    });
+    let position = Rc::new(Cell::new(initial_position));
+    // Set while a cancelled traversal is being undone, so that the guards are not run again.
+    let restoring = Rc::new(Cell::new(false));
+    // The number of redirects of the current navigation, for detecting redirect loops.
+    let redirects = Rc::new(Cell::new(0u32));
+
+    #[cfg(feature = "suspense")]
+    let scope = use_current_scope();
+    // Incremented on every navigation so that loaders of outdated navigations are ignored.
+    #[cfg(feature = "suspense")]
+    let generation = Rc::new(Cell::new(0u64));
+    // The handle of the timeout that saves the scroll position once the user stops scrolling.
+    let pending_save = Rc::new(Cell::new(None));
+    // The url and the pending loader of the last prefetched route. The loader is `None` if it has
+    // already finished.
+    #[cfg(feature = "suspense")]
+    let prefetched: Rc<RefCell<Option<Prefetched>>> = Default::default();
+
+    #[cfg(feature = "suspense")]
+    let prefetcher = {
+        let route = route.clone();
+        let loader = loader.clone();
+        let prefetched = prefetched.clone();
+        move |url: &str| {
+            if prefetched.borrow().as_ref().is_some_and(|(u, _)| u == url) {
+                return;
+            }
+            let fut = loader.load(&route.match_path(url)).map(|fut| {
+                let fut = fut.shared();
+                scope.run_in(|| spawn_local_scoped(fut.clone()));
+                fut
+            });
+            *prefetched.borrow_mut() = Some((url.to_string(), fut));
+        }
+    };
+    // There is nothing to prefetch without loaders.
+    #[cfg(not(feature = "suspense"))]
+    let prefetcher = |_: &str| {};
+
+    let navigator = {
+        let integration = integration.clone();
+        let scroll = scroll.clone();
+        let pending_save = pending_save.clone();
+        move |url: &str, mode: HistoryMode, state: Option<String>| {
+            if mode == HistoryMode::Traverse && restoring.replace(false) {
+                // The history is back at the current entry.
+                return;
+            }
+            let to = route.match_path(url);
+            let from = route.match_path(&location.url());
+            let action = match before_leave.check(&from, &to) {
+                NavigationAction::Continue => before_enter.check(&from, &to),
+                action => action,
+            };
+            let action = match action {
+                NavigationAction::Redirect(_) if redirects.get() >= MAX_REDIRECTS => {
+                    console_error!(
+                        "navigation guards redirected more than {MAX_REDIRECTS} times in a row, \
+                         there is probably a redirect loop. The navigation to {url} is cancelled."
+                    );
+                    NavigationAction::Cancel
+                }
+                action => action,
+            };
+            match action {
+                NavigationAction::Continue => {}
+                NavigationAction::Cancel => {
+                    if mode == HistoryMode::Traverse {
+                        // The history entry was already changed. Go back to the current entry.
+                        match integration.history_position() {
+                            Some(new) if new != position.get() => {
+                                restoring.set(true);
+                                integration.go(position.get() as isize - new as isize);
+                            }
+                            // The position of the new entry is unknown. Restore the current url
+                            // in the new entry instead.
+                            _ => {
+                                integration.replace(&location.url());
+                                integration.save_history_position(position.get());
+                                if let Some(state) = location.state.get_clone_untracked() {
+                                    integration.set_state(&state);
+                                }
+                            }
+                        }
+                    }
+                    return;
+                }
+                NavigationAction::Redirect(url) => {
+                    let mode = match mode {
+                        HistoryMode::Traverse => HistoryMode::Replace,
+                        mode => mode,
+                    };
+                    redirects.set(redirects.get() + 1);
+                    run_navigator("navigate", &url, mode, None);
+                    redirects.set(redirects.get() - 1);
+                    return;
+                }
+            }
+
+            // The pending save belongs to the entry that is left.
+            if let Some(handle) = pending_save.take() {
+                window().clear_timeout_with_handle(handle);
+            }
+            if scroll.enabled && mode == HistoryMode::Push {
+                integration.save_scroll_position(integration.scroll_position());
+            }
+            match mode {
+                HistoryMode::Push => {
+                    integration.push(url);
+                    position.set(position.get() + 1);
+                    integration.save_history_position(position.get());
+                }
+                HistoryMode::Replace => {
+                    integration.replace(url);
+                    integration.save_history_position(position.get());
+                }
+                HistoryMode::Traverse => match integration.history_position() {
+                    Some(new) => position.set(new),
+                    // An entry that was not added by the router (e.g. by editing the hash).
+                    None => {
+                        position.set(position.get() + 1);
+                        integration.save_history_position(position.get());
+                    }
+                },
+            }
+            if let Some(state) = &state {
+                integration.set_state(state);
+            }
+
+            #[cfg(not(feature = "suspense"))]
+            {
+                location.sync(&*integration);
+                scroll.scroll(&*integration, &to, mode);
+            }
+            #[cfg(feature = "suspense")]
+            {
+                generation.set(generation.get() + 1);
+
+                // Reuse the loader if the route was prefetched.
+                let prefetched = prefetched.borrow_mut().take();
+                let pending = match prefetched {
+                    Some((prefetched_url, fut)) if prefetched_url == url => {
+                        fut.and_then(|fut| poll_once(Box::pin(fut)))
+                    }
+                    _ => loader.load(&to),
+                };
+                match pending {
+                    None => {
+                        location.sync(&*integration);
+                        scroll.scroll(&*integration, &to, mode);
+                    }
+                    Some(fut) => scope.run_in(|| {
+                        let current = generation.get();
+                        let generation = generation.clone();
+                        let integration = integration.clone();
+                        let scroll = scroll.clone();
+                        let guard = SuspenseTaskGuard::new();
+                        spawn_local_scoped(async move {
+                            fut.await;
+                            // Do not commit if another navigation happened in the meantime.
+                            if generation.get() == current {
+                                location.sync(&*integration);
+                                scroll.scroll(&*integration, &to, mode);
+                            }
+                            drop(guard);
+                        });
+                    }),
+                }
+            }
+        }
+    };
+
+    ROUTER_STATE.with(|state| {
+        assert!(
+            state.borrow().is_none(),
+            "cannot have more than one Router component initialized"
+        );
+        *state.borrow_mut() = Some(RouterState {
+            location,
+            navigator: Rc::new(navigator),
+            prefetcher: Rc::new(prefetcher),
+            integration: integration.clone(),
+        });
+    });
+
+    // Set ROUTER_STATE to None when the Router is destroyed.
+    on_cleanup(|| ROUTER_STATE.with(|state| *state.borrow_mut() = None));
+
+    // Listen to popstate event.
+    integration.on_popstate(Box::new({
+        let integration = integration.clone();
+        move || {
+            if location.pathname.is_alive() {
+                run_navigator(
+                    "RouterBase",
+                    &integration_url(&*integration),
+                    HistoryMode::Traverse,
+                    None,
+                );
+            }
+        }
+    }));
+    let view = view(route_signal);
+    if is_not_ssr!() {
+        if scroll.enabled {
+            save_scroll_on_scroll_end(integration.clone(), pending_save);
+            if scroll.restore {
+                // Restore the scroll position after a reload.
+                let integration = integration.clone();
+                on_mount(move || {
+                    if let Some(position) = integration.saved_scroll_position() {
+                        integration.scroll_to(position);
+                    }
+                });
+            }
+        }
+        let nodes = view.as_web_sys();
+        on_mount(move || {
+            for node in nodes {
+                let handler: Closure<dyn FnMut(web_sys::MouseEvent)> =
+                    Closure::new(integration.click_handler());
+                node.add_event_listener_with_callback(
+                    "click",
+                    handler.into_js_value().unchecked_ref(),
+                )
+                .unwrap(); // TODO: manage in scope
+            }
+            // TODO: this does not work for dynamic views
+        });
+    }
+    view
+}
+
+/// Saves the scroll position in the current history entry once the user stops scrolling.
+///
+/// `pending` is set to the handle of the timeout that saves the position while the user is still
+/// scrolling.
+fn save_scroll_on_scroll_end(integration: Rc<dyn Integration>, pending: Rc<Cell<Option<i32>>>) {
+    // The router restores the scroll position itself.
+    let _ = window()
+        .history()
+        .unwrap_throw()
+        .set_scroll_restoration(web_sys::ScrollRestoration::Manual);
+
+    let save = Closure::<dyn FnMut()>::new({
+        let pending = pending.clone();
+        move || {
+            pending.set(None);
+            integration.save_scroll_position(integration.scroll_position());
+        }
+    });
+    let on_scroll = Closure::<dyn FnMut()>::new({
+        let pending = pending.clone();
+        move || {
+            let window = window();
+            if let Some(handle) = pending.take() {
+                window.clear_timeout_with_handle(handle);
+            }
+            let handle = window
+                .set_timeout_with_callback_and_timeout_and_arguments_0(
+                    save.as_ref().unchecked_ref(),
+                    100,
+                )
+                .unwrap_throw();
+            pending.set(Some(handle));
+        }
+    });
+    window()
+        .add_event_listener_with_callback("scroll", on_scroll.as_ref().unchecked_ref())
+        .unwrap_throw();
+    let (pending, on_scroll) = (ThreadBound::new(pending), ThreadBound::new(on_scroll));
+    on_cleanup(move || {
+        let window = window();
+        if let Some(handle) = pending.take() {
+            window.clear_timeout_with_handle(handle);
+        }
+        let _ = window
+            .remove_event_listener_with_callback("scroll", on_scroll.as_ref().unchecked_ref());
+    });
+}
+
+/// Props for [`SubRouter`].
+#[derive(Props, Debug)]
+pub struct SubRouterProps<R, S, M, L, F>
+where
+    R: Route + 'static,
+    S: Route + Clone + 'static,
+    M: Fn(&R) -> Option<&S> + 'static,
+    L: FnOnce(View) -> View + 'static,
+    F: FnOnce(ReadSignal<S>) -> View + 'static,
+{
+    route: ReadSignal<R>,
+    select: M,
+    layout: L,
+    view: F,
+}
+
+impl<R, S, M, L, F> SubRouterProps<R, S, M, L, F>
+where
+    R: Route + 'static,
+    S: Route + Clone + 'static,
+    M: Fn(&R) -> Option<&S> + 'static,
+    L: FnOnce(View) -> View + 'static,
+    F: FnOnce(ReadSignal<S>) -> View + 'static,
+{
+    /// Create a new [`SubRouterProps`].
+    pub fn new(route: ReadSignal<R>, select: M, layout: L, view: F) -> Self {
+        Self {
+            route,
+            select,
+            layout,
+            view,
+        }
+    }
+}
+
+/// Renders a layout around the nested route of a `#[sub_route]` variant.
+///
+/// `select` extracts the nested route from the parent `route`. The [`Route`] derive generates a
+/// `select_*` method for every `#[sub_route]` variant that can be used for this, e.g.
+/// `Routes::select_admin` for a variant named `Admin`. `view` is called once with a signal
+/// of the nested route and its result is passed to `layout` as the outlet. Navigating between
+/// nested routes only updates the nested route signal, so the layout is kept alive for as long as
+/// the `SubRouter` itself is not re-created by the parent view.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore_router::*;
+/// #[derive(Route, Clone)]
+/// enum AdminRoutes {
+///     #[to("/")]
+///     Dashboard,
+///     #[to("/users")]
+///     Users,
+///     #[not_found]
+///     NotFound,
+/// }
+///
+/// #[derive(Route, Clone)]
+/// enum Routes {
+///     #[to("/")]
+///     Home,
+///     #[sub_route("/admin")]
+///     Admin(AdminRoutes),
+///     #[not_found]
+///     NotFound,
+/// }
+///
+/// # fn app(route: ReadSignal<Routes>) -> View {
+/// view! {
+///     SubRouter(
+///         route=route,
+///         select=Routes::select_admin,
+///         layout=|outlet| view! { nav { "Admin" } main { (outlet) } },
+///         view=|route: ReadSignal<AdminRoutes>| view! {
+///             (match route.get_clone() {
+///                 AdminRoutes::Dashboard => "Dashboard",
+///                 AdminRoutes::Users => "Users",
+///                 AdminRoutes::NotFound => "Not Found",
+///             })
+///         },
+///     )
+/// }
+/// # }
+/// ```
+///
+/// If `select` returns `None` when the component is first rendered, e.g. because the parent route
+/// does not match the nested route yet, the nested route starts out as `S::default()`. For a
+/// derived [`Route`], this is the `#[not_found]` variant.
+#[component]
+pub fn SubRouter<R, S, M, L, F>(props: SubRouterProps<R, S, M, L, F>) -> View
+where
+    R: Route + MaybeSend + 'static,
+    S: Route + Clone + MaybeSend + 'static,
+    M: Fn(&R) -> Option<&S> + MaybeSend + 'static,
+    L: FnOnce(View) -> View + 'static,
+    F: FnOnce(ReadSignal<S>) -> View + 'static,
+{
+    let SubRouterProps {
+        route,
+        select,
+        layout,
+        view,
     } = props;
-    let integration = Rc::new(integration);
-    let base_pathname = base_pathname();
-
-    PATHNAME.with(|pathname| {
-        assert!(
-            pathname.get().is_none(),
-            "cannot have more than one Router component initialized"
-        );
-        // Get initial url from window.location.
-        let path = integration.current_pathname();
-        let path = path.strip_prefix(&base_pathname).unwrap_or(&path);
-        pathname.set(Some(create_signal(path.to_string())));
-    });
-    let pathname = PATHNAME.with(|p| p.get().unwrap_throw());
 
-    // Set PATHNAME to None when the Router is destroyed.
-    on_cleanup(|| PATHNAME.with(|pathname| pathname.set(None)));
-
-    // Listen to popstate event.
-    integration.on_popstate(Box::new({
-        let integration = integration.clone();
-        move || {
-            let path = integration.current_pathname();
-            let path = path.strip_prefix(&base_pathname).unwrap_or(&path);
-            if pathname.with(|pathname| pathname != path) {
-                pathname.set(path.to_string());
-            }
-        }
-    }));
-    let route_signal = create_memo(move || pathname.with(|pathname| route.match_path(pathname)));
-    let view = view(route_signal);
-    let nodes = view.as_web_sys();
-    on_mount(move || {
-        for node in nodes {
-            let handler: Closure<dyn FnMut(web_sys::MouseEvent)> =
-                Closure::new(integration.click_handler());
-            node.add_event_listener_with_callback("click", handler.into_js_value().unchecked_ref())
-                .unwrap(); // TODO: manage in scope
+    let initial = route
+        .with_untracked(|route| select(route).cloned())
+        .unwrap_or_default();
+    let sub_route = create_signal(initial);
+    create_effect(move || {
+        // The parent route can briefly point to a different variant before this scope is
+        // disposed. Keep the last nested route in that case.
+        if let Some(new) = route.with(|route| select(route).cloned()) {
+            sub_route.set(new);
         }
-        // TODO: this does not work for dynamic views
     });
-    view
+
+    let outlet = view(*sub_route);
+    layout(outlet)
 }
 
 /// Props for [`StaticRouter`].
@@ -291,10 +1660,13 @@ where
 ///
 /// This is useful for SSR where we want the HTML to be rendered instantly instead of waiting for
 /// the route preload to finish loading.
+///
+/// If the route is not found (see [`Route::is_not_found`]), the status code of the
+/// [`SsrResponse`](sycamore::web::SsrResponse) is set to `404`.
 #[component]
 pub fn StaticRouter<R, F>(props: StaticRouterProps<R, F>) -> View
 where
-    R: Route + 'static,
+    R: Route + MaybeSend + 'static,
     F: Fn(ReadSignal<R>) -> View + 'static,
 {
     view! {
@@ -307,11 +1679,16 @@ where
 #[component]
 fn StaticRouterBase<R, F>(props: StaticRouterProps<R, F>) -> View
 where
-    R: Route + 'static,
+    R: Route + MaybeSend + 'static,
     F: Fn(ReadSignal<R>) -> View + 'static,
 {
     let StaticRouterProps { view, route } = props;
 
+    if route.is_not_found() {
+        if let Some(response) = use_ssr_response() {
+            response.set_status(404);
+        }
+    }
     view(*create_signal(route))
 }
 
@@ -320,26 +1697,70 @@ where
 /// This is useful for imperatively navigating to an url when using an anchor tag (`<a>`) is not
 /// possible/suitable (e.g. when submitting a form).
 ///
+/// The new history entry is added using the [`Integration`] of the active [`Router`]. The
+/// navigation guards and the loader of the router are run first, so the navigation can be cancelled
+/// or delayed.
+///
 /// # Panics
 /// This function will `panic!()` if a [`Router`] has not yet been created.
 pub fn navigate(url: &str) {
-    PATHNAME.with(|pathname| {
-        assert!(
-            pathname.get().is_some(),
-            "navigate can only be used with a Router"
-        );
+    run_navigator("navigate", url, HistoryMode::Push, None);
+}
 
-        let pathname = pathname.get().unwrap_throw();
-        let path = url.strip_prefix(&base_pathname()).unwrap_or(url);
-        pathname.set(path.to_string());
+/// Runs the navigator of the active router. `name` is used in the panic message if there is no
+/// active router.
+fn run_navigator(name: &str, url: &str, mode: HistoryMode, state: Option<String>) {
+    let navigator = use_router_state(name).navigator;
+    navigator(url, mode, state);
+}
 
-        // Update History API.
-        let history = window().history().unwrap_throw();
-        history
-            .push_state_with_url(&JsValue::UNDEFINED, "", Some(url))
-            .unwrap_throw();
-        window().scroll_to_with_x_and_y(0.0, 0.0);
-    });
+/// Options for [`navigate_with_options`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NavigateOptions {
+    /// Replace the current history entry instead of adding a new one.
+    pub replace: bool,
+    /// State to attach to the new history entry. The state of the current entry can be accessed
+    /// with [`use_history_state`].
+    pub state: Option<String>,
+}
+
+/// Navigates to the specified `url` with the given `options`. See [`navigate`].
+///
+/// # Panics
+/// This function will `panic!()` if a [`Router`] has not yet been created.
+pub fn navigate_with_options(url: &str, options: NavigateOptions) {
+    let mode = if options.replace {
+        HistoryMode::Replace
+    } else {
+        HistoryMode::Push
+    };
+    run_navigator("navigate_with_options", url, mode, options.state);
+}
+
+/// Runs the loader of the route matching the `url` ahead of time, so that navigating to the `url`
+/// later on does not have to wait as long. The loader is only run again once the `url` has been
+/// navigated to.
+///
+/// This does nothing if there is no active [`Router`].
+pub fn prefetch(url: &str) {
+    if let Some(state) = try_use_router_state() {
+        (state.prefetcher)(url);
+    }
+}
+
+/// Navigates to the path of the specified `route`. See [`Route::to_path`].
+///
+/// This is useful for navigating without hand-writing urls that can drift from the route
+/// definitions.
+///
+/// # Panics
+/// This function will `panic!()` if a [`Router`] has not yet been created or if the route does not
+/// have a path (see [`Route::to_path`]).
+pub fn navigate_to<R: Route>(route: &R) {
+    let path = route
+        .to_path()
+        .expect("navigate_to can only be used with a route that has a path");
+    navigate(&path);
 }
 
 /// Navigates to the specified `url` without adding a new history entry. Instead, this replaces the
@@ -351,23 +1772,63 @@ pub fn navigate(url: &str) {
 /// # Panics
 /// This function will `panic!()` if a [`Router`] has not yet been created.
 pub fn navigate_replace(url: &str) {
-    PATHNAME.with(|pathname| {
-        assert!(
-            pathname.get().is_some(),
-            "navigate_replace can only be used with a Router"
-        );
+    run_navigator("navigate_replace", url, HistoryMode::Replace, None);
+}
 
-        let pathname = pathname.get().unwrap_throw();
-        let path = url.strip_prefix(&base_pathname()).unwrap_or(url);
-        pathname.set(path.to_string());
+/// Returns a signal of the current query string, without the leading `?`.
+///
+/// The signal is updated whenever the query string changes, including when navigating to the same
+/// pathname with a different query string.
+///
+/// # Panics
+/// This function will `panic!()` if a [`Router`] has not yet been created.
+pub fn use_query() -> ReadSignal<String> {
+    *use_router_state("use_query").location.query
+}
 
-        // Update History API.
-        let history = window().history().unwrap_throw();
-        history
-            .replace_state_with_url(&JsValue::UNDEFINED, "", Some(url))
-            .unwrap_throw();
-        window().scroll_to_with_x_and_y(0.0, 0.0);
-    });
+/// Returns a signal of the value of the query parameter `key`, converted using [`TryFromParam`].
+///
+/// The value is `None` if the parameter is missing or cannot be converted.
+///
+/// # Panics
+/// This function will `panic!()` if a [`Router`] has not yet been created.
+pub fn use_query_param<T: TryFromParam + MaybeSend + 'static>(
+    key: impl Into<String>,
+) -> ReadSignal<Option<T>> {
+    let key = key.into();
+    let query = use_router_state("use_query_param").location.query;
+    create_memo(move || {
+        query.with(|query| {
+            QueryParams::parse(query)
+                .get(&key)
+                .and_then(T::try_from_param)
+        })
+    })
+}
+
+/// Returns a signal of the current hash, without the leading `#`.
+///
+/// # Panics
+/// This function will `panic!()` if a [`Router`] has not yet been created.
+pub fn use_hash() -> ReadSignal<String> {
+    *use_router_state("use_hash").location.hash
+}
+
+/// Returns a signal of the state that was attached to the current history entry, if any. See
+/// [`NavigateOptions::state`].
+///
+/// # Panics
+/// This function will `panic!()` if a [`Router`] has not yet been created.
+pub fn use_history_state() -> ReadSignal<Option<String>> {
+    *use_router_state("use_history_state").location.state
+}
+
+/// Returns a signal of the current pathname, relative to the base path of the app.
+///
+/// # Panics
+/// This function will `panic!()` if a [`Router`] has not yet been created.
+pub fn use_pathname() -> ReadSignal<String> {
+    *use_router_state("use_pathname").location.pathname
 }
 
 fn meta_keys_pressed(kb_event: &KeyboardEvent) -> bool {
@@ -377,8 +1838,10 @@ fn meta_keys_pressed(kb_event: &KeyboardEvent) -> bool {
 #[cfg(test)]
 mod tests {
     use sycamore::prelude::*;
+    use sycamore::web::SsrResponse;
 
     use super::*;
+    use crate::Redirect;
 
     #[test]
     fn static_router() {
@@ -440,4 +1903,542 @@ mod tests {
             "Not Found"
         );
     }
+
+    #[test]
+    fn static_router_response() {
+        #[derive(Route, Clone)]
+        enum Routes {
+            #[to("/")]
+            Home,
+            #[to("/old")]
+            Old,
+            #[not_found]
+            NotFound,
+        }
+
+        let render = |route: Routes| {
+            sycamore::web::render_to_string_with_response(move || {
+                view! {
+                    StaticRouter(
+                        route=route,
+                        view=|route: ReadSignal<Routes>| match route.get_clone() {
+                            Routes::Home => view! { "Home" },
+                            Routes::Old => view! { Redirect(to=Routes::Home) },
+                            Routes::NotFound => view! { "Not Found" },
+                        },
+                    )
+                }
+            })
+            .1
+        };
+
+        assert_eq!(render(Routes::Home), SsrResponse::default());
+        assert_eq!(
+            render(Routes::Old),
+            SsrResponse {
+                status: Some(302),
+                redirect: Some("/".to_string()),
+                headers: Vec::new(),
+            }
+        );
+        assert_eq!(render(Routes::NotFound).status, Some(404));
+    }
+
+    #[test]
+    fn base_pathname_segment_boundary() {
+        assert!(is_in_base_pathname("/app", "/app"));
+        assert!(is_in_base_pathname("/app/about", "/app"));
+        assert!(is_in_base_pathname("/app?page=2", "/app"));
+        assert!(!is_in_base_pathname("/application", "/app"));
+        assert!(!is_in_base_pathname("/about", "/app"));
+        assert!(is_in_base_pathname("/about", ""));
+    }
+
+    #[test]
+    fn hash_link_urls() {
+        assert_eq!(hash_link_url("#/about"), Some("/about"));
+        assert_eq!(hash_link_url("#/"), Some("/"));
+        assert_eq!(hash_link_url("/about"), Some("/about"));
+        assert_eq!(hash_link_url("#section"), None);
+        assert_eq!(hash_link_url("#"), None);
+        assert_eq!(hash_link_url("//example.com"), None);
+        assert_eq!(hash_link_url("https://example.com"), None);
+    }
+
+    #[test]
+    fn memory_integration() {
+        #[derive(Route, Clone, Debug, PartialEq, Eq)]
+        enum Routes {
+            #[to("/")]
+            Home,
+            #[to("/about")]
+            About,
+            #[to("/search?<q>")]
+            Search { q: String },
+            #[not_found]
+            NotFound,
+        }
+
+        let root = create_root(|| {
+            let integration = MemoryIntegration::new("/");
+            let current = create_signal(Routes::NotFound);
+            let router_integration = integration.clone();
+            let _: View = view! {
+                Router(
+                    integration=router_integration,
+                    view=move |route: ReadSignal<Routes>| {
+                        create_effect(move || current.set(route.get_clone()));
+                        view! {}
+                    },
+                )
+            };
+            assert_eq!(current.get_clone(), Routes::Home);
+
+            navigate("/about");
+            assert_eq!(current.get_clone(), Routes::About);
+            assert_eq!(integration.current_url(), "/about");
+
+            navigate_to(&Routes::Search {
+                q: "sycamore".to_string(),
+            });
+            assert_eq!(
+                current.get_clone(),
+                Routes::Search {
+                    q: "sycamore".to_string()
+                }
+            );
+            assert_eq!(use_query().get_clone(), "q=sycamore");
+
+            // Changing only the query should update the route.
+            navigate("/search?q=router#results");
+            assert_eq!(
+                current.get_clone(),
+                Routes::Search {
+                    q: "router".to_string()
+                }
+            );
+            assert_eq!(use_hash().get_clone(), "results");
+
+            integration.back();
+            assert_eq!(
+                current.get_clone(),
+                Routes::Search {
+                    q: "sycamore".to_string()
+                }
+            );
+            integration.go(-2);
+            assert_eq!(current.get_clone(), Routes::Home);
+            assert!(!integration.can_go_back());
+            integration.forward();
+            assert_eq!(current.get_clone(), Routes::About);
+
+            // Replacing the current entry discards the forward history.
+            navigate("/404");
+            assert_eq!(current.get_clone(), Routes::NotFound);
+            assert!(!integration.can_go_forward());
+            navigate_replace("/");
+            assert_eq!(current.get_clone(), Routes::Home);
+            integration.back();
+            assert_eq!(current.get_clone(), Routes::About);
+        });
+        root.dispose();
+    }
+
+    #[test]
+    fn navigation_guards() {
+        #[derive(Route, Clone, Debug, PartialEq, Eq)]
+        enum Routes {
+            #[to("/")]
+            Home,
+            #[to("/form")]
+            Form,
+            #[to("/admin")]
+            Admin,
+            #[to("/login")]
+            Login,
+            #[not_found]
+            NotFound,
+        }
+
+        let root = create_root(|| {
+            let integration = MemoryIntegration::new("/");
+            let current = create_signal(Routes::NotFound);
+            let dirty = create_signal(false);
+            let router_integration = integration.clone();
+            let _: View = view! {
+                Router(
+                    integration=router_integration,
+                    before_leave=move |from: &Routes, _to: &Routes| {
+                        if *from == Routes::Form && dirty.get() {
+                            NavigationAction::Cancel
+                        } else {
+                            NavigationAction::Continue
+                        }
+                    },
+                    before_enter=|_from: &Routes, to: &Routes| match to {
+                        Routes::Admin => NavigationAction::Redirect("/login".to_string()),
+                        _ => NavigationAction::Continue,
+                    },
+                    view=move |route: ReadSignal<Routes>| {
+                        create_effect(move || current.set(route.get_clone()));
+                        view! {}
+                    },
+                )
+            };
+
+            navigate("/form");
+            assert_eq!(current.get_clone(), Routes::Form);
+
+            // Unsaved changes block the navigation.
+            dirty.set(true);
+            navigate("/");
+            assert_eq!(current.get_clone(), Routes::Form);
+            assert_eq!(integration.current_url(), "/form");
+
+            // Going back is also blocked and the history goes back to the current entry.
+            integration.back();
+            assert_eq!(current.get_clone(), Routes::Form);
+            assert_eq!(integration.current_url(), "/form");
+            assert!(!integration.can_go_forward());
+
+            dirty.set(false);
+            navigate("/admin");
+            assert_eq!(current.get_clone(), Routes::Login);
+            assert_eq!(integration.current_url(), "/login");
+            integration.back();
+            assert_eq!(integration.current_url(), "/form");
+            integration.back();
+            assert_eq!(integration.current_url(), "/");
+        });
+        root.dispose();
+    }
+
+    #[test]
+    fn navigation_guard_redirect_loop() {
+        #[derive(Route, Clone, Debug, PartialEq, Eq)]
+        enum Routes {
+            #[to("/")]
+            Home,
+            #[to("/a")]
+            A,
+            #[to("/b")]
+            B,
+            #[to("/c")]
+            C,
+            #[not_found]
+            NotFound,
+        }
+
+        let root = create_root(|| {
+            let integration = MemoryIntegration::new("/");
+            let current = create_signal(Routes::NotFound);
+            let router_integration = integration.clone();
+            let _: View = view! {
+                Router(
+                    integration=router_integration,
+                    before_enter=|_from: &Routes, to: &Routes| match to {
+                        Routes::A => NavigationAction::Redirect("/b".to_string()),
+                        Routes::B => NavigationAction::Redirect("/a".to_string()),
+                        _ => NavigationAction::Continue,
+                    },
+                    view=move |route: ReadSignal<Routes>| {
+                        create_effect(move || current.set(route.get_clone()));
+                        view! {}
+                    },
+                )
+            };
+
+            // The redirect loop cancels the navigation and keeps the current route.
+            navigate("/a");
+            assert_eq!(current.get_clone(), Routes::Home);
+            assert_eq!(integration.current_url(), "/");
+            assert!(!integration.can_go_back());
+
+            // The router keeps working afterwards.
+            navigate("/c");
+            assert_eq!(current.get_clone(), Routes::C);
+            assert_eq!(integration.current_url(), "/c");
+        });
+        root.dispose();
+    }
+
+    #[cfg(feature = "suspense")]
+    #[tokio::test]
+    async fn route_loader() {
+        #[derive(Route, Clone, Debug, PartialEq, Eq)]
+        enum Routes {
+            #[to("/")]
+            Home,
+            #[to("/post/<id>")]
+            Post { id: u32 },
+            #[not_found]
+            NotFound,
+        }
+
+        let (tx, rx) = futures::channel::oneshot::channel::<String>();
+        let rx = Rc::new(RefCell::new(Some(rx)));
+        let local = tokio::task::LocalSet::new();
+        let mut current = None;
+        let mut post = None;
+        let root = local
+            .run_until(async {
+                create_root(|| {
+                    let integration = MemoryIntegration::new("/");
+                    let current_signal = create_signal(Routes::NotFound);
+                    let post_signal = create_signal(String::new());
+                    let loader = RouteLoader::new(move |route: &Routes| {
+                        let rx = match route {
+                            Routes::Post { .. } => rx.borrow_mut().take(),
+                            _ => None,
+                        };
+                        async move {
+                            if let Some(rx) = rx {
+                                post_signal.set(rx.await.unwrap());
+                            }
+                        }
+                    });
+                    let _: View = view! {
+                        Router(
+                            integration=integration,
+                            loader=loader,
+                            view=move |route: ReadSignal<Routes>| {
+                                create_effect(move || current_signal.set(route.get_clone()));
+                                view! {}
+                            },
+                        )
+                    };
+
+                    navigate("/post/1");
+                    // The route is only updated once the loader has finished.
+                    assert_eq!(current_signal.get_clone(), Routes::Home);
+                    current = Some(current_signal);
+                    post = Some(post_signal);
+                })
+            })
+            .await;
+        let (current, post) = (current.unwrap(), post.unwrap());
+
+        tx.send("Hello".to_string()).unwrap();
+        local.await;
+        assert_eq!(current.get_clone(), Routes::Post { id: 1 });
+        assert_eq!(post.get_clone(), "Hello");
+        root.dispose();
+    }
+
+    #[test]
+    fn history_state() {
+        #[derive(Route, Clone, Debug, PartialEq, Eq)]
+        enum Routes {
+            #[to("/")]
+            Home,
+            #[to("/post/<id>")]
+            Post { id: u32 },
+            #[not_found]
+            NotFound,
+        }
+
+        let root = create_root(|| {
+            let integration = MemoryIntegration::new("/");
+            let router_integration = integration.clone();
+            let _: View = view! {
+                Router(
+                    integration=router_integration,
+                    view=|_: ReadSignal<Routes>| view! {},
+                )
+            };
+
+            navigate_with_options(
+                "/post/2",
+                NavigateOptions {
+                    replace: false,
+                    state: Some("from-home".to_string()),
+                },
+            );
+            assert_eq!(
+                use_history_state().get_clone().as_deref(),
+                Some("from-home")
+            );
+            integration.back();
+            assert_eq!(use_history_state().get_clone(), None);
+            integration.forward();
+            assert_eq!(
+                use_history_state().get_clone().as_deref(),
+                Some("from-home")
+            );
+        });
+        root.dispose();
+    }
+
+    #[cfg(feature = "suspense")]
+    #[test]
+    fn prefetch_reuses_loader() {
+        #[derive(Route, Clone, Debug, PartialEq, Eq)]
+        enum Routes {
+            #[to("/")]
+            Home,
+            #[to("/post/<id>")]
+            Post { id: u32 },
+            #[not_found]
+            NotFound,
+        }
+
+        let root = create_root(|| {
+            let loads = create_signal(0);
+            let _: View = view! {
+                Router(
+                    integration=MemoryIntegration::new("/"),
+                    loader=RouteLoader::new(move |_: &Routes| {
+                        loads.set(loads.get() + 1);
+                        async {}
+                    }),
+                    view=|_: ReadSignal<Routes>| view! {},
+                )
+            };
+            assert_eq!(loads.get(), 1);
+
+            // The prefetched loader is reused by the navigation.
+            prefetch("/post/1");
+            prefetch("/post/1");
+            assert_eq!(loads.get(), 2);
+            navigate("/post/1");
+            assert_eq!(loads.get(), 2);
+            navigate("/");
+            assert_eq!(loads.get(), 3);
+        });
+        root.dispose();
+    }
+
+    #[test]
+    fn scroll_restoration() {
+        #[derive(Route, Clone, Copy, Debug, PartialEq, Eq)]
+        enum Routes {
+            #[to("/")]
+            Home,
+            #[to("/list")]
+            List,
+            #[to("/tab")]
+            Tab,
+            #[not_found]
+            NotFound,
+        }
+
+        let at = |y| ScrollPosition { x: 0.0, y };
+
+        let root = create_root(|| {
+            let integration = MemoryIntegration::new("/");
+            let router_integration = integration.clone();
+            let _: View = view! {
+                Router(
+                    integration=router_integration,
+                    scroll=ScrollBehavior::new().skip(|route| *route == Routes::Tab),
+                    view=|_: ReadSignal<Routes>| view! {},
+                )
+            };
+
+            integration.scroll_to(at(500.0));
+            navigate("/list");
+            assert_eq!(integration.scroll_position(), at(0.0));
+
+            integration.scroll_to(at(300.0));
+            integration.back();
+            assert_eq!(integration.scroll_position(), at(500.0));
+            integration.forward();
+            assert_eq!(integration.scroll_position(), at(300.0));
+
+            // Opted out routes keep the scroll position.
+            navigate("/tab");
+            assert_eq!(integration.scroll_position(), at(300.0));
+            // Unknown anchors scroll to the top.
+            navigate("/list#missing");
+            assert_eq!(integration.scroll_position(), at(0.0));
+        });
+        root.dispose();
+    }
+
+    #[test]
+    fn sub_router_layout() {
+        #[derive(Route, Clone)]
+        enum Admin {
+            #[to("/")]
+            Dashboard,
+            #[to("/users")]
+            Users,
+            #[not_found]
+            NotFound,
+        }
+
+        #[derive(Route, Clone)]
+        enum Routes {
+            #[to("/")]
+            Home,
+            #[sub_route("/admin")]
+            Admin(Admin),
+            #[not_found]
+            NotFound,
+        }
+
+        #[component(inline_props)]
+        fn Comp(path: String) -> View {
+            let route = Routes::default().match_path(&path);
+
+            view! {
+                StaticRouter(
+                    route=route,
+                    view=|route: ReadSignal<Routes>| {
+                        match route.get_clone() {
+                            Routes::Home => view! { "Home" },
+                            Routes::Admin(_) => view! {
+                                SubRouter(
+                                    route=route,
+                                    select=Routes::select_admin,
+                                    layout=|outlet| view! { nav { "Admin" } main { (outlet) } },
+                                    view=|route: ReadSignal<Admin>| view! {
+                                        (match route.get_clone() {
+                                            Admin::Dashboard => "Dashboard",
+                                            Admin::Users => "Users",
+                                            Admin::NotFound => "Not Found",
+                                        })
+                                    },
+                                )
+                            },
+                            Routes::NotFound => view! { "Not Found" },
+                        }
+                    },
+                )
+            }
+        }
+
+        assert_eq!(
+            sycamore::render_to_string(|| view! { Comp(path="/admin".to_string()) }),
+            "<nav data-hk=\"0.0\">Admin</nav><main data-hk=\"0.1\"><!--/-->Dashboard<!--/--></main>"
+        );
+        assert_eq!(
+            sycamore::render_to_string(|| view! { Comp(path="/admin/users".to_string()) }),
+            "<nav data-hk=\"0.0\">Admin</nav><main data-hk=\"0.1\"><!--/-->Users<!--/--></main>"
+        );
+        assert_eq!(
+            sycamore::render_to_string(|| view! { Comp(path="/admin/404".to_string()) }),
+            "<nav data-hk=\"0.0\">Admin</nav><main data-hk=\"0.1\"><!--/-->Not Found<!--/--></main>"
+        );
+        // The parent route does not match yet, so the nested not found route is rendered.
+        assert_eq!(
+            sycamore::render_to_string(|| view! {
+                SubRouter(
+                    route=*create_signal(Routes::Home),
+                    select=|route: &Routes| match route {
+                        Routes::Admin(admin) => Some(admin),
+                        _ => None,
+                    },
+                    layout=|outlet| outlet,
+                    view=|route: ReadSignal<Admin>| view! {
+                        (match route.get_clone() {
+                            Admin::NotFound => "Not Found",
+                            _ => "Matched",
+                        })
+                    },
+                )
+            }),
+            "<!--/-->Not Found<!--/-->"
+        );
+    }
 }
@@ -0,0 +1,181 @@
+//! Static site generation for [`Route`] enums.
+
+#[cfg(feature = "suspense")]
+use sycamore::prelude::*;
+#[cfg(feature = "suspense")]
+use sycamore::web::{StaticPage, StaticSite, StaticSiteError};
+
+use crate::Route;
+#[cfg(feature = "suspense")]
+use crate::StaticRouter;
+
+/// Returns the paths of all the routes of `R` that do not have dynamic parameters (see
+/// [`Route::static_routes`]), followed by the paths of `routes`. Duplicate paths are removed.
+///
+/// `routes` should contain the routes with dynamic parameters that should be rendered, e.g. one
+/// route for every blog post.
+pub fn static_paths<R: Route>(routes: impl IntoIterator<Item = R>) -> Vec<String> {
+    let mut paths = Vec::new();
+    for route in R::static_routes().into_iter().chain(routes) {
+        // Routes without a path cannot be rendered.
+        let Some(path) = route.to_path() else {
+            continue;
+        };
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Renders all the routes of `R` with a [`StaticRouter`] and writes them to the output directory
+/// of the `site`, along with a sitemap. See [`StaticSite::generate`].
+///
+/// The rendered paths are given by [`static_paths`]: every route without dynamic parameters as
+/// well as all the `routes` passed in. Every path is matched again before it is rendered, so a
+/// route that does not round-trip through [`Route::to_path`] is rendered as the route it would
+/// match in the browser. Paths that match the `#[not_found]` route are returned as errors.
+///
+/// This requires the `suspense` feature.
+///
+/// # Example
+/// ```no_run
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::StaticSite;
+/// # use sycamore_router::*;
+/// #[derive(Route, Clone)]
+/// enum Routes {
+///     #[to("/")]
+///     Home,
+///     #[to("/posts/<id>")]
+///     Post { id: u32 },
+///     #[not_found]
+///     NotFound,
+/// }
+///
+/// # tokio_test::block_on(async move {
+/// let site = StaticSite::new("dist", "https://example.com");
+/// let posts = (1..=3).map(|id| Routes::Post { id });
+/// generate_static_site(&site, posts, |route: ReadSignal<Routes>| match route.get_clone() {
+///     Routes::Home => view! { "Home" },
+///     Routes::Post { id } => view! { "Post " (id) },
+///     Routes::NotFound => view! { "Not found" },
+/// })
+/// .await
+/// .unwrap();
+/// # })
+/// ```
+#[cfg(feature = "suspense")]
+pub async fn generate_static_site<R, F>(
+    site: &StaticSite,
+    routes: impl IntoIterator<Item = R>,
+    view: F,
+) -> Result<Vec<StaticPage>, StaticSiteError>
+where
+    R: Route + MaybeSend + 'static,
+    F: Fn(ReadSignal<R>) -> View + Clone + 'static,
+{
+    site.generate(static_paths(routes), move |path| {
+        let route = R::default().match_path(path);
+        let view = view.clone();
+        view! {
+            StaticRouter(route=route, view=view)
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "suspense")]
+    use std::fs;
+
+    use super::*;
+
+    #[derive(Route, Clone, Debug, PartialEq, Eq)]
+    enum Docs {
+        #[to("/")]
+        Index,
+        #[to("/<page>")]
+        Page(String),
+        #[not_found]
+        NotFound,
+    }
+
+    #[derive(Route, Clone, Debug, PartialEq, Eq)]
+    enum Routes {
+        #[to("/")]
+        Home,
+        #[to("/about")]
+        About,
+        #[to("/posts/<id>")]
+        Post { id: u32 },
+        #[sub_route("/docs")]
+        Docs(Docs),
+        #[to("/404")]
+        #[not_found]
+        NotFound,
+    }
+
+    #[cfg(feature = "suspense")]
+    fn view(route: ReadSignal<Routes>) -> View {
+        match route.get_clone() {
+            Routes::Home => "Home".into(),
+            Routes::About => "About".into(),
+            Routes::Post { id } => format!("Post {id}").into(),
+            Routes::Docs(Docs::Index) => "Docs".into(),
+            Routes::Docs(Docs::Page(page)) => page.into(),
+            Routes::Docs(Docs::NotFound) | Routes::NotFound => "Not found".into(),
+        }
+    }
+
+    #[test]
+    fn static_paths_includes_dynamic_routes() {
+        assert_eq!(
+            static_paths([
+                Routes::Post { id: 1 },
+                Routes::About,
+                Routes::Post { id: 2 }
+            ]),
+            ["/", "/about", "/docs", "/posts/1", "/posts/2"]
+        );
+    }
+
+    #[cfg(feature = "suspense")]
+    #[tokio::test]
+    async fn generate_static_site_reports_not_found() {
+        let out_dir =
+            std::env::temp_dir().join(format!("sycamore-router-ssg-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&out_dir);
+        let site = StaticSite::new(&out_dir, "https://example.com");
+
+        let pages = generate_static_site(
+            &site,
+            [
+                Routes::Post { id: 1 },
+                Routes::Docs(Docs::Page("intro".to_string())),
+            ],
+            view,
+        )
+        .await
+        .unwrap();
+        assert_eq!(pages.len(), 5);
+        assert_eq!(
+            fs::read_to_string(out_dir.join("docs/intro/index.html")).unwrap(),
+            "<!doctype html>intro"
+        );
+        assert!(fs::read_to_string(out_dir.join("sitemap.xml"))
+            .unwrap()
+            .contains("<loc>https://example.com/posts/1</loc>"));
+
+        let err = generate_static_site(&site, [Routes::Post { id: 2 }, Routes::NotFound], view)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, StaticSiteError::Status(failed) if failed == &[("/404".to_string(), 404)])
+        );
+        assert!(!out_dir.join("posts/2").exists());
+
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+}
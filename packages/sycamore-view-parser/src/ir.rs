@@ -55,8 +55,10 @@ pub enum PropType {
     PlainHyphenated { ident: String },
     /// Syntax: `"<quoted-name>"=<expr>`.
     PlainQuoted { ident: String },
-    /// Syntax: `<dir>:<prop>=<expr>`.
+    /// Syntax: `<dir>:<prop>=<expr>`. The value of a `use:` directive can be omitted.
     Directive { dir: Ident, ident: Ident },
+    /// Syntax: `<dir>:<hyphenated-prop>=<expr>`.
+    DirectiveHyphenated { dir: Ident, ident: String },
     /// Syntax: `ref=<expr>`.
     Ref,
     /// Syntax: `..attributes=<expr>`
@@ -3,7 +3,7 @@
 use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
 use syn::token::{Brace, Paren};
-use syn::{braced, parenthesized, token, Ident, LitStr, Result, Token};
+use syn::{braced, parenthesized, parse_quote_spanned, token, Ident, LitStr, Result, Token};
 
 use crate::ir::*;
 
@@ -112,7 +112,13 @@ impl Parse for Prop {
     fn parse(input: ParseStream) -> Result<Self> {
         let span = input.span();
         let ty = input.parse()?;
-        if !matches!(ty, PropType::Spread { .. }) {
+        // Actions can be used without an argument, e.g. `use:autofocus`.
+        let is_action = matches!(&ty, PropType::Directive { dir, .. } if dir == "use");
+        if is_action && !input.peek(Token![=]) {
+            let value = parse_quote_spanned!(span=> ());
+            return Ok(Self { ty, value, span });
+        }
+        if !matches!(ty, PropType::Spread) {
             let _eqs: Token![=] = input.parse()?;
         }
         let value = input.parse()?;
@@ -148,7 +154,21 @@ impl Parse for PropType {
                 } else if input.peek(Token![:]) {
                     let _colon: Token![:] = input.parse()?;
                     let ident = input.call(Ident::parse_any)?;
-                    Ok(Self::Directive { dir: name, ident })
+                    if input.peek(Token![-]) {
+                        let mut segments = vec![ident];
+                        while input.peek(Token![-]) {
+                            let _: Token![-] = input.parse()?;
+                            segments.push(input.call(Ident::parse_any)?);
+                        }
+                        let ident = segments
+                            .into_iter()
+                            .map(|i| i.to_string())
+                            .collect::<Vec<_>>()
+                            .join("-");
+                        Ok(Self::DirectiveHyphenated { dir: name, ident })
+                    } else {
+                        Ok(Self::Directive { dir: name, ident })
+                    }
                 } else {
                     Ok(Self::Plain { ident: name })
                 }
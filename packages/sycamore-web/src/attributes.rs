@@ -29,6 +29,38 @@ impl AttributeValue for MaybeDyn<JsValue> {
     }
 }
 
+/// A single class that is toggled on an element. This is what is used by the `class:` directive.
+pub(crate) struct ClassToggle(pub BoolAttribute);
+
+impl AttributeValue for ClassToggle {
+    fn set_self(self, el: &mut HtmlNode, name: Cow<'static, str>) {
+        el.set_class_toggle(name, self.0);
+    }
+}
+
+/// A single CSS property that is set on an element. This is what is used by the `style:`
+/// directive.
+pub(crate) struct StyleProperty(pub StringAttribute);
+
+impl AttributeValue for StyleProperty {
+    fn set_self(self, el: &mut HtmlNode, name: Cow<'static, str>) {
+        el.set_style_property(name, self.0);
+    }
+}
+
+/// An attribute that calls a function with the element once it is applied. This is used for
+/// bindings and actions that need access to the element, even when they are spread through
+/// [`Attributes`]. Does nothing in SSR mode.
+pub(crate) struct WithElement<F>(pub F);
+
+impl<F: FnOnce(web_sys::Element) + 'static> AttributeValue for WithElement<F> {
+    fn set_self(self, el: &mut HtmlNode, _name: Cow<'static, str>) {
+        if is_not_ssr!() {
+            (self.0)(el.as_web_sys().clone().unchecked_into());
+        }
+    }
+}
+
 /// Trait used to implement `AttributeValue` for `Box<dyn AttributeValue>`.
 #[doc(hidden)]
 pub trait AttributeValueBoxed: 'static {
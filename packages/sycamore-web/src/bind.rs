@@ -1,8 +1,21 @@
 //! Definition for bind-able attributes/properties.
 
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlInputElement, HtmlOptionElement, HtmlSelectElement};
+
 use crate::events::EventDescriptor;
 use crate::*;
 
+/// A two-way binding between some reactive state and an element. This is what is used by the
+/// `bind:` directive.
+///
+/// This is implemented for every [`BindDescriptor`] with a [`Signal`] of its value type, as well
+/// as for bindings that do not map to a single property, such as [`group`].
+pub trait Bind<S> {
+    /// Sets up the binding on the element.
+    fn bind<T: GlobalAttributes>(el: T, state: S) -> T;
+}
+
 /// Description for a bind-able attribute/property.
 pub trait BindDescriptor {
     /// The event which we listen to to update the value.
@@ -15,8 +28,22 @@ pub trait BindDescriptor {
     const CONVERT_FROM_JS: for<'a> fn(&'a JsValue) -> Option<Self::ValueTy>;
 }
 
+impl<E: BindDescriptor> Bind<Signal<E::ValueTy>> for E
+where
+    E::ValueTy: MaybeSend,
+{
+    fn bind<T: GlobalAttributes>(mut el: T, signal: Signal<E::ValueTy>) -> T {
+        on_event::<E::Event>(&mut el, move |el| {
+            let js_value = js_sys::Reflect::get(el, &E::TARGET_PROPERTY.into()).unwrap();
+            signal.set(E::CONVERT_FROM_JS(&js_value).expect("failed to convert value from js"));
+        });
+        el.prop(E::TARGET_PROPERTY, move || signal.get_clone().into())
+    }
+}
+
 macro_rules! impl_bind {
-    ($name:ident: $event:ty, $value:ty, $target:expr, $fn:expr) => {
+    ($(#[$attr:meta])* $name:ident: $event:ty, $value:ty, $target:expr, $fn:expr) => {
+        $(#[$attr])*
         #[allow(non_camel_case_types)]
         pub struct $name;
         impl BindDescriptor for $name {
@@ -29,13 +56,183 @@ macro_rules! impl_bind {
 }
 
 macro_rules! impl_binds {
-    ($($name:ident: $event:ty, $value:ty, $target:expr, $fn:expr,)*) => {
-        $(impl_bind!($name: $event, $value, $target, $fn);)*
+    ($($(#[$attr:meta])* $name:ident: $event:ty, $value:ty, $target:expr, $fn:expr,)*) => {
+        $(impl_bind!($(#[$attr])* $name: $event, $value, $target, $fn);)*
     };
 }
 
 impl_binds! {
     value: events::input, String, "value", JsValue::as_string,
     valueAsNumber: events::input, f64, "valueAsNumber", JsValue::as_f64,
+    valueAsDate: events::input, Option<js_sys::Date>, "valueAsDate", |v| Some(v.dyn_ref().cloned()),
     checked: events::change, bool, "checked", JsValue::as_bool,
+    textContent: events::input, String, "textContent", JsValue::as_string,
+    /// Binds the inner HTML of an element, e.g. one with the `contenteditable` attribute.
+    ///
+    /// This is a possible security risk, just like
+    /// [`dangerously_set_inner_html`](crate::GlobalProps::dangerously_set_inner_html). Never bind
+    /// this to user input that is displayed to other users, as that will create an XSS (Cross-Site
+    /// Scripting) vulnerability.
+    innerHTML: events::input, String, "innerHTML", JsValue::as_string,
+}
+
+/// Binds a radio button or a checkbox that is part of a group of inputs.
+///
+/// The state is a tuple of the signal of the group and the value of this input.
+/// - For radio buttons, the signal is a `Signal<T>` that holds the value of the checked input.
+/// - For checkboxes, the signal is a `Signal<Vec<T>>` that holds the values of all the checked
+///   inputs.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # fn App() -> View {
+/// let size = create_signal("medium");
+/// let toppings = create_signal(vec!["cheese"]);
+/// view! {
+///     input(r#type="radio", bind:group=(size, "small"))
+///     input(r#type="radio", bind:group=(size, "medium"))
+///     input(r#type="checkbox", bind:group=(toppings, "cheese"))
+///     input(r#type="checkbox", bind:group=(toppings, "olives"))
+/// }
+/// # }
+/// ```
+#[allow(non_camel_case_types)]
+pub struct group;
+
+impl<T: PartialEq + Clone + MaybeSend + 'static> Bind<(Signal<T>, T)> for group {
+    fn bind<E: GlobalAttributes>(mut el: E, (signal, input_value): (Signal<T>, T)) -> E {
+        let checked_value = input_value.clone();
+        on_event::<events::change>(&mut el, move |el| {
+            if el.unchecked_ref::<HtmlInputElement>().checked() {
+                signal.set(checked_value.clone());
+            }
+        });
+        with_element(el, move |el| {
+            create_effect(move || {
+                let is_checked = signal.with(|selected| *selected == input_value);
+                set_property(&el, "checked", &is_checked.into());
+            });
+        })
+    }
+}
+
+impl<T: PartialEq + Clone + MaybeSend + 'static> Bind<(Signal<Vec<T>>, T)> for group {
+    fn bind<E: GlobalAttributes>(mut el: E, (signal, input_value): (Signal<Vec<T>>, T)) -> E {
+        let checked_value = input_value.clone();
+        on_event::<events::change>(&mut el, move |el| {
+            let is_checked = el.unchecked_ref::<HtmlInputElement>().checked();
+            signal.update(|selected| {
+                if !is_checked {
+                    selected.retain(|v| *v != checked_value);
+                } else if !selected.contains(&checked_value) {
+                    selected.push(checked_value.clone());
+                }
+            });
+        });
+        with_element(el, move |el| {
+            create_effect(move || {
+                let is_checked = signal.with(|selected| selected.contains(&input_value));
+                set_property(&el, "checked", &is_checked.into());
+            });
+        })
+    }
+}
+
+/// Binds the values of the selected options of a `<select multiple>` element to a
+/// `Signal<Vec<String>>`.
+///
+/// Options that are added after the signal was last updated are not selected automatically.
+#[allow(non_camel_case_types)]
+pub struct selectedValues;
+
+impl Bind<Signal<Vec<String>>> for selectedValues {
+    fn bind<T: GlobalAttributes>(mut el: T, signal: Signal<Vec<String>>) -> T {
+        on_event::<events::change>(&mut el, move |el| {
+            let selected = el.unchecked_ref::<HtmlSelectElement>().selected_options();
+            let values = (0..selected.length())
+                .filter_map(|i| selected.item(i))
+                .map(|option| option.unchecked_into::<HtmlOptionElement>().value())
+                .collect();
+            signal.set(values);
+        });
+        with_element(el, move |el| {
+            create_effect(move || {
+                let options = el.unchecked_ref::<HtmlSelectElement>().options();
+                signal.with(|values| {
+                    for i in 0..options.length() {
+                        let Some(option) = options.item(i) else {
+                            continue;
+                        };
+                        let option = option.unchecked_into::<HtmlOptionElement>();
+                        let is_selected = values.contains(&option.value());
+                        if option.selected() != is_selected {
+                            option.set_selected(is_selected);
+                        }
+                    }
+                });
+            });
+        })
+    }
+}
+
+/// Binds the selected files of an `<input type="file">` element to a
+/// `Signal<Option<FileList>>`.
+///
+/// The signal is `None` if no files are selected. Setting the signal to `None` clears the input.
+#[allow(non_camel_case_types)]
+pub struct files;
+
+impl Bind<Signal<Option<web_sys::FileList>>> for files {
+    fn bind<T: GlobalAttributes>(mut el: T, signal: Signal<Option<web_sys::FileList>>) -> T {
+        on_event::<events::change>(&mut el, move |el| {
+            signal.set(selected_files(el.unchecked_ref()));
+        });
+        with_element(el, move |el| {
+            create_effect(move || {
+                let input = el.unchecked_ref::<HtmlInputElement>();
+                signal.with(|list| {
+                    if *list != selected_files(input) {
+                        match list {
+                            // Files can only be removed by resetting the value.
+                            None => input.set_value(""),
+                            Some(list) => input.set_files(Some(list)),
+                        }
+                    }
+                });
+            });
+        })
+    }
+}
+
+/// Returns the selected files of the input, or `None` if there are none.
+fn selected_files(input: &HtmlInputElement) -> Option<web_sys::FileList> {
+    input.files().filter(|list| list.length() > 0)
+}
+
+/// Adds a handler for the event `E` that is called with the element. The handler is run in the
+/// current scope.
+fn on_event<E: EventDescriptor>(
+    el: &mut impl SetAttribute,
+    mut handler: impl FnMut(&Element) + 'static,
+) {
+    let scope = use_current_scope();
+    el.set_event_handler(E::NAME, move |ev: web_sys::Event| {
+        scope.run_in(|| handler(ev.current_target().unwrap().unchecked_ref()))
+    });
+}
+
+/// Calls `f` with the element once the binding is applied to it. Does nothing in SSR mode. This is
+/// only used for bindings that do not map to a single property.
+fn with_element<T: SetAttribute>(mut el: T, f: impl FnOnce(Element) + 'static) -> T {
+    el.set_attribute("bind", WithElement(f));
+    el
+}
+
+/// Sets a property of the element if its value changed, like [`GlobalAttributes::prop`] does.
+fn set_property(el: &Element, name: &str, new: &JsValue) {
+    let name = JsValue::from_str(name);
+    if js_sys::Reflect::get(el, &name).unwrap_throw() != *new {
+        js_sys::Reflect::set(el, &name, new).unwrap_throw();
+    }
 }
@@ -81,11 +81,8 @@ pub fn NoSsr(children: Children) -> View {
 #[component(inline_props)]
 pub fn NoHydrate(children: Children) -> View {
     if is_ssr!() {
-        let is_hydrating = IS_HYDRATING.replace(false);
-        let children = children.call();
-        IS_HYDRATING.set(is_hydrating);
-        children
-    } else if IS_HYDRATING.get() {
+        provide_context_in_new_scope(IsHydrating::new(false), move || children.call())
+    } else if is_hydrating() {
         view! {}
     } else {
         children.call()
@@ -0,0 +1,319 @@
+//! Delegated event handling. See [`delegate_events`].
+
+// Most of this is only used by `DomNode`, but is compiled in SSR mode as well.
+#![cfg_attr(any(not(target_arch = "wasm32"), sycamore_force_ssr), allow(dead_code))]
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+use js_sys::{Array, Function, Object, Reflect};
+use web_sys::{Event, Node};
+
+use crate::utils::ThreadBound;
+use crate::*;
+
+/// Events that bubble and can therefore be delegated. Handlers for all other events are attached
+/// directly to the element.
+const DELEGATED_EVENTS: &[&str] = &[
+    "beforeinput",
+    "click",
+    "contextmenu",
+    "dblclick",
+    "focusin",
+    "focusout",
+    "input",
+    "keydown",
+    "keyup",
+    "mousedown",
+    "mousemove",
+    "mouseout",
+    "mouseover",
+    "mouseup",
+    "pointerdown",
+    "pointermove",
+    "pointerout",
+    "pointerover",
+    "pointerup",
+    "touchend",
+    "touchmove",
+    "touchstart",
+];
+
+/// Property that is set on an event once it has been dispatched to the delegated handlers. An
+/// event can reach more than one listener if a portal is rendered inside of the render root.
+const DISPATCHED_KEY: &str = "__sycamore_dispatched";
+
+type Listener = Closure<dyn FnMut(Event)>;
+
+/// The node that a view is rendered into. This is provided by the render functions so that
+/// [`delegate_events`] knows where to attach the listeners.
+#[derive(Clone)]
+pub(crate) struct RenderRoot(pub web_sys::Node);
+
+/// A [`Portal`] that was rendered while events were delegated.
+struct PortalEntry {
+    /// The element that the portal is rendered into.
+    parent: Node,
+    /// The markers around the children of the portal.
+    start: Node,
+    end: Node,
+    /// The marker at the position of the portal in the view. Events that bubble out of the portal
+    /// continue from here.
+    host: Node,
+}
+
+struct DelegationState {
+    root: Node,
+    /// The nodes that the listeners are attached to, i.e. the render root and the parents of the
+    /// portals, along with the number of users of each node.
+    containers: Vec<(Node, usize)>,
+    portals: Vec<PortalEntry>,
+    /// One listener for every type of event that has a handler.
+    listeners: HashMap<Cow<'static, str>, Listener>,
+}
+
+impl DelegationState {
+    fn add_container(&mut self, node: Node) {
+        if let Some((_, count)) = self.containers.iter_mut().find(|(c, _)| c == &node) {
+            *count += 1;
+            return;
+        }
+        for (name, listener) in &self.listeners {
+            node.add_event_listener_with_callback(name, listener.as_ref().unchecked_ref())
+                .unwrap_throw();
+        }
+        self.containers.push((node, 1));
+    }
+
+    fn remove_container(&mut self, node: &Node) {
+        let Some(i) = self.containers.iter().position(|(c, _)| c == node) else {
+            return;
+        };
+        self.containers[i].1 -= 1;
+        if self.containers[i].1 == 0 {
+            let (node, _) = self.containers.swap_remove(i);
+            for (name, listener) in &self.listeners {
+                node.remove_event_listener_with_callback(name, listener.as_ref().unchecked_ref())
+                    .unwrap_throw();
+            }
+        }
+    }
+
+    /// Returns the nodes that an event that targets `target` bubbles through, or `None` if the
+    /// target is not rendered inside of the render root. Events bubble out of a portal into the
+    /// node that contains the portal.
+    fn event_path(&self, target: Node) -> Option<Vec<Node>> {
+        let mut path = Vec::new();
+        let mut node = target;
+        loop {
+            // Reaching the top of the document means that the node is not inside of the root.
+            let parent = node.parent_node()?;
+            if parent == self.root {
+                path.push(node);
+                return Some(path);
+            }
+            let portal = self.portals.iter().find(|portal| {
+                portal.parent == parent
+                    && portal.start.compare_document_position(&node)
+                        & Node::DOCUMENT_POSITION_FOLLOWING
+                        != 0
+                    && portal.end.compare_document_position(&node)
+                        & Node::DOCUMENT_POSITION_PRECEDING
+                        != 0
+            });
+            let next = portal.map_or(parent, |portal| portal.host.clone());
+            path.push(std::mem::replace(&mut node, next));
+        }
+    }
+}
+
+/// The state of event delegation. This is provided as a context by [`delegate_events`].
+#[derive(Clone)]
+pub(crate) struct EventDelegation(ThreadBound<Rc<RefCell<DelegationState>>>);
+
+impl EventDelegation {
+    /// Returns `true` if handlers for the event are delegated.
+    pub fn is_delegated(name: &str) -> bool {
+        DELEGATED_EVENTS.contains(&name)
+    }
+
+    /// Adds a delegated event handler to `node`. The handler is removed when the current scope is
+    /// cleaned up.
+    pub fn add_handler(&self, node: &Node, name: Cow<'static, str>, handler: Listener) {
+        let key = JsValue::from_str(&handler_key(&name));
+        self.listen(name);
+
+        // The array is always replaced instead of modified so that an ongoing dispatch is not
+        // affected.
+        let handlers = match Reflect::get(node, &key).unwrap_throw().dyn_into::<Array>() {
+            Ok(handlers) => handlers.slice(0, handlers.length()),
+            Err(_) => Array::new(),
+        };
+        handlers.push(handler.as_ref());
+        Reflect::set(node, &key, &handlers).unwrap_throw();
+
+        let node = node.clone();
+        let cleanup = ThreadBound::new(move || {
+            if let Ok(handlers) = Reflect::get(&node, &key).unwrap_throw().dyn_into::<Array>() {
+                let f: &JsValue = handler.as_ref();
+                let remaining = handlers.filter(&mut |other, _, _| &other != f);
+                Reflect::set(&node, &key, &remaining).unwrap_throw();
+            }
+            drop(handler);
+        });
+        on_cleanup(move || cleanup.into_inner()());
+    }
+
+    /// Makes sure that there is a listener for the event on all the containers.
+    fn listen(&self, name: Cow<'static, str>) {
+        let mut state = self.0.borrow_mut();
+        if state.listeners.contains_key(&name) {
+            return;
+        }
+        let weak = Rc::downgrade(&self.0);
+        let key = JsValue::from_str(&handler_key(&name));
+        let listener = Closure::wrap(
+            Box::new(move |ev: Event| dispatch(&weak, &key, ev)) as Box<dyn FnMut(Event)>
+        );
+        for (container, _) in &state.containers {
+            container
+                .add_event_listener_with_callback(&name, listener.as_ref().unchecked_ref())
+                .unwrap_throw();
+        }
+        state.listeners.insert(name, listener);
+    }
+
+    /// Registers a portal so that events bubble from the portal to `host`. The listeners are also
+    /// attached to the parent of the portal. The portal is unregistered when the current scope is
+    /// cleaned up.
+    pub fn add_portal(&self, parent: Node, start: Node, end: Node, host: Node) {
+        let mut state = self.0.borrow_mut();
+        state.add_container(parent.clone());
+        state.portals.push(PortalEntry {
+            parent,
+            start,
+            end,
+            host: host.clone(),
+        });
+
+        let this = self.clone();
+        on_cleanup(move || {
+            let mut state = this.0.borrow_mut();
+            if let Some(i) = state.portals.iter().position(|p| p.host == host) {
+                let portal = state.portals.swap_remove(i);
+                state.remove_container(&portal.parent);
+            }
+        });
+    }
+}
+
+/// The name of the property on a node that holds the delegated handlers for the event.
+fn handler_key(name: &str) -> String {
+    format!("__sycamore_{name}")
+}
+
+/// Calls the delegated handlers for the event, starting at the target and bubbling up to the
+/// render root.
+fn dispatch(state: &Weak<RefCell<DelegationState>>, key: &JsValue, ev: Event) {
+    let Some(state) = state.upgrade() else {
+        return;
+    };
+    let dispatched = JsValue::from_str(DISPATCHED_KEY);
+    if Reflect::has(&ev, &dispatched).unwrap_throw() {
+        return;
+    }
+    let Some(target) = ev
+        .target()
+        .and_then(|target| target.dyn_into::<Node>().ok())
+    else {
+        return;
+    };
+    // The borrow must be released before the handlers are called, since they might render new
+    // elements with delegated handlers.
+    let Some(path) = state.borrow().event_path(target) else {
+        return;
+    };
+    Reflect::set(&ev, &dispatched, &JsValue::TRUE).unwrap_throw();
+
+    let current_target = JsValue::from_str("currentTarget");
+    for node in path {
+        let Ok(handlers) = Reflect::get(&node, key).unwrap_throw().dyn_into::<Array>() else {
+            continue;
+        };
+        // `currentTarget` would otherwise be the container that the listener is attached to.
+        let descriptor = Object::new();
+        Reflect::set(&descriptor, &"configurable".into(), &JsValue::TRUE).unwrap_throw();
+        Reflect::set(&descriptor, &"value".into(), &node).unwrap_throw();
+        Object::define_property(ev.unchecked_ref::<Object>(), &current_target, &descriptor);
+
+        for handler in handlers.iter() {
+            handler
+                .unchecked_ref::<Function>()
+                .call1(&JsValue::UNDEFINED, &ev)
+                .unwrap_throw();
+        }
+        if ev.cancel_bubble() {
+            break;
+        }
+    }
+    Reflect::delete_property(ev.unchecked_ref::<Object>(), &current_target).unwrap_throw();
+}
+
+/// Delegates the event handlers of the current render to the render root.
+///
+/// Instead of attaching a listener to every element with an `on:` handler, a single listener is
+/// attached to the render root for every type of event. The handlers are stored on the elements
+/// and called by this listener, from the target of the event up to the root. This reduces the
+/// number of listeners significantly for views with many elements, such as large lists.
+///
+/// This must be called inside the view function that is passed to [`render`], [`render_to`],
+/// [`render_in_scope`], or the hydration functions. It only affects the handlers that are created
+/// afterwards in the same scope or its children. Does nothing in SSR mode.
+///
+/// Only events that bubble, such as `click` and `input`, are delegated. Handlers for other events
+/// are attached to the element as usual. [`Event::stop_propagation`] stops the event from reaching
+/// the delegated handlers of ancestor elements. Since the handlers are called by the listener of
+/// the root, they run after all the handlers that were attached directly to elements. Inside a
+/// delegated handler, `event.current_target()` is the element that the handler belongs to.
+///
+/// Events bubble out of a [`Portal`] into the element that contains the portal, rather than into
+/// the element that the portal is rendered into.
+///
+/// # Example
+/// ```no_run
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::delegate_events;
+/// # fn App() -> View { view! {} }
+/// sycamore::render(|| {
+///     delegate_events();
+///     view! { App {} }
+/// });
+/// ```
+pub fn delegate_events() {
+    if is_ssr!() {
+        return;
+    }
+    let Some(RenderRoot(root)) = try_use_context::<RenderRoot>() else {
+        panic!("`delegate_events` must be called inside of a render function");
+    };
+    let delegation = EventDelegation(ThreadBound::new(Rc::new(RefCell::new(DelegationState {
+        root: root.clone(),
+        containers: vec![(root, 1)],
+        portals: Vec::new(),
+        listeners: HashMap::new(),
+    }))));
+    provide_context(delegation.clone());
+    on_cleanup(move || {
+        let mut state = delegation.0.borrow_mut();
+        let state = &mut *state;
+        for (container, _) in state.containers.drain(..) {
+            for (name, listener) in &state.listeners {
+                container
+                    .remove_event_listener_with_callback(name, listener.as_ref().unchecked_ref())
+                    .unwrap_throw();
+            }
+        }
+        state.listeners.clear();
+    });
+}
@@ -1124,6 +1124,20 @@ pub trait GlobalAttributes: SetAttribute + Sized {
         self
     }
 
+    /// Add or remove the class `name` depending on `value`, without affecting the other classes
+    /// of the element. This is what is used by the `class:` directive.
+    fn class_toggle(mut self, name: &'static str, value: impl Into<MaybeDyn<bool>>) -> Self {
+        self.set_attribute(name, ClassToggle(value.into()));
+        self
+    }
+
+    /// Set the CSS property `name` in the inline style of the element. The property is removed if
+    /// `value` is `None`. This is what is used by the `style:` directive.
+    fn style_property(mut self, name: &'static str, value: impl Into<StringAttribute>) -> Self {
+        self.set_attribute(name, StyleProperty(value.into()));
+        self
+    }
+
     /// Set JS property `name` with `value`.
     fn prop(mut self, name: &'static str, value: impl Into<MaybeDyn<JsValue>>) -> Self {
         self.set_attribute(name, value.into());
@@ -1142,20 +1156,26 @@ pub trait GlobalAttributes: SetAttribute + Sized {
         self
     }
 
-    /// Set a two way binding with `name`.
-    fn bind<E: bind::BindDescriptor>(mut self, _: E, signal: Signal<E::ValueTy>) -> Self {
-        let scope = use_current_scope(); // Run handler inside the current scope.
-        let handler = move |ev: web_sys::Event| {
-            scope.run_in(|| {
-                let value =
-                    js_sys::Reflect::get(&ev.current_target().unwrap(), &E::TARGET_PROPERTY.into())
-                        .unwrap();
-                signal.set(E::CONVERT_FROM_JS(&value).expect("failed to convert value from js"));
-            })
-        };
-        self.set_event_handler(<E::Event as events::EventDescriptor>::NAME, handler);
+    /// Set a two way binding with `name`. See [`bind`] for the available bindings.
+    fn bind<E: bind::Bind<S>, S>(self, _: E, state: S) -> Self {
+        E::bind(self, state)
+    }
 
-        self.prop(E::TARGET_PROPERTY, move || signal.get_clone().into())
+    /// Attach an action to the element. This is what is used by the `use:` directive.
+    ///
+    /// An action is a function that is called with the element and `arg` when the element is
+    /// created, before it is inserted into the document. It runs in the scope of the element, so
+    /// it can create effects that track `arg` and register teardown logic with [`on_cleanup`].
+    /// Use [`on_mount`] inside the action for work that needs the element to be in the document.
+    /// Actions are not called in SSR mode.
+    fn r#use<T: Into<MaybeDyn<T>> + 'static>(
+        mut self,
+        action: impl FnOnce(web_sys::Element, MaybeDyn<T>) + 'static,
+        arg: impl Into<MaybeDyn<T>>,
+    ) -> Self {
+        let arg = arg.into();
+        self.set_attribute("use", WithElement(move |el| action(el, arg)));
+        self
     }
 }
 
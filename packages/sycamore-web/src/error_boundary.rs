@@ -0,0 +1,203 @@
+//! Definition of the [`ErrorBoundary`] component.
+
+use sycamore_macro::{component, view, Props};
+
+use crate::*;
+
+type FallbackFn = dyn Fn(CapturedError, ResetErrorBoundary) -> View;
+
+/// Props for [`ErrorBoundary`].
+#[derive(Props)]
+pub struct ErrorBoundaryProps {
+    /// The [`View`] to display instead of the children once an error was caught. Receives the
+    /// error and a handle that can be used to reset the boundary.
+    #[prop(setter(transform = |f: impl Fn(CapturedError, ResetErrorBoundary) -> View + 'static| Box::new(f) as Box<FallbackFn>))]
+    fallback: Box<FallbackFn>,
+    children: Children,
+}
+
+/// A handle for resetting an [`ErrorBoundary`]. This is passed to the fallback of the boundary.
+#[derive(Clone, Copy, Debug)]
+pub struct ResetErrorBoundary {
+    error: Signal<Option<CapturedError>>,
+    /// Incremented on every reset to create the children of the boundary again.
+    resets: Signal<u32>,
+}
+
+impl ResetErrorBoundary {
+    /// Clears the caught error and creates the children of the boundary again.
+    pub fn reset(self) {
+        batch(|| {
+            self.error.set(None);
+            self.resets.update(|resets| *resets += 1);
+        });
+    }
+}
+
+/// `ErrorBoundary` catches errors in its children and displays a fallback instead.
+///
+/// The following errors are caught:
+/// - Errors reported with [`report_error`], including components that return an
+///   `Err(_)` (see the `From<Result<View, E>>` implementation of [`View`]).
+/// - Panics while rendering the children.
+/// - Panics inside effects and memos that are created by the children.
+///
+/// Errors are always caught by the nearest `ErrorBoundary`. Resetting the boundary creates the
+/// children from scratch. Because of this, the children of an `ErrorBoundary` must not move out of
+/// the values that they capture (see [`ChildrenFn`]).
+///
+/// Panics can only be caught on targets that support unwinding. On `wasm32-unknown-unknown`,
+/// panics always abort.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::ErrorBoundary;
+/// #[component(inline_props)]
+/// fn Parse(input: &'static str) -> Result<View, std::num::ParseIntError> {
+///     let value: i32 = input.parse()?;
+///     Ok(view! { (value) })
+/// }
+///
+/// # fn App() -> View {
+/// view! {
+///     ErrorBoundary(fallback=|error, reset| view! {
+///         p { "Error: " (error.to_string()) }
+///         button(on:click=move |_| reset.reset()) { "Retry" }
+///     }) {
+///         Parse(input="not a number")
+///     }
+/// }
+/// # }
+/// ```
+#[component]
+pub fn ErrorBoundary(props: ErrorBoundaryProps) -> View {
+    let ErrorBoundaryProps { fallback, children } = props;
+    let error = create_signal(None);
+    let resets = create_signal(0);
+    let reset = ResetErrorBoundary { error, resets };
+
+    let children_view = match children.into_fn() {
+        // Re-runs in a new scope whenever the boundary is reset.
+        Ok(children) => View::from(move || {
+            resets.track();
+            untrack(|| create_children(error, || children.call()))
+        }),
+        // Children that were not created by the `view!` macro can only be created once.
+        Err(children) => create_children(error, move || children.call()),
+    };
+
+    view! {
+        Show(when=move || error.with(Option::is_none)) {
+            (children_view)
+        }
+        (move || error.get_clone().map(|e| fallback(e, reset)))
+    }
+}
+
+/// Creates the children of a boundary in a new scope that reports its errors to the boundary.
+fn create_children(error: Signal<Option<CapturedError>>, children: impl FnOnce() -> View) -> View {
+    let mut children_view = View::default();
+    let scope = create_child_scope(|| {
+        provide_error_handler(move |e| error.set(Some(e)));
+        match catch_panic(children) {
+            Ok(view) => children_view = view,
+            Err(e) => error.set(Some(e)),
+        }
+    });
+    // Clean up whatever was created before the children panicked.
+    if error.with_untracked(Option::is_some) {
+        scope.dispose();
+    }
+    children_view
+}
+
+#[cfg(test)]
+#[cfg_ssr]
+mod tests {
+    use std::fmt;
+
+    use expect_test::expect;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct MyError;
+
+    impl fmt::Display for MyError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("my error")
+        }
+    }
+
+    impl std::error::Error for MyError {}
+
+    #[component(inline_props)]
+    fn Fallible(fail: bool) -> Result<View, MyError> {
+        if fail {
+            Err(MyError)
+        } else {
+            Ok(view! { "ok" })
+        }
+    }
+
+    #[component]
+    fn Panicking() -> View {
+        panic!("render failed")
+    }
+
+    fn fallback(error: CapturedError, _reset: ResetErrorBoundary) -> View {
+        format!("error: {error}").into()
+    }
+
+    #[test]
+    fn renders_children_without_error() {
+        let res = render_to_string(|| {
+            view! {
+                ErrorBoundary(fallback=fallback) {
+                    Fallible(fail=false)
+                }
+            }
+        });
+        expect!["<!--/--><!--/-->ok<!--/--><!--/--><!--/--><!--/-->"].assert_eq(&res);
+    }
+
+    #[test]
+    fn renders_fallback_for_err() {
+        let res = render_to_string(|| {
+            view! {
+                ErrorBoundary(fallback=fallback) {
+                    Fallible(fail=true)
+                }
+            }
+        });
+        expect!["<!--/--><!--/--><!--/-->error: my error<!--/-->"].assert_eq(&res);
+    }
+
+    #[test]
+    fn renders_fallback_for_panic() {
+        let res = render_to_string(|| {
+            view! {
+                ErrorBoundary(fallback=fallback) {
+                    Panicking {}
+                }
+            }
+        });
+        expect!["<!--/--><!--/--><!--/-->error: render failed<!--/-->"].assert_eq(&res);
+    }
+
+    #[test]
+    fn nearest_boundary_catches_error() {
+        let res = render_to_string(|| {
+            view! {
+                ErrorBoundary(fallback=|_, _| "outer".into()) {
+                    "before "
+                    ErrorBoundary(fallback=fallback) {
+                        Fallible(fail=true)
+                    }
+                }
+            }
+        });
+        expect!["<!--/--><!--/-->before <!--/--><!--/--><!--/-->error: my error<!--/--><!--/--><!--/--><!--/--><!--/-->"].assert_eq(&res);
+    }
+}
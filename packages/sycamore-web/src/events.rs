@@ -1,8 +1,12 @@
 //! Definition for all the events that can be listened to.
 
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
 #[cfg(feature = "suspense")]
 use sycamore_futures::spawn_local_scoped;
-use wasm_bindgen::JsCast;
+use wasm_bindgen::{JsCast, JsValue};
 pub use web_sys::{
     AnimationEvent, BeforeUnloadEvent, CompositionEvent, DeviceMotionEvent, DeviceOrientationEvent,
     DragEvent, ErrorEvent, Event, FocusEvent, GamepadEvent, HashChangeEvent, InputEvent,
@@ -212,3 +216,285 @@ where
         spawn_local_scoped(self(event));
     }
 }
+
+/// A value that can be used as the `detail` of a [`CustomEvent`].
+///
+/// This is implemented for [`JsValue`], [`js_sys::Object`], and some primitive types. Implement
+/// this trait for your own types to use them as the payload of custom events.
+///
+/// # Example
+/// ```
+/// # use sycamore::web::events::EventDetail;
+/// # use sycamore::web::js_sys::{self, Reflect};
+/// # use sycamore::web::wasm_bindgen::JsValue;
+/// struct Selection {
+///     index: u32,
+/// }
+///
+/// impl EventDetail for Selection {
+///     fn into_js(self) -> JsValue {
+///         let detail = js_sys::Object::new();
+///         Reflect::set(&detail, &"index".into(), &self.index.into()).unwrap();
+///         detail.into()
+///     }
+///
+///     fn from_js(value: JsValue) -> Option<Self> {
+///         let index = Reflect::get(&value, &"index".into()).ok()?.as_f64()?;
+///         Some(Self { index: index as u32 })
+///     }
+/// }
+/// ```
+pub trait EventDetail: Sized {
+    /// Converts the value into the JS value that is stored in the event.
+    fn into_js(self) -> JsValue;
+    /// Converts the JS value that is stored in the event back. Returns `None` if the value has the
+    /// wrong type.
+    fn from_js(value: JsValue) -> Option<Self>;
+}
+
+impl EventDetail for JsValue {
+    fn into_js(self) -> JsValue {
+        self
+    }
+
+    fn from_js(value: JsValue) -> Option<Self> {
+        Some(value)
+    }
+}
+
+impl EventDetail for js_sys::Object {
+    fn into_js(self) -> JsValue {
+        self.into()
+    }
+
+    fn from_js(value: JsValue) -> Option<Self> {
+        value.dyn_into().ok()
+    }
+}
+
+impl EventDetail for () {
+    fn into_js(self) -> JsValue {
+        JsValue::NULL
+    }
+
+    fn from_js(_: JsValue) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl EventDetail for String {
+    fn into_js(self) -> JsValue {
+        self.into()
+    }
+
+    fn from_js(value: JsValue) -> Option<Self> {
+        value.as_string()
+    }
+}
+
+impl EventDetail for bool {
+    fn into_js(self) -> JsValue {
+        self.into()
+    }
+
+    fn from_js(value: JsValue) -> Option<Self> {
+        value.as_bool()
+    }
+}
+
+impl EventDetail for f64 {
+    fn into_js(self) -> JsValue {
+        self.into()
+    }
+
+    fn from_js(value: JsValue) -> Option<Self> {
+        value.as_f64()
+    }
+}
+
+impl<T: EventDetail> EventDetail for Option<T> {
+    fn into_js(self) -> JsValue {
+        self.map_or(JsValue::NULL, T::into_js)
+    }
+
+    fn from_js(value: JsValue) -> Option<Self> {
+        if value.is_null() || value.is_undefined() {
+            Some(None)
+        } else {
+            T::from_js(value).map(Some)
+        }
+    }
+}
+
+/// A [`web_sys::CustomEvent`] with a `detail` of type `D`.
+///
+/// This is the type of the events that are declared with [`custom_event!`](crate::custom_event).
+/// It derefs to [`web_sys::CustomEvent`], so all the usual methods of events are available.
+#[repr(transparent)]
+pub struct CustomEvent<D> {
+    event: web_sys::CustomEvent,
+    _phantom: PhantomData<fn() -> D>,
+}
+
+impl<D: EventDetail> CustomEvent<D> {
+    /// Creates a new event with the name `name` that bubbles and is cancelable.
+    pub fn new(name: &str, detail: D) -> Self {
+        let init = web_sys::CustomEventInit::new();
+        init.set_bubbles(true);
+        init.set_cancelable(true);
+        init.set_detail(&detail.into_js());
+        web_sys::CustomEvent::new_with_event_init_dict(name, &init)
+            .unwrap()
+            .unchecked_into()
+    }
+
+    /// Returns the detail of the event.
+    ///
+    /// # Panics
+    /// Panics if the detail cannot be converted to `D`, e.g. if an event with the same name but a
+    /// different detail was dispatched from JS.
+    #[track_caller]
+    pub fn detail(&self) -> D {
+        self.try_detail()
+            .expect("detail of custom event has the wrong type")
+    }
+
+    /// Returns the detail of the event, or `None` if it cannot be converted to `D`.
+    pub fn try_detail(&self) -> Option<D> {
+        D::from_js(self.event.detail())
+    }
+}
+
+impl<D> Deref for CustomEvent<D> {
+    type Target = web_sys::CustomEvent;
+
+    fn deref(&self) -> &Self::Target {
+        &self.event
+    }
+}
+
+impl<D> Clone for CustomEvent<D> {
+    fn clone(&self) -> Self {
+        Self {
+            event: self.event.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<D> fmt::Debug for CustomEvent<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CustomEvent").field(&self.event).finish()
+    }
+}
+
+impl<D> AsRef<JsValue> for CustomEvent<D> {
+    fn as_ref(&self) -> &JsValue {
+        self.event.as_ref()
+    }
+}
+
+impl<D> From<CustomEvent<D>> for JsValue {
+    fn from(event: CustomEvent<D>) -> Self {
+        event.event.into()
+    }
+}
+
+impl<D> From<CustomEvent<D>> for Event {
+    fn from(event: CustomEvent<D>) -> Self {
+        event.event.into()
+    }
+}
+
+impl<D> JsCast for CustomEvent<D> {
+    fn instanceof(val: &JsValue) -> bool {
+        web_sys::CustomEvent::instanceof(val)
+    }
+
+    fn unchecked_from_js(val: JsValue) -> Self {
+        Self {
+            event: val.unchecked_into(),
+            _phantom: PhantomData,
+        }
+    }
+
+    fn unchecked_from_js_ref(val: &JsValue) -> &Self {
+        // SAFETY: `CustomEvent<D>` is a transparent wrapper around `web_sys::CustomEvent`, which
+        // is itself a transparent wrapper around `JsValue`.
+        unsafe { &*(val as *const JsValue as *const Self) }
+    }
+}
+
+/// Returns whether `ident` is the name of the event `name` with the hyphens replaced by underscores.
+/// This is used by [`custom_event!`](crate::custom_event) so that the `view!` macro can find the
+/// descriptor of the event.
+#[doc(hidden)]
+pub const fn is_descriptor_name(ident: &str, name: &str) -> bool {
+    let (ident, name) = (ident.as_bytes(), name.as_bytes());
+    if ident.len() != name.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < ident.len() {
+        let expected = if name[i] == b'-' { b'_' } else { name[i] };
+        if ident[i] != expected {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Declares custom events with a typed `detail`.
+///
+/// Every declaration creates an event descriptor, just like the ones in [`events`](crate::events)
+/// for the built-in events. The handlers of the event receive a [`CustomEvent<D>`], where `D` is
+/// the type of the detail, which must implement [`EventDetail`].
+///
+/// Custom events can be used with the `on:` directive in the `view!` macro. The hyphens in the name
+/// of the event are replaced with underscores to find the descriptor, which must be in scope. The
+/// identifier of the descriptor must therefore be the name of the event with the hyphens replaced
+/// by underscores, which is checked at compile time. Built-in events take precedence over
+/// descriptors in scope, so a custom event cannot have the name of a built-in event. Use
+/// [`NodeRef::dispatch`](crate::NodeRef::dispatch) to dispatch the events.
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::custom_event;
+/// custom_event! {
+///     /// Emitted when the value of the picker changes.
+///     pub value_changed: String = "value-changed";
+///     /// Emitted when the picker is closed.
+///     pub picker_closed: () = "picker-closed";
+/// }
+///
+/// # fn App() -> View {
+/// view! {
+///     color-picker(on:value-changed=|ev: CustomEvent<String>| console_log!("{}", ev.detail()))
+/// }
+/// # }
+/// # use sycamore::web::events::CustomEvent;
+/// ```
+#[macro_export]
+macro_rules! custom_event {
+    ($($(#[$attr:meta])* $vis:vis $name:ident: $detail:ty = $event:literal;)*) => {
+        $(
+            $(#[$attr])*
+            #[allow(non_camel_case_types)]
+            #[derive(Clone, Copy, Debug)]
+            $vis struct $name;
+            impl $crate::events::EventDescriptor for $name {
+                type EventTy = $crate::events::CustomEvent<$detail>;
+                const NAME: &'static str = $event;
+            }
+            const _: () = ::std::assert!(
+                $crate::events::is_descriptor_name(::std::stringify!($name), $event),
+                ::std::concat!(
+                    "the descriptor of the custom event \"", $event, "\" must be named after the ",
+                    "event with the hyphens replaced by underscores"
+                ),
+            );
+        )*
+    };
+}
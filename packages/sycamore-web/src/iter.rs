@@ -18,7 +18,7 @@ use crate::*;
 #[derive(Props)]
 pub struct KeyedProps<T, K, U, List, F, Key>
 where
-    List: Into<MaybeDyn<Vec<T>>> + 'static,
+    List: Into<ListSource<T>> + 'static,
     F: Fn(T) -> U + 'static,
     Key: Fn(&T) -> K + 'static,
     T: 'static,
@@ -36,6 +36,9 @@ where
 /// Using this will minimize re-renders instead of re-rendering every view node on every
 /// state change.
 ///
+/// If `list` is a [`SignalVec`], the changes made to it are applied directly to the DOM instead of
+/// diffing the whole list.
+///
 /// For non keyed iteration, see [`Indexed`].
 ///
 /// # Example
@@ -72,12 +75,12 @@ where
 #[component]
 pub fn Keyed<T, K, U, List, F, Key>(props: KeyedProps<T, K, U, List, F, Key>) -> View
 where
-    T: PartialEq + Clone + 'static,
-    K: Hash + Eq + 'static,
+    T: PartialEq + Clone + MaybeSend + 'static,
+    K: Hash + Eq + MaybeSend + 'static,
     U: Into<View>,
-    List: Into<MaybeDyn<Vec<T>>> + 'static,
-    F: Fn(T) -> U + 'static,
-    Key: Fn(&T) -> K + 'static,
+    List: Into<ListSource<T>> + 'static,
+    F: Fn(T) -> U + MaybeSend + 'static,
+    Key: Fn(&T) -> K + MaybeSend + 'static,
 {
     let KeyedProps {
         list, view, key, ..
@@ -87,52 +90,17 @@ where
         // In SSR mode, just create a static view.
         View::from(
             list.into()
-                .evaluate()
+                .get_clone()
                 .into_iter()
                 .map(|x| view(x).into())
                 .collect::<Vec<_>>(),
         )
     } else {
-        let start = HtmlNode::create_marker_node();
-        let start_node = start.as_web_sys().clone();
-        let end = HtmlNode::create_marker_node();
-        let end_node = end.as_web_sys().clone();
-
-        // Run the initial function in the outer scope, not the effect scope.
-        // This is because we might want to create signals and other things managed by the reactive
-        // tree that will be used in furture triggers of this effect. These things must therefore
-        // live as long as the effect.
-        let scope = use_current_scope();
-        create_effect_initial(move || {
-            scope.run_in(move || {
-                let nodes = map_keyed(list, move |x| view(x).into().as_web_sys(), key);
-                // Flatten nodes.
-                let flattened = nodes.map(|x| x.iter().flatten().cloned().collect::<Vec<_>>());
-                let view = flattened.with(|x| {
-                    View::from_nodes(
-                        x.iter()
-                            .map(|x| HtmlNode::from_web_sys(x.clone()))
-                            .collect(),
-                    )
-                });
-                (
-                    Box::new(move || {
-                        // Get all nodes between start and end and reconcile with new nodes.
-                        let mut new = flattened.get_clone();
-                        let mut old = utils::get_nodes_between(&start_node, &end_node);
-                        // We must include the end node in case `old` is empty (precondition for
-                        // reconcile_fragments).
-                        new.push(end_node.clone());
-                        old.push(end_node.clone());
-
-                        if let Some(parent) = start_node.parent_node() {
-                            reconcile_fragments(&parent, &mut old, &new);
-                        }
-                    }) as Box<dyn FnMut()>,
-                    (start, view, end).into(),
-                )
-            })
-        })
+        let map_fn = move |x| view(x).into().as_web_sys();
+        match list.into() {
+            ListSource::Dyn(list) => render_mapped(map_keyed(list, map_fn, key)),
+            ListSource::Vec(list) => render_signal_vec(list.map_keyed(map_fn, key)),
+        }
     }
 }
 
@@ -140,7 +108,7 @@ where
 #[derive(Props)]
 pub struct IndexedProps<T, U, List, F>
 where
-    List: Into<MaybeDyn<Vec<T>>> + 'static,
+    List: Into<ListSource<T>> + 'static,
     F: Fn(T) -> U + 'static,
     T: 'static,
 {
@@ -156,6 +124,9 @@ where
 /// [`View`]s. Using this will minimize re-renders instead of re-rendering every single
 /// node on every state change.
 ///
+/// If `list` is a [`SignalVec`], the changes made to it are applied directly to the DOM instead of
+/// diffing the whole list.
+///
 /// For keyed iteration, see [`Keyed`].
 ///
 /// # Example
@@ -178,10 +149,10 @@ where
 #[component]
 pub fn Indexed<T, U, List, F>(props: IndexedProps<T, U, List, F>) -> View
 where
-    T: PartialEq + Clone + 'static,
+    T: PartialEq + Clone + MaybeSend + 'static,
     U: Into<View>,
-    List: Into<MaybeDyn<Vec<T>>> + 'static,
-    F: Fn(T) -> U + 'static,
+    List: Into<ListSource<T>> + 'static,
+    F: Fn(T) -> U + MaybeSend + 'static,
 {
     let IndexedProps { list, view, .. } = props;
 
@@ -189,52 +160,177 @@ where
         // In SSR mode, just create a static view.
         View::from(
             list.into()
-                .evaluate()
+                .get_clone()
                 .into_iter()
                 .map(|x| view(x).into())
                 .collect::<Vec<_>>(),
         )
     } else {
-        let start = HtmlNode::create_marker_node();
-        let start_node = start.as_web_sys().clone();
-        let end = HtmlNode::create_marker_node();
-        let end_node = end.as_web_sys().clone();
-
-        // Run the initial function in the outer scope, not the effect scope.
-        // This is because we might want to create signals and other things managed by the reactive
-        // tree that will be used in furture triggers of this effect. These things must therefore
-        // live as long as the effect.
-        let scope = use_current_scope();
-        create_effect_initial(move || {
-            scope.run_in(move || {
-                let nodes = map_indexed(list, move |x| view(x).into().as_web_sys());
-                // Flatten nodes.
-                let flattened = nodes.map(|x| x.iter().flatten().cloned().collect::<Vec<_>>());
-                let view = flattened.with(|x| {
-                    View::from_nodes(
-                        x.iter()
-                            .map(|x| HtmlNode::from_web_sys(x.clone()))
-                            .collect(),
-                    )
-                });
-                (
-                    Box::new(move || {
-                        // Get all nodes between start and end and reconcile with new nodes.
-                        let mut new = flattened.get_clone();
-                        let mut old = utils::get_nodes_between(&start_node, &end_node);
-                        // We must include the end node in case `old` is empty (precondition for
-                        // reconcile_fragments).
-                        new.push(end_node.clone());
-                        old.push(end_node.clone());
-
-                        if let Some(parent) = start_node.parent_node() {
-                            reconcile_fragments(&parent, &mut old, &new);
-                        }
-                    }) as Box<dyn FnMut()>,
-                    (start, view, end).into(),
-                )
-            })
-        })
+        let map_fn = move |x| view(x).into().as_web_sys();
+        match list.into() {
+            ListSource::Dyn(list) => render_mapped(map_indexed(list, map_fn)),
+            ListSource::Vec(list) => render_signal_vec(list.map_indexed(map_fn)),
+        }
+    }
+}
+
+/// Renders the nodes of a mapped `Vec` and reconciles them with the DOM whenever it changes.
+fn render_mapped(nodes: ReadSignal<Vec<Vec<web_sys::Node>>>) -> View {
+    let start = HtmlNode::create_marker_node();
+    let start_node = start.as_web_sys().clone();
+    let end = HtmlNode::create_marker_node();
+    let end_node = end.as_web_sys().clone();
+
+    // Flatten nodes.
+    let flattened = nodes.map(|x| x.iter().flatten().cloned().collect::<Vec<_>>());
+    let view = flattened.with_untracked(|x| {
+        View::from_nodes(
+            x.iter()
+                .map(|x| HtmlNode::from_web_sys(x.clone()))
+                .collect(),
+        )
+    });
+    let mut is_initial = true;
+    create_effect(move || {
+        // Get all nodes between start and end and reconcile with new nodes.
+        let mut new = flattened.get_clone();
+        // The nodes are already in the view the first time.
+        if std::mem::take(&mut is_initial) {
+            return;
+        }
+        let mut old = utils::get_nodes_between(&start_node, &end_node);
+        // We must include the end node in case `old` is empty (precondition for
+        // reconcile_fragments).
+        new.push(end_node.clone());
+        old.push(end_node.clone());
+
+        if let Some(parent) = start_node.parent_node() {
+            reconcile_fragments(&parent, &mut old, &new);
+        }
+    });
+    (start, view, end).into()
+}
+
+/// Renders the nodes of a mapped [`ReadSignalVec`] and applies its diffs directly to the DOM,
+/// without diffing the nodes.
+fn render_signal_vec(mapped: ReadSignalVec<Vec<web_sys::Node>>) -> View {
+    let start = HtmlNode::create_marker_node();
+    let start_node = start.as_web_sys().clone();
+    let end = HtmlNode::create_marker_node();
+    let end_node = end.as_web_sys().clone();
+
+    let diffs = mapped.subscribe();
+    let mut groups = mapped.get_clone_untracked();
+    let view = View::from_nodes(
+        groups
+            .iter()
+            .flatten()
+            .map(|x| HtmlNode::from_web_sys(x.clone()))
+            .collect(),
+    );
+    // Whether diffs were applied while the nodes were not mounted, in which case the DOM does not
+    // match the groups anymore.
+    let mut diverged = false;
+    create_effect(move || {
+        let diffs = diffs.take();
+        let parent = start_node.parent_node();
+        if parent.is_some() && !diverged {
+            for diff in diffs {
+                apply_vec_diff(parent.as_ref(), &end_node, &mut groups, diff);
+            }
+            return;
+        }
+        for diff in diffs {
+            apply_vec_diff(None, &end_node, &mut groups, diff);
+        }
+        diverged = parent.is_none();
+        if let Some(parent) = parent {
+            // Reconcile the nodes that were mounted with the current groups.
+            let mut old = utils::get_nodes_between(&start_node, &end_node);
+            let mut new = groups.iter().flatten().cloned().collect::<Vec<_>>();
+            // We must include the end node in case `old` is empty (precondition for
+            // reconcile_fragments).
+            old.push(end_node.clone());
+            new.push(end_node.clone());
+            reconcile_fragments(&parent, &mut old, &new);
+        }
+    });
+    (start, view, end).into()
+}
+
+/// Returns the first node in `groups`, or `None` if all the groups are empty.
+fn first_node(groups: &[Vec<web_sys::Node>]) -> Option<&web_sys::Node> {
+    groups.iter().find_map(|nodes| nodes.first())
+}
+
+/// Applies a diff to the groups of nodes of every item, and to the DOM if there is a `parent`.
+/// `end` is the marker node after the last group.
+fn apply_vec_diff(
+    parent: Option<&web_sys::Node>,
+    end: &web_sys::Node,
+    groups: &mut Vec<Vec<web_sys::Node>>,
+    diff: VecDiff<Vec<web_sys::Node>>,
+) {
+    let insert = |nodes: &[web_sys::Node], before: &web_sys::Node| {
+        if let Some(parent) = parent {
+            for node in nodes {
+                parent.insert_before(node, Some(before)).unwrap();
+            }
+        }
+    };
+    let remove = |nodes: &[web_sys::Node]| {
+        if let Some(parent) = parent {
+            for node in nodes {
+                parent.remove_child(node).unwrap();
+            }
+        }
+    };
+    match diff {
+        VecDiff::Push(nodes) => {
+            insert(&nodes, end);
+            groups.push(nodes);
+        }
+        VecDiff::Pop => remove(&groups.pop().unwrap()),
+        VecDiff::Insert { index, value } => {
+            let before = first_node(&groups[index..]).unwrap_or(end).clone();
+            insert(&value, &before);
+            groups.insert(index, value);
+        }
+        VecDiff::Remove { index } => remove(&groups.remove(index)),
+        VecDiff::Swap { a, b } => {
+            let (a, b) = (a.min(b), a.max(b));
+            let after_b = first_node(&groups[b + 1..]).unwrap_or(end).clone();
+            // If all the groups from a to b are empty, the nodes of b are already in place.
+            if let Some(before_a) = first_node(&groups[a..b]).cloned() {
+                insert(&groups[b], &before_a);
+            }
+            insert(&groups[a], &after_b);
+            groups.swap(a, b);
+        }
+        VecDiff::Set { index, value } => {
+            let before = first_node(&groups[index + 1..]).unwrap_or(end).clone();
+            remove(&groups[index]);
+            insert(&value, &before);
+            groups[index] = value;
+        }
+        VecDiff::Clear => {
+            for nodes in groups.drain(..) {
+                remove(&nodes);
+            }
+        }
+        VecDiff::Replace(new) => {
+            if let Some(parent) = parent {
+                // Nodes of items that are still present are reused, so reconcile them.
+                let mut old = groups.iter().flatten().cloned().collect::<Vec<_>>();
+                let mut new = new.iter().flatten().cloned().collect::<Vec<_>>();
+                // We must include the end node in case `old` is empty (precondition for
+                // reconcile_fragments).
+                old.push(end.clone());
+                new.push(end.clone());
+                reconcile_fragments(parent, &mut old, &new);
+            }
+            *groups = new;
+        }
     }
 }
 
@@ -10,6 +10,11 @@
 //!
 //! - `suspense` - Enables suspense support.
 //!
+//! - `sync` - Enables the `sync` feature of `sycamore-reactive`, which makes reactive roots and
+//!   signals `Send + Sync`. The server side rendering functions that wait for suspense then render
+//!   on a blocking thread of the tokio runtime, so their futures and streams are `Send` and no
+//!   `tokio::task::LocalSet` is needed.
+//!
 //! - `wasm-bindgen-interning` (_default_) - Enables interning for `wasm-bindgen` strings. This
 //!   improves performance at a slight cost in binary size. If you want to minimize the size of the
 //!   resulting `.wasm` binary, you might want to disable this.
@@ -27,7 +32,8 @@
 
 use std::borrow::Cow;
 use std::cell::Cell;
-use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use sycamore_macro::*;
 use sycamore_reactive::*;
@@ -40,7 +46,9 @@ pub mod utils;
 
 mod attributes;
 mod components;
+mod delegation;
 mod elements;
+mod error_boundary;
 mod iter;
 mod macros;
 mod node;
@@ -48,6 +56,8 @@ mod noderef;
 mod portal;
 #[cfg(feature = "suspense")]
 mod resource;
+#[cfg(feature = "suspense")]
+mod ssg;
 mod stable_counter;
 #[cfg(feature = "suspense")]
 mod suspense;
@@ -56,13 +66,17 @@ pub(crate) mod view;
 
 pub use self::attributes::*;
 pub use self::components::*;
+pub use self::delegation::*;
 pub use self::elements::*;
+pub use self::error_boundary::*;
 pub use self::iter::*;
 pub use self::node::*;
 pub use self::noderef::*;
 pub use self::portal::*;
 #[cfg(feature = "suspense")]
 pub use self::resource::*;
+#[cfg(feature = "suspense")]
+pub use self::ssg::*;
 pub use self::stable_counter::*;
 #[cfg(feature = "suspense")]
 pub use self::suspense::*;
@@ -86,9 +100,9 @@ pub mod rt {
     pub use crate::{bind, custom_element, tags, View};
 }
 
-/// Re-export of `js-sys` and `wasm-bindgen` for convenience.
+/// Re-export of `js-sys`, `wasm-bindgen` and `web-sys` for convenience.
 //#[doc(no_inline)]
-pub use {js_sys, wasm_bindgen};
+pub use {js_sys, wasm_bindgen, web_sys};
 
 /// A macro that expands to whether we are in SSR mode or not.
 ///
@@ -181,8 +195,12 @@ pub type HtmlNode = HydrateNode;
 /// type.
 pub type Children = sycamore_core::Children<View>;
 
+/// A type alias for [`ChildrenFn`](sycamore_core::ChildrenFn) automatically selecting the correct
+/// node type.
+pub type ChildrenFn = sycamore_core::ChildrenFn<View>;
+
 /// Create a new effect, but only if we are not in SSR mode.
-pub fn create_client_effect(f: impl FnMut() + 'static) {
+pub fn create_client_effect(f: impl FnMut() + MaybeSend + 'static) {
     if is_not_ssr!() {
         create_effect(f);
     }
@@ -198,15 +216,15 @@ pub fn create_client_effect(f: impl FnMut() + 'static) {
 /// point (when there is an `.await`).
 pub fn on_mount(f: impl FnOnce() + 'static) {
     if cfg!(target_arch = "wasm32") {
-        let is_alive = Rc::new(Cell::new(true));
+        let is_alive = Arc::new(AtomicBool::new(true));
         on_cleanup({
-            let is_alive = Rc::clone(&is_alive);
-            move || is_alive.set(false)
+            let is_alive = Arc::clone(&is_alive);
+            move || is_alive.store(false, Ordering::Relaxed)
         });
 
         let scope = use_current_scope();
         let cb = move || {
-            if is_alive.get() {
+            if is_alive.load(Ordering::Relaxed) {
                 scope.run_in(f);
             }
         };
@@ -1,4 +1,6 @@
 use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::rc::Rc;
 
 use wasm_bindgen::intern;
 
@@ -7,6 +9,68 @@ use crate::*;
 /// View backend for rendering to the browser DOM.
 pub struct DomNode {
     pub(crate) raw: web_sys::Node,
+    /// The classes that are toggled by `class:` directives. This is only created once it is
+    /// needed.
+    class_toggles: Option<ClassToggles>,
+}
+
+/// The classes that are toggled on an element by `class:` directives, along with whether they are
+/// active. Setting the `class` attribute overwrites these, so they are re-applied afterwards.
+#[derive(Clone, Default)]
+pub(crate) struct ClassToggles(Rc<RefCell<Vec<ClassToggle>>>);
+
+struct ClassToggle {
+    name: Cow<'static, str>,
+    active: bool,
+}
+
+impl ClassToggles {
+    /// Adds a class toggle and returns its index.
+    pub fn add(&self, name: Cow<'static, str>, active: bool) -> usize {
+        let mut toggles = self.0.borrow_mut();
+        toggles.push(ClassToggle { name, active });
+        toggles.len() - 1
+    }
+
+    /// Sets whether the class toggle at `index` is active.
+    pub fn set(&self, index: usize, active: bool) {
+        self.0.borrow_mut()[index].active = active;
+    }
+
+    /// Adds the classes of the active toggles to the element and removes the classes of the
+    /// inactive ones.
+    pub fn apply(&self, el: &web_sys::Element) {
+        let class_list = el.class_list();
+        for toggle in self.0.borrow().iter() {
+            class_list
+                .toggle_with_force(&toggle.name, toggle.active)
+                .unwrap();
+        }
+    }
+}
+
+impl DomNode {
+    /// Returns the class toggles of this element, creating them if needed.
+    pub(crate) fn class_toggles(&mut self) -> ClassToggles {
+        self.class_toggles
+            .get_or_insert_with(Default::default)
+            .clone()
+    }
+
+    /// Sets the `class` attribute and re-applies the class toggles, which it overwrites.
+    pub(crate) fn set_class_attribute(
+        el: &web_sys::Element,
+        value: Option<&str>,
+        toggles: Option<&ClassToggles>,
+    ) {
+        match value {
+            Some(value) => el.set_attribute("class", value).unwrap(),
+            None => el.remove_attribute("class").unwrap(),
+        }
+        if let Some(toggles) = toggles {
+            toggles.apply(el);
+        }
+    }
 }
 
 impl From<DomNode> for View<DomNode> {
@@ -92,6 +156,7 @@ impl ViewHtmlNode for DomNode {
     fn create_element(tag: Cow<'static, str>) -> Self {
         Self {
             raw: document().create_element(intern(&tag)).unwrap().into(),
+            class_toggles: None,
         }
     }
 
@@ -101,24 +166,40 @@ impl ViewHtmlNode for DomNode {
                 .create_element_ns(Some(namespace), intern(&tag))
                 .unwrap()
                 .into(),
+            class_toggles: None,
         }
     }
 
     fn create_text_node(text: Cow<'static, str>) -> Self {
         Self {
             raw: document().create_text_node(&text).into(),
+            class_toggles: None,
         }
     }
 
     fn create_marker_node() -> Self {
         Self {
             raw: document().create_comment("").into(),
+            class_toggles: None,
         }
     }
 
     fn set_attribute(&mut self, name: Cow<'static, str>, value: StringAttribute) {
         // FIXME: use setAttributeNS if SVG
-        if let Some(value) = value.as_static() {
+        if name == "class" {
+            let node = self.raw.clone().unchecked_into::<web_sys::Element>();
+            if let Some(value) = value.as_static() {
+                if value.is_some() {
+                    Self::set_class_attribute(&node, value.as_deref(), self.class_toggles.as_ref());
+                }
+            } else {
+                let toggles = self.class_toggles();
+                create_effect(move || {
+                    let value = value.get_clone();
+                    Self::set_class_attribute(&node, value.as_deref(), Some(&toggles));
+                });
+            }
+        } else if let Some(value) = value.as_static() {
             if let Some(value) = value {
                 self.raw
                     .unchecked_ref::<web_sys::Element>()
@@ -155,16 +236,54 @@ impl ViewHtmlNode for DomNode {
         }
     }
 
+    fn set_class_toggle(&mut self, name: Cow<'static, str>, value: BoolAttribute) {
+        let class_list = self.raw.unchecked_ref::<web_sys::Element>().class_list();
+        let toggles = self.class_toggles();
+        if let Some(value) = value.as_static() {
+            // Inactive toggles are recorded as well, so that they also remove the class from the
+            // `class` attribute, like in SSR.
+            class_list.toggle_with_force(&name, *value).unwrap();
+            toggles.add(name, *value);
+        } else {
+            let index = toggles.add(name.clone(), false);
+            create_effect(move || {
+                let active = value.get();
+                toggles.set(index, active);
+                class_list.toggle_with_force(&name, active).unwrap();
+            });
+        }
+    }
+
+    fn set_style_property(&mut self, name: Cow<'static, str>, value: StringAttribute) {
+        // `style` is also available on SVG elements, which are not `HtmlElement`s.
+        let style = self.raw.unchecked_ref::<web_sys::HtmlElement>().style();
+        if let Some(value) = value.as_static() {
+            if let Some(value) = value {
+                style.set_property(&name, value).unwrap();
+            }
+        } else {
+            create_effect(move || match value.get_clone() {
+                Some(value) => style.set_property(&name, &value).unwrap(),
+                None => {
+                    style.remove_property(&name).unwrap();
+                }
+            });
+        }
+    }
+
     fn set_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<JsValue>) {
         if let Some(value) = value.as_static() {
             assert!(js_sys::Reflect::set(&self.raw, &name.as_ref().into(), value).unwrap_throw())
         } else {
             let node = self.raw.clone().unchecked_into::<web_sys::Element>();
             create_effect(move || {
-                assert!(
-                    js_sys::Reflect::set(&node, &name.as_ref().into(), &value.get_clone())
-                        .unwrap_throw()
-                )
+                // Setting some properties, such as `textContent`, moves the cursor even if the
+                // value is the same, so only set the property if it changed.
+                let name = JsValue::from_str(&name);
+                let value = value.get_clone();
+                if js_sys::Reflect::get(&node, &name).unwrap_throw() != value {
+                    assert!(js_sys::Reflect::set(&node, &name, &value).unwrap_throw())
+                }
             });
         }
     }
@@ -175,10 +294,17 @@ impl ViewHtmlNode for DomNode {
         handler: impl FnMut(web_sys::Event) + 'static,
     ) {
         let cb = Closure::wrap(Box::new(handler) as Box<dyn FnMut(_)>);
-        self.raw
-            .add_event_listener_with_callback(&name, cb.as_ref().unchecked_ref())
-            .unwrap();
-        on_cleanup(|| drop(cb));
+        match try_use_context::<EventDelegation>() {
+            Some(delegation) if EventDelegation::is_delegated(&name) => {
+                delegation.add_handler(&self.raw, name, cb);
+            }
+            _ => {
+                self.raw
+                    .add_event_listener_with_callback(&name, cb.as_ref().unchecked_ref())
+                    .unwrap();
+                on_cleanup(|| drop(cb));
+            }
+        }
     }
 
     fn set_inner_html(&mut self, inner_html: Cow<'static, str>) {
@@ -192,6 +318,9 @@ impl ViewHtmlNode for DomNode {
     }
 
     fn from_web_sys(node: web_sys::Node) -> Self {
-        Self { raw: node }
+        Self {
+            raw: node,
+            class_toggles: None,
+        }
     }
 }
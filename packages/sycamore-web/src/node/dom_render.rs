@@ -29,8 +29,11 @@ pub fn render_in_scope(view: impl FnOnce() -> View, parent: &web_sys::Node) {
     if is_ssr!() {
         panic!("`render_in_scope` is not available in SSR mode");
     } else {
-        IS_HYDRATING.set(false);
-        let nodes = view().nodes;
+        let nodes = provide_context_in_new_scope(RenderRoot(parent.clone()), || {
+            provide_context(IsHydrating::new(false));
+            view()
+        })
+        .nodes;
         for node in nodes {
             parent.append_child(node.as_web_sys()).unwrap();
         }
@@ -104,9 +107,11 @@ pub fn hydrate_in_scope(view: impl FnOnce() -> View, parent: &web_sys::Node) {
             }
         });
 
-        IS_HYDRATING.set(true);
+        let is_hydrating = IsHydrating::new(true);
+        provide_context(is_hydrating);
         provide_context(mode);
         provide_context(HydrationRegistry::new());
+        provide_context(RenderRoot(parent.clone()));
         let nodes = view().nodes;
         // We need to append `nodes` to the `parent` so that the top level nodes also get properly
         // hydrated.
@@ -115,13 +120,13 @@ pub fn hydrate_in_scope(view: impl FnOnce() -> View, parent: &web_sys::Node) {
             parent.append_child(node);
         }
 
-        // Wait until suspense is resolved before setting `IS_HYDRATING` to `false`.
+        // Wait until suspense is resolved before leaving hydration mode.
         #[cfg(not(feature = "suspense"))]
-        IS_HYDRATING.set(false);
+        is_hydrating.set(false);
         #[cfg(feature = "suspense")]
-        create_effect(|| {
-            if IS_HYDRATING.get() && !sycamore_futures::use_is_loading_global() {
-                IS_HYDRATING.set(false);
+        create_effect(move || {
+            if is_hydrating.get() && !sycamore_futures::use_is_loading_global() {
+                is_hydrating.set(false);
             }
         });
     }
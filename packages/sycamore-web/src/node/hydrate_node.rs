@@ -57,7 +57,7 @@ impl From<HydrateNode> for View<HydrateNode> {
 
 impl ViewNode for HydrateNode {
     fn append_child(&mut self, child: Self) {
-        if IS_HYDRATING.get() {
+        if is_hydrating() {
             match child.0 {
                 NodeState::Hydrated(_) => {
                     // Noop for hydration since node is already in right place.
@@ -137,7 +137,7 @@ fn check_node(node: &web_sys::Node, tag: &str, key: HydrationKey) {
 
 impl ViewHtmlNode for HydrateNode {
     fn create_element(tag: Cow<'static, str>) -> Self {
-        if IS_HYDRATING.get() {
+        if is_hydrating() {
             let reg: HydrationRegistry = use_context();
             let key = reg.next_key();
             let node = HYDRATE_NODES
@@ -151,7 +151,7 @@ impl ViewHtmlNode for HydrateNode {
     }
 
     fn create_element_ns(namespace: &'static str, tag: Cow<'static, str>) -> Self {
-        if IS_HYDRATING.get() {
+        if is_hydrating() {
             let reg: HydrationRegistry = use_context();
             let key = reg.next_key();
             let node = HYDRATE_NODES
@@ -172,7 +172,7 @@ impl ViewHtmlNode for HydrateNode {
     }
 
     fn create_text_node(text: Cow<'static, str>) -> Self {
-        if IS_HYDRATING.get() {
+        if is_hydrating() {
             Self(NodeState::TextStatic)
         } else {
             Self(NodeState::Hydrated(DomNode::create_text_node(text)))
@@ -180,7 +180,7 @@ impl ViewHtmlNode for HydrateNode {
     }
 
     fn create_dynamic_text_node(text: Cow<'static, str>) -> Self {
-        if IS_HYDRATING.get() {
+        if is_hydrating() {
             Self(NodeState::TextDynamic(DomNode::create_text_node(text)))
         } else {
             Self(NodeState::Hydrated(DomNode::create_text_node(text)))
@@ -194,19 +194,26 @@ impl ViewHtmlNode for HydrateNode {
 
     fn set_attribute(&mut self, name: Cow<'static, str>, value: StringAttribute) {
         // FIXME: use setAttributeNS if SVG
-        if IS_HYDRATING.get() {
+        if is_hydrating() {
             // Noop if value is static since attributes are already set.
             if value.as_static().is_none() {
                 let node = self
                     .as_web_sys()
                     .clone()
                     .unchecked_into::<web_sys::Element>();
+                let toggles = (name == "class").then(|| self.0.unwrap_mut().class_toggles());
                 create_effect_initial(move || {
                     let _ = value.track(); // Track dependencies of value.
                     (
-                        Box::new(move || match value.get_clone() {
-                            Some(value) => node.set_attribute(&name, &value).unwrap(),
-                            None => node.remove_attribute(&name).unwrap(),
+                        Box::new(move || match toggles.as_ref() {
+                            Some(toggles) => {
+                                let value = value.get_clone();
+                                DomNode::set_class_attribute(&node, value.as_deref(), Some(toggles))
+                            }
+                            None => match value.get_clone() {
+                                Some(value) => node.set_attribute(&name, &value).unwrap(),
+                                None => node.remove_attribute(&name).unwrap(),
+                            },
                         }),
                         (),
                     )
@@ -219,7 +226,7 @@ impl ViewHtmlNode for HydrateNode {
 
     fn set_bool_attribute(&mut self, name: Cow<'static, str>, value: BoolAttribute) {
         // FIXME: use setAttributeNS if SVG
-        if IS_HYDRATING.get() {
+        if is_hydrating() {
             if value.as_static().is_none() {
                 let node = self
                     .as_web_sys()
@@ -244,6 +251,62 @@ impl ViewHtmlNode for HydrateNode {
         }
     }
 
+    fn set_class_toggle(&mut self, name: Cow<'static, str>, value: BoolAttribute) {
+        if is_hydrating() {
+            // The class is already rendered, but it is still recorded so that it is re-applied if
+            // the `class` attribute changes.
+            let toggles = self.0.unwrap_mut().class_toggles();
+            if let Some(value) = value.as_static() {
+                toggles.add(name, *value);
+            } else {
+                let class_list = self
+                    .as_web_sys()
+                    .unchecked_ref::<web_sys::Element>()
+                    .class_list();
+                let index = toggles.add(name.clone(), false);
+                create_effect_initial(move || {
+                    toggles.set(index, value.get()); // Track dependencies of value.
+                    (
+                        Box::new(move || {
+                            let active = value.get();
+                            toggles.set(index, active);
+                            class_list.toggle_with_force(&name, active).unwrap();
+                        }),
+                        (),
+                    )
+                });
+            }
+        } else {
+            self.0.unwrap_mut().set_class_toggle(name, value);
+        }
+    }
+
+    fn set_style_property(&mut self, name: Cow<'static, str>, value: StringAttribute) {
+        if is_hydrating() {
+            // Noop if value is static since the property is already rendered.
+            if value.as_static().is_none() {
+                let style = self
+                    .as_web_sys()
+                    .unchecked_ref::<web_sys::HtmlElement>()
+                    .style();
+                create_effect_initial(move || {
+                    let _ = value.track(); // Track dependencies of value.
+                    (
+                        Box::new(move || match value.get_clone() {
+                            Some(value) => style.set_property(&name, &value).unwrap(),
+                            None => {
+                                style.remove_property(&name).unwrap();
+                            }
+                        }),
+                        (),
+                    )
+                });
+            }
+        } else {
+            self.0.unwrap_mut().set_style_property(name, value);
+        }
+    }
+
     fn set_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<JsValue>) {
         self.0.unwrap_mut().set_property(name, value);
     }
@@ -258,7 +321,7 @@ impl ViewHtmlNode for HydrateNode {
 
     fn set_inner_html(&mut self, inner_html: Cow<'static, str>) {
         // If we are hydrating, inner HTML should already be set.
-        if !IS_HYDRATING.get() {
+        if !is_hydrating() {
             self.0.unwrap_mut().set_inner_html(inner_html);
         }
     }
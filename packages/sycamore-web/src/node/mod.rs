@@ -53,6 +53,11 @@ pub trait ViewHtmlNode: ViewNode {
     fn set_attribute(&mut self, name: Cow<'static, str>, value: StringAttribute);
     /// Set a boolean HTML attribute.
     fn set_bool_attribute(&mut self, name: Cow<'static, str>, value: BoolAttribute);
+    /// Add or remove a single class of an element, without affecting its other classes.
+    fn set_class_toggle(&mut self, name: Cow<'static, str>, value: BoolAttribute);
+    /// Set a single CSS property in the inline style of an element. The property is removed if
+    /// the value is `None`.
+    fn set_style_property(&mut self, name: Cow<'static, str>, value: StringAttribute);
     /// Set a JS property on an element.
     fn set_property(&mut self, name: Cow<'static, str>, value: MaybeDyn<JsValue>);
     /// Set an event handler on an element.
@@ -75,9 +80,38 @@ pub trait AsHtmlNode {
     fn as_html_node(&mut self) -> &mut HtmlNode;
 }
 
-thread_local! {
-    /// Whether we are in hydration mode or not.
-    pub(crate) static IS_HYDRATING: Cell<bool> = const { Cell::new(false) };
+/// Whether we are in hydration mode or not.
+///
+/// This is provided as a context by the render functions so that every render has its own state.
+/// Outside of a render, we are never in hydration mode.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct IsHydrating(Signal<bool>);
+
+impl IsHydrating {
+    pub fn new(value: bool) -> Self {
+        Self(create_signal(value))
+    }
+
+    pub fn get(self) -> bool {
+        self.0.get_untracked()
+    }
+
+    #[cfg_attr(
+        any(
+            not(feature = "hydrate"),
+            not(target_arch = "wasm32"),
+            sycamore_force_ssr
+        ),
+        allow(dead_code)
+    )]
+    pub fn set(self, value: bool) {
+        self.0.set_silent(value);
+    }
+}
+
+/// Returns whether we are in hydration mode in the current scope.
+pub(crate) fn is_hydrating() -> bool {
+    try_use_context::<IsHydrating>().is_some_and(IsHydrating::get)
 }
 
 /// A struct for keeping track of state used for hydration.
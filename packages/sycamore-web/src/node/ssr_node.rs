@@ -11,6 +11,10 @@ pub enum SsrNode {
         tag: Cow<'static, str>,
         attributes: Vec<(Cow<'static, str>, Cow<'static, str>)>,
         bool_attributes: Vec<(Cow<'static, str>, bool)>,
+        /// Classes set by `class:` directives. These are merged into the `class` attribute.
+        class_toggles: Vec<(Cow<'static, str>, bool)>,
+        /// Properties set by `style:` directives. These are merged into the `style` attribute.
+        style_properties: Vec<(Cow<'static, str>, Cow<'static, str>)>,
         children: Vec<Self>,
         // NOTE: This field is boxed to avoid allocating memory for a field that is rarely used.
         inner_html: Option<Box<Cow<'static, str>>>,
@@ -75,7 +79,7 @@ impl ViewNode for SsrNode {
 
 impl ViewHtmlNode for SsrNode {
     fn create_element(tag: Cow<'static, str>) -> Self {
-        let hk_key = if IS_HYDRATING.get() {
+        let hk_key = if is_hydrating() {
             let reg: HydrationRegistry = use_context();
             Some(reg.next_key())
         } else {
@@ -85,6 +89,8 @@ impl ViewHtmlNode for SsrNode {
             tag,
             attributes: Vec::new(),
             bool_attributes: Vec::new(),
+            class_toggles: Vec::new(),
+            style_properties: Vec::new(),
             children: Vec::new(),
             inner_html: None,
             hk_key,
@@ -128,6 +134,26 @@ impl ViewHtmlNode for SsrNode {
         }
     }
 
+    fn set_class_toggle(&mut self, name: Cow<'static, str>, value: BoolAttribute) {
+        match self {
+            Self::Element { class_toggles, .. } => class_toggles.push((name, value.evaluate())),
+            _ => panic!("can only set class on an element"),
+        }
+    }
+
+    fn set_style_property(&mut self, name: Cow<'static, str>, value: StringAttribute) {
+        match self {
+            Self::Element {
+                style_properties, ..
+            } => {
+                if let Some(value) = value.evaluate() {
+                    style_properties.push((name, value))
+                }
+            }
+            _ => panic!("can only set style on an element"),
+        }
+    }
+
     fn set_property(&mut self, _name: Cow<'static, str>, _value: MaybeDyn<JsValue>) {
         // Noop in SSR mode.
     }
@@ -175,18 +201,38 @@ pub(crate) fn render_recursive(node: &SsrNode, buf: &mut String) {
             tag,
             attributes,
             bool_attributes,
+            class_toggles,
+            style_properties,
             children,
             inner_html,
             hk_key,
         } => {
             buf.push('<');
             buf.push_str(tag);
+            let mut has_class = false;
+            let mut has_style = false;
             for (name, value) in attributes {
-                buf.push(' ');
-                buf.push_str(name);
-                buf.push_str("=\"");
-                html_escape::encode_double_quoted_attribute_to_string(value, buf);
-                buf.push('"');
+                let value = match name.as_ref() {
+                    "class" if !has_class => {
+                        has_class = true;
+                        merge_classes(value, class_toggles)
+                    }
+                    "style" if !has_style => {
+                        has_style = true;
+                        merge_styles(value, style_properties)
+                    }
+                    _ => Cow::Borrowed(value.as_ref()),
+                };
+                render_attribute(name, &value, buf);
+            }
+            if !has_class && !class_toggles.is_empty() {
+                let value = merge_classes("", class_toggles);
+                if !value.is_empty() {
+                    render_attribute("class", &value, buf);
+                }
+            }
+            if !has_style && !style_properties.is_empty() {
+                render_attribute("style", &merge_styles("", style_properties), buf);
             }
             for (name, value) in bool_attributes {
                 if *value {
@@ -251,6 +297,59 @@ pub(crate) fn render_recursive(node: &SsrNode, buf: &mut String) {
     }
 }
 
+/// Render the attribute `name="value"` with a leading space by appending to `buf`.
+fn render_attribute(name: &str, value: &str, buf: &mut String) {
+    buf.push(' ');
+    buf.push_str(name);
+    buf.push_str("=\"");
+    html_escape::encode_double_quoted_attribute_to_string(value, buf);
+    buf.push('"');
+}
+
+/// Applies the classes of `class:` directives to the value of a `class` attribute, in the same
+/// way as `classList.toggle` would in the browser.
+fn merge_classes<'a>(class: &'a str, toggles: &[(Cow<'static, str>, bool)]) -> Cow<'a, str> {
+    if toggles.is_empty() {
+        return Cow::Borrowed(class);
+    }
+    let mut classes = class.split_ascii_whitespace().collect::<Vec<_>>();
+    for (name, value) in toggles {
+        if *value {
+            if !classes.contains(&name.as_ref()) {
+                classes.push(name);
+            }
+        } else {
+            classes.retain(|class| class != name);
+        }
+    }
+    Cow::Owned(classes.join(" "))
+}
+
+/// Appends the properties of `style:` directives to the value of a `style` attribute. Later
+/// declarations take precedence, so this overrides properties that are already set.
+fn merge_styles<'a>(
+    style: &'a str,
+    properties: &[(Cow<'static, str>, Cow<'static, str>)],
+) -> Cow<'a, str> {
+    if properties.is_empty() {
+        return Cow::Borrowed(style);
+    }
+    let mut style = style.trim_end().to_string();
+    for (name, value) in properties {
+        if !style.is_empty() {
+            if !style.ends_with(';') {
+                style.push(';');
+            }
+            style.push(' ');
+        }
+        style.push_str(name);
+        style.push_str(": ");
+        style.push_str(value);
+        style.push(';');
+    }
+    Cow::Owned(style)
+}
+
 /// Recursively render a [`View`] to a string by calling `render_recursive` on each node.
 pub(crate) fn render_recursive_view(view: &View, buf: &mut String) {
     for node in &view.nodes {
@@ -339,6 +438,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn class_and_style_directives() {
+        check(
+            move || {
+                sycamore_macro::view! {
+                    div(class:active=true, class:hidden=false, style:color="red")
+                }
+            },
+            expect![[r#"<div class="active" style="color: red;" data-hk="0.0"></div>"#]],
+        );
+        // Directives are merged into the `class` and `style` attributes.
+        check(
+            move || {
+                sycamore_macro::view! {
+                    div(
+                        class:active=true,
+                        class:hidden=false,
+                        class="hidden item active",
+                        style:color="red",
+                        style:margin=None::<&str>,
+                        style="padding: 0;",
+                    )
+                }
+            },
+            expect![[
+                r#"<div class="item active" style="padding: 0; color: red;" data-hk="0.0"></div>"#
+            ]],
+        );
+    }
+
     #[test]
     fn svg_element() {
         check(
@@ -1,3 +1,8 @@
+use std::cell::RefCell;
+#[cfg_ssr]
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
 use super::*;
 
 /// The mode in which SSR is being run.
@@ -18,45 +23,260 @@ pub enum SsrMode {
     Streaming,
 }
 
+/// Metadata about the HTTP response that is collected while rendering a [`View`] on the server.
+///
+/// Components can write to this using the [`SsrResponseContext`] returned by
+/// [`use_ssr_response`]. The render functions ending in `_with_response` return it alongside the
+/// rendered HTML so that the server can set the status code and headers of the response.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SsrResponse {
+    /// The status code of the response. If `None`, the server should use its default (usually
+    /// `200`).
+    pub status: Option<u16>,
+    /// The url that the client should be redirected to, if any. This should be sent in the
+    /// `Location` header.
+    pub redirect: Option<String>,
+    /// Additional headers of the response, in the order in which they were added.
+    pub headers: Vec<(String, String)>,
+}
+
+/// A handle to the [`SsrResponse`] of the current render. This is obtained using
+/// [`use_ssr_response`].
+#[derive(Clone, Debug, Default)]
+pub struct SsrResponseContext(Arc<Mutex<SsrResponse>>);
+
+impl SsrResponseContext {
+    /// Sets the status code of the response.
+    pub fn set_status(&self, status: u16) {
+        self.0.lock().unwrap().status = Some(status);
+    }
+
+    /// Redirects the client to `url`. This sets the status code to `302 Found` unless another
+    /// redirection status code has already been set.
+    pub fn redirect(&self, url: impl Into<String>) {
+        let mut res = self.0.lock().unwrap();
+        if !matches!(res.status, Some(300..=399)) {
+            res.status = Some(302);
+        }
+        res.redirect = Some(url.into());
+    }
+
+    /// Sets a header of the response, replacing all previous values of the header. Header names are
+    /// compared case-insensitively.
+    pub fn set_header(&self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        let mut res = self.0.lock().unwrap();
+        res.headers.retain(|(n, _)| !n.eq_ignore_ascii_case(&name));
+        res.headers.push((name, value.into()));
+    }
+
+    /// Adds a header to the response without replacing previous values of the header.
+    pub fn append_header(&self, name: impl Into<String>, value: impl Into<String>) {
+        self.0
+            .lock()
+            .unwrap()
+            .headers
+            .push((name.into(), value.into()));
+    }
+
+    /// Returns a snapshot of the response.
+    pub fn get(&self) -> SsrResponse {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Returns a handle to the [`SsrResponse`] of the current render.
+///
+/// This returns `None` if not rendering on the server, or if the view is not rendered using one of
+/// the render functions (e.g. when rendering on the client).
+///
+/// # Example
+/// ```
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::{render_to_string_with_response, use_ssr_response};
+/// #[component]
+/// fn NotFound() -> View {
+///     if let Some(res) = use_ssr_response() {
+///         res.set_status(404);
+///     }
+///     view! { "Page not found" }
+/// }
+///
+/// let (html, res) = render_to_string_with_response(NotFound);
+/// assert_eq!(html, "Page not found");
+/// assert_eq!(res.status, Some(404));
+/// ```
+pub fn use_ssr_response() -> Option<SsrResponseContext> {
+    try_use_context::<SsrResponseContext>()
+}
+
+/// The maximum number of roots that are kept in the pool of a thread. Roots that are released while
+/// the pool is larger than this are removed from the pool.
+///
+/// Removed roots are disposed but not freed, since tasks spawned by the render might still refer to
+/// them. Only the empty root itself is leaked.
+#[cfg_ssr]
+const MAX_SSR_ROOTS: usize = 16;
+
+#[cfg_ssr]
+thread_local! {
+    /// The roots that are used for rendering on this thread, along with a flag that is set while
+    /// a render is using the root.
+    static SSR_ROOTS: RefCell<Vec<(RootHandle, Arc<AtomicBool>)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A root from the pool of SSR roots. Every render gets its own root so that concurrent renders on
+/// the same thread do not dispose each other's reactive state.
+///
+/// The root is disposed and returned to the pool when this is dropped. This can be moved into a
+/// `Send` stream. If it is dropped on another thread, the root is disposed right away with the
+/// `sync` feature, since roots can then be moved between threads. Otherwise, the root cannot be
+/// accessed, so it is only disposed once it is reused by another render.
+#[cfg_ssr]
+struct SsrRootLease {
+    in_use: Arc<AtomicBool>,
+    thread: std::thread::ThreadId,
+    #[cfg(feature = "sync")]
+    root: RootHandle,
+}
+
+#[cfg_ssr]
+impl SsrRootLease {
+    /// Takes an unused root from the pool of this thread. If all the roots are in use, a new root is
+    /// added to the pool.
+    fn acquire() -> (RootHandle, Self) {
+        let reused = SSR_ROOTS.with(|roots| {
+            roots
+                .borrow()
+                .iter()
+                .find(|(_, in_use)| !in_use.load(Ordering::Acquire))
+                .cloned()
+        });
+        let (root, in_use) = match reused {
+            Some((root, in_use)) => {
+                in_use.store(true, Ordering::Release);
+                // Clean up everything from the previous render in case it was released on another
+                // thread.
+                root.dispose();
+                (root, in_use)
+            }
+            None => {
+                let entry = (create_root(|| {}), Arc::new(AtomicBool::new(true)));
+                SSR_ROOTS.with(|roots| roots.borrow_mut().push(entry.clone()));
+                entry
+            }
+        };
+        let lease = Self {
+            in_use,
+            thread: std::thread::current().id(),
+            #[cfg(feature = "sync")]
+            root,
+        };
+        (root, lease)
+    }
+}
+
+#[cfg_ssr]
+impl Drop for SsrRootLease {
+    fn drop(&mut self) {
+        if std::thread::current().id() == self.thread {
+            // The pool might already be destroyed if the lease is dropped while the thread exits.
+            let _ = SSR_ROOTS.try_with(|roots| {
+                let mut roots = roots.borrow_mut();
+                let Some(i) = roots
+                    .iter()
+                    .position(|(_, in_use)| Arc::ptr_eq(in_use, &self.in_use))
+                else {
+                    return;
+                };
+                let root = if roots.len() > MAX_SSR_ROOTS {
+                    roots.swap_remove(i).0
+                } else {
+                    roots[i].0
+                };
+                drop(roots);
+                root.dispose();
+            });
+        } else {
+            #[cfg(feature = "sync")]
+            self.root.dispose();
+        }
+        self.in_use.store(false, Ordering::Release);
+    }
+}
+
 /// Render a [`View`] into a static [`String`]. Useful for rendering to a string on the server side.
 #[must_use]
 pub fn render_to_string(view: impl FnOnce() -> View) -> String {
+    render_to_string_with_response(view).0
+}
+
+/// Like [`render_to_string`] but also returns the [`SsrResponse`] that was written to by the
+/// components while rendering.
+#[must_use]
+pub fn render_to_string_with_response(view: impl FnOnce() -> View) -> (String, SsrResponse) {
     is_not_ssr! {
         let _ = view;
         panic!("`render_to_string` only available in SSR mode");
     }
     is_ssr! {
-        use std::cell::LazyCell;
-
-        thread_local! {
-            /// Use a static variable here so that we can reuse the same root for multiple calls to
-            /// this function.
-            static SSR_ROOT: LazyCell<RootHandle> = LazyCell::new(|| create_root(|| {}));
-        }
         let mut buf = String::new();
-        SSR_ROOT.with(|root| {
-            root.dispose();
-            root.run_in(|| {
-                // We run this in a new scope so that we can dispose everything after we render it.
-                provide_context(HydrationRegistry::new());
-                provide_context(SsrMode::Sync);
-
-                IS_HYDRATING.set(true);
-                let view = view();
-                IS_HYDRATING.set(false);
-                ssr_node::render_recursive_view(&view, &mut buf);
-            });
+        let response = SsrResponseContext::default();
+        let (root, _lease) = SsrRootLease::acquire();
+        root.run_in(|| {
+            provide_context(IsHydrating::new(true));
+            provide_context(HydrationRegistry::new());
+            provide_context(SsrMode::Sync);
+            provide_context(response.clone());
+
+            let view = view();
+            ssr_node::render_recursive_view(&view, &mut buf);
         });
-        buf
+        (buf, response.get())
     }
 }
 
+/// A closure that creates the [`View`] that is rendered by the server side rendering functions
+/// that wait for suspense.
+///
+/// With the `sync` feature, this must be `Send + 'static`, since the view is rendered on a
+/// blocking thread of the tokio runtime. Otherwise, this is implemented for all closures that
+/// return a [`View`].
+#[cfg(all(feature = "suspense", feature = "sync"))]
+pub trait SuspenseViewFn: FnOnce() -> View + Send + 'static {}
+#[cfg(all(feature = "suspense", feature = "sync"))]
+impl<F: FnOnce() -> View + Send + 'static> SuspenseViewFn for F {}
+
+/// A closure that creates the [`View`] that is rendered by the server side rendering functions
+/// that wait for suspense.
+///
+/// With the `sync` feature, this must be `Send + 'static`, since the view is rendered on a
+/// blocking thread of the tokio runtime. Otherwise, this is implemented for all closures that
+/// return a [`View`].
+#[cfg(all(feature = "suspense", not(feature = "sync")))]
+pub trait SuspenseViewFn: FnOnce() -> View {}
+#[cfg(all(feature = "suspense", not(feature = "sync")))]
+impl<F: FnOnce() -> View> SuspenseViewFn for F {}
+
 /// Renders a [`View`] into a static [`String`] while awaiting for all suspense boundaries to
 /// resolve. Useful for rendering to a string on the server side.
 ///
 /// This sets the SSR mode to "blocking" mode. This means that rendering will wait until suspense
 /// is resolved before returning.
 ///
+/// # Executor
+///
+/// The suspense tasks are run on a `tokio::task::LocalSet` that is created by this function.
+///
+/// Without the `sync` feature, the render runs on the current thread, so the returned future is
+/// not `Send`. In a multi-threaded runtime, run it on a thread that only runs local tasks, e.g.
+/// with `tokio::task::spawn_blocking` and `Handle::block_on`, as in the example of
+/// [`render_to_string_stream`].
+///
+/// With the `sync` feature, the render runs on a blocking thread of the current tokio runtime
+/// instead, so the returned future is `Send` and can be spawned with `tokio::spawn`. This must be
+/// called from within a tokio runtime.
+///
 /// # Example
 /// ```
 /// # use sycamore::prelude::*;
@@ -73,85 +293,125 @@ pub fn render_to_string(view: impl FnOnce() -> View) -> String {
 /// ```
 #[must_use]
 #[cfg(feature = "suspense")]
-pub async fn render_to_string_await_suspense(view: impl FnOnce() -> View) -> String {
+pub async fn render_to_string_await_suspense(view: impl SuspenseViewFn) -> String {
+    render_to_string_await_suspense_with_response(view).await.0
+}
+
+/// Like [`render_to_string_await_suspense`] but also returns the [`SsrResponse`] that was written
+/// to by the components while rendering, including inside suspense boundaries.
+#[must_use]
+#[cfg(feature = "suspense")]
+pub async fn render_to_string_await_suspense_with_response(
+    view: impl SuspenseViewFn,
+) -> (String, SsrResponse) {
     is_not_ssr! {
         let _ = view;
         panic!("`render_to_string` only available in SSR mode");
     }
     is_ssr! {
-        use std::num::NonZeroU32;
-        use std::cell::LazyCell;
-        use std::fmt::Write;
-        use std::collections::HashMap;
+        #[cfg(feature = "sync")]
+        {
+            let handle = tokio::runtime::Handle::current();
+            tokio::task::spawn_blocking(move || {
+                handle.block_on(render_to_string_await_suspense_impl(view))
+            })
+            .await
+            .unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()))
+        }
+        #[cfg(not(feature = "sync"))]
+        {
+            render_to_string_await_suspense_impl(view).await
+        }
+    }
+}
 
-        use futures::StreamExt;
+/// Implementation of [`render_to_string_await_suspense_with_response`]. The returned future runs
+/// the suspense tasks on the current thread.
+#[cfg_not_ssr]
+#[cfg(feature = "suspense")]
+pub(crate) async fn render_to_string_await_suspense_impl(
+    view: impl FnOnce() -> View,
+) -> (String, SsrResponse) {
+    let _ = view;
+    panic!("`render_to_string` only available in SSR mode");
+}
 
-        const BUFFER_SIZE: usize = 5;
+/// Implementation of [`render_to_string_await_suspense_with_response`]. The returned future runs
+/// the suspense tasks on the current thread.
+#[cfg_ssr]
+#[cfg(feature = "suspense")]
+pub(crate) async fn render_to_string_await_suspense_impl(
+    view: impl FnOnce() -> View,
+) -> (String, SsrResponse) {
+    use std::collections::HashMap;
+    use std::fmt::Write;
+    use std::num::NonZeroU32;
 
-        thread_local! {
-            /// Use a static variable here so that we can reuse the same root for multiple calls to
-            /// this function.
-            static SSR_ROOT: LazyCell<RootHandle> = LazyCell::new(|| create_root(|| {}));
-        }
-        IS_HYDRATING.set(true);
-        sycamore_futures::provide_executor_scope(async {
-            let mut buf = String::new();
+    use futures::StreamExt;
 
-            let (sender, mut receiver) = futures::channel::mpsc::channel(BUFFER_SIZE);
-            SSR_ROOT.with(|root| {
-                root.dispose();
-                root.run_in(|| {
-                    // We run this in a new scope so that we can dispose everything after we render it.
-                    provide_context(HydrationRegistry::new());
-                    provide_context(SsrMode::Blocking);
-                    let suspense_state = SuspenseState { sender };
+    const BUFFER_SIZE: usize = 5;
 
-                    provide_context(suspense_state);
+    sycamore_futures::provide_executor_scope(async {
+        let mut buf = String::new();
+        let response = SsrResponseContext::default();
 
-                    let view = view();
-                    ssr_node::render_recursive_view(&view, &mut buf);
-                });
-            });
+        let (sender, mut receiver) = futures::channel::mpsc::channel(BUFFER_SIZE);
+        let (root, lease) = SsrRootLease::acquire();
+        root.run_in(|| {
+            provide_context(IsHydrating::new(true));
+            provide_context(HydrationRegistry::new());
+            provide_context(SsrMode::Blocking);
+            provide_context(response.clone());
+            let suspense_state = SuspenseState { sender };
+
+            provide_context(suspense_state);
+
+            let view = view();
+            ssr_node::render_recursive_view(&view, &mut buf);
+        });
 
-            // Split at suspense fragment locations.
-            let split = buf.split("<!--sycamore-suspense-").collect::<Vec<_>>();
-            // Calculate the number of suspense fragments.
-            let n = split.len() - 1;
+        // Split at suspense fragment locations.
+        let split = buf.split("<!--sycamore-suspense-").collect::<Vec<_>>();
+        // Calculate the number of suspense fragments.
+        let n = split.len() - 1;
 
-            // Now we wait until all suspense fragments are resolved.
-            let mut fragment_map = HashMap::new();
-            if n == 0 {
+        // Now we wait until all suspense fragments are resolved.
+        let mut fragment_map = HashMap::new();
+        if n == 0 {
+            receiver.close();
+        }
+        let mut i = 0;
+        while let Some(fragment) = receiver.next().await {
+            fragment_map.insert(fragment.key, fragment.view);
+            i += 1;
+            if i == n {
+                // We have received all suspense fragments so we shouldn't need the receiver anymore.
                 receiver.close();
             }
-            let mut i = 0;
-            while let Some(fragment) = receiver.next().await {
-                fragment_map.insert(fragment.key, fragment.view);
-                i += 1;
-                if i == n {
-                    // We have received all suspense fragments so we shouldn't need the receiver anymore.
-                    receiver.close();
-                }
-            }
-            IS_HYDRATING.set(false);
-
-            // Finally, replace all suspense marker nodes with rendered values.
-            if let [first, rest @ ..] = split.as_slice() {
-                rest.iter().fold(first.to_string(), |mut acc, s| {
-                    // Try to parse the key.
-                    let (num, rest) = s.split_once("-->").expect("end of suspense marker not found");
-                    let key: u32 = num.parse().expect("could not parse suspense key");
-                    let key = NonZeroU32::try_from(key).expect("suspense key cannot be 0");
-                    let fragment = fragment_map.get(&key).expect("fragment not found");
-                    ssr_node::render_recursive_view(fragment, &mut acc);
-
-                    write!(&mut acc, "{rest}").unwrap();
-                    acc
-                })
-            } else {
-                unreachable!("split should always have at least one element")
-            }
-        }).await
-    }
+        }
+        drop(lease);
+
+        // Finally, replace all suspense marker nodes with rendered values.
+        let html = if let [first, rest @ ..] = split.as_slice() {
+            rest.iter().fold(first.to_string(), |mut acc, s| {
+                // Try to parse the key.
+                let (num, rest) = s
+                    .split_once("-->")
+                    .expect("end of suspense marker not found");
+                let key: u32 = num.parse().expect("could not parse suspense key");
+                let key = NonZeroU32::try_from(key).expect("suspense key cannot be 0");
+                let fragment = fragment_map.get(&key).expect("fragment not found");
+                ssr_node::render_recursive_view(fragment, &mut acc);
+
+                write!(&mut acc, "{rest}").unwrap();
+                acc
+            })
+        } else {
+            unreachable!("split should always have at least one element")
+        };
+        (html, response.get())
+    })
+    .await
 }
 
 /// Renders a [`View`] to a stream.
@@ -164,8 +424,19 @@ pub async fn render_to_string_await_suspense(view: impl FnOnce() -> View) -> Str
 ///
 /// # Executor
 ///
-/// This function (unlike [`render_to_string_await_suspense`]) does not automatically create an
-/// executor. You must provide the executor yourself by using `tokio::task::LocalSet`.
+/// Without the `sync` feature, this function (unlike [`render_to_string_await_suspense`]) does
+/// not automatically create an executor. You must provide the executor yourself by using
+/// `tokio::task::LocalSet`. The returned stream itself is `Send`, so it can be sent out of the
+/// `LocalSet`, e.g. to a web framework.
+///
+/// With the `sync` feature, the view is rendered on a blocking thread of the current tokio
+/// runtime, which also runs the suspense tasks on a `LocalSet` until the stream is finished or
+/// dropped. No `LocalSet` is needed then, so this can be called from a task that was spawned with
+/// `tokio::spawn`. This must be called from within a tokio runtime, and it blocks until the
+/// initial HTML is rendered.
+///
+/// Every render has its own reactive root, so multiple streams can be rendered concurrently in the
+/// same `LocalSet`. The root is kept alive until the stream is finished or dropped.
 ///
 /// # Example
 /// ```
@@ -212,86 +483,130 @@ pub async fn render_to_string_await_suspense(view: impl FnOnce() -> View) -> Str
 /// ```
 #[cfg(feature = "suspense")]
 pub fn render_to_string_stream(
-    view: impl FnOnce() -> View,
+    view: impl SuspenseViewFn,
 ) -> impl futures::Stream<Item = String> + Send {
+    render_to_string_stream_with_response(view).1
+}
+
+/// Like [`render_to_string_stream`] but also returns the [`SsrResponse`] that was written to by
+/// the components while rendering.
+///
+/// Since the status code and headers must be sent before the body, the response only contains what
+/// was written during the initial render. Changes made inside suspense boundaries that resolve
+/// later are not included.
+#[cfg(feature = "suspense")]
+pub fn render_to_string_stream_with_response(
+    view: impl SuspenseViewFn,
+) -> (SsrResponse, impl futures::Stream<Item = String> + Send) {
     is_not_ssr! {
         let _ = view;
         panic!("`render_to_string` only available in SSR mode");
         #[allow(unreachable_code)] // TODO: never type cannot be coerced into `impl Stream` somehow.
-        futures::stream::empty()
+        (SsrResponse::default(), futures::stream::empty())
     }
     is_ssr! {
-        use std::cell::LazyCell;
+        #[cfg(feature = "sync")]
+        {
+            use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+
+            let handle = tokio::runtime::Handle::current();
+            let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+            tokio::task::spawn_blocking(move || {
+                let local = tokio::task::LocalSet::new();
+                handle.block_on(local.run_until(async move {
+                    let ret = catch_unwind(AssertUnwindSafe(|| {
+                        render_to_string_stream_impl(view)
+                    }));
+                    let _ = sender.send(ret);
+                }));
+                // Run the suspense tasks until the stream is finished or dropped, which disposes
+                // the root and thereby cancels the remaining tasks.
+                handle.block_on(local);
+            });
+            match receiver.recv().expect("render thread exited") {
+                Ok(ret) => ret,
+                Err(payload) => resume_unwind(payload),
+            }
+        }
+        #[cfg(not(feature = "sync"))]
+        {
+            render_to_string_stream_impl(view)
+        }
+    }
+}
 
-        use futures::StreamExt;
+/// Implementation of [`render_to_string_stream_with_response`]. The suspense tasks are spawned on
+/// the current `LocalSet`.
+#[cfg_ssr]
+#[cfg(feature = "suspense")]
+fn render_to_string_stream_impl(
+    view: impl FnOnce() -> View,
+) -> (SsrResponse, impl futures::Stream<Item = String> + Send) {
+    use futures::StreamExt;
 
-        const BUFFER_SIZE: usize = 5;
+    const BUFFER_SIZE: usize = 5;
 
-        thread_local! {
-            /// Use a static variable here so that we can reuse the same root for multiple calls to
-            /// this function.
-            static SSR_ROOT: LazyCell<RootHandle> = LazyCell::new(|| create_root(|| {}));
+    let mut buf = String::new();
+    let response = SsrResponseContext::default();
+    let (sender, mut receiver) = futures::channel::mpsc::channel(BUFFER_SIZE);
+    let (root, lease) = SsrRootLease::acquire();
+    root.run_in(|| {
+        provide_context(IsHydrating::new(true));
+        provide_context(HydrationRegistry::new());
+        provide_context(SsrMode::Streaming);
+        provide_context(response.clone());
+        let suspense_state = SuspenseState { sender };
+
+        provide_context(suspense_state);
+
+        let view = view();
+        ssr_node::render_recursive_view(&view, &mut buf);
+    });
+
+    // Calculate the number of suspense fragments.
+    let mut n = buf.matches("<!--sycamore-suspense-").count();
+
+    // ```js
+    // function __sycamore_suspense(key) {
+    //   let start = document.querySelector(`suspense-start[data-key="${key}"]`)
+    //   let end = document.querySelector(`suspense-end[data-key="${key}"]`)
+    //   let template = document.getElementById(`sycamore-suspense-${key}`)
+    //   start.parentNode.insertBefore(template.content, start)
+    //   while (start.nextSibling != end) {
+    //     start.parentNode.removeChild(start.nextSibling)
+    //   }
+    // }
+    // ```
+    static SUSPENSE_REPLACE_SCRIPT: &str = r#"<script>function __sycamore_suspense(e){let s=document.querySelector(`suspense-start[data-key="${e}"]`),n=document.querySelector(`suspense-end[data-key="${e}"]`),r=document.getElementById(`sycamore-suspense-${e}`);for(s.parentNode.insertBefore(r.content,s);s.nextSibling!=n;)s.parentNode.removeChild(s.nextSibling);}</script>"#;
+    let stream = async_stream::stream! {
+        // Keep the root until the stream is finished or dropped.
+        let _lease = lease;
+        let mut initial = String::new();
+        initial.push_str("<!doctype html>");
+        initial.push_str(&buf);
+        initial.push_str(SUSPENSE_REPLACE_SCRIPT);
+        yield initial;
+
+        if n == 0 {
+            receiver.close();
         }
-        IS_HYDRATING.set(true);
-        let mut buf = String::new();
-        let (sender, mut receiver) = futures::channel::mpsc::channel(BUFFER_SIZE);
-        SSR_ROOT.with(|root| {
-            root.dispose();
-            root.run_in(|| {
-                // We run this in a new scope so that we can dispose everything after we render it.
-                provide_context(HydrationRegistry::new());
-                provide_context(SsrMode::Streaming);
-                let suspense_state = SuspenseState { sender };
-
-                provide_context(suspense_state);
-
-                let view = view();
-                ssr_node::render_recursive_view(&view, &mut buf);
-            });
-        });
-
-        // Calculate the number of suspense fragments.
-        let mut n = buf.matches("<!--sycamore-suspense-").count();
-
-        // ```js
-        // function __sycamore_suspense(key) {
-        //   let start = document.querySelector(`suspense-start[data-key="${key}"]`)
-        //   let end = document.querySelector(`suspense-end[data-key="${key}"]`)
-        //   let template = document.getElementById(`sycamore-suspense-${key}`)
-        //   start.parentNode.insertBefore(template.content, start)
-        //   while (start.nextSibling != end) {
-        //     start.parentNode.removeChild(start.nextSibling)
-        //   }
-        // }
-        // ```
-        static SUSPENSE_REPLACE_SCRIPT: &str = r#"<script>function __sycamore_suspense(e){let s=document.querySelector(`suspense-start[data-key="${e}"]`),n=document.querySelector(`suspense-end[data-key="${e}"]`),r=document.getElementById(`sycamore-suspense-${e}`);for(s.parentNode.insertBefore(r.content,s);s.nextSibling!=n;)s.parentNode.removeChild(s.nextSibling);}</script>"#;
-        async_stream::stream! {
-            let mut initial = String::new();
-            initial.push_str("<!doctype html>");
-            initial.push_str(&buf);
-            initial.push_str(SUSPENSE_REPLACE_SCRIPT);
-            yield initial;
-
-            if n == 0 {
+        let mut i = 0;
+        while let Some(fragment) = receiver.next().await {
+            let buf_fragment = render_suspense_fragment(fragment);
+            // Check if we have any nested suspense.
+            let n_add = buf_fragment.matches("<!--sycamore-suspense-").count();
+            n += n_add;
+
+            yield buf_fragment;
+
+            i += 1;
+            if i == n {
+                // We have received all suspense fragments so we shouldn't need the receiver anymore.
                 receiver.close();
             }
-            let mut i = 0;
-            while let Some(fragment) = receiver.next().await {
-                let buf_fragment = render_suspense_fragment(fragment);
-                // Check if we have any nested suspense.
-                let n_add = buf_fragment.matches("<!--sycamore-suspense-").count();
-                n += n_add;
-
-                yield buf_fragment;
-
-                i += 1;
-                if i == n {
-                    // We have received all suspense fragments so we shouldn't need the receiver anymore.
-                    receiver.close();
-                }
-            }
         }
-    }
+    };
+    (response.get(), stream)
 }
 
 #[cfg_ssr]
@@ -363,4 +678,203 @@ mod tests {
         ]];
         expect.assert_eq(&res);
     }
+
+    #[tokio::test]
+    async fn interleaved_renders_are_isolated() {
+        let (sender1, receiver1) = oneshot::channel();
+        let (sender2, receiver2) = oneshot::channel();
+        let ssr1 = render_to_string_await_suspense(move || view! { App(receiver=receiver1) });
+        let ssr2 = render_to_string_await_suspense(move || view! { App(receiver=receiver2) });
+        futures::pin_mut!(ssr1, ssr2);
+        assert!(futures::poll!(&mut ssr1).is_pending());
+        assert!(futures::poll!(&mut ssr2).is_pending());
+
+        // A synchronous render in between must not dispose the pending renders either.
+        let _ = render_to_string(|| view! { "Hello" });
+
+        // Resolve the second render first.
+        sender2.send(()).unwrap();
+        let res2 = ssr2.await;
+        assert!(futures::poll!(&mut ssr1).is_pending());
+        sender1.send(()).unwrap();
+        let res1 = ssr1.await;
+
+        let expect = expect![[
+            r#"<suspense-start data-key="1" data-hk="0.0"></suspense-start><!--/--><!--/--><!--/-->Hello, async!<!--/--><!--/--><!--/--><suspense-end data-key="1"></suspense-end>"#
+        ]];
+        expect.assert_eq(&res1);
+        expect.assert_eq(&res2);
+    }
+
+    #[tokio::test]
+    async fn interleaved_streams_are_isolated() {
+        use futures::StreamExt;
+
+        let local = tokio::task::LocalSet::new();
+        local
+            .run_until(async {
+                let (sender1, receiver1) = oneshot::channel();
+                let (sender2, receiver2) = oneshot::channel();
+                let stream1 = render_to_string_stream(move || view! { App(receiver=receiver1) });
+                let stream2 = render_to_string_stream(move || view! { App(receiver=receiver2) });
+                futures::pin_mut!(stream1, stream2);
+
+                let initial1 = stream1.next().await.unwrap();
+                let initial2 = stream2.next().await.unwrap();
+                assert!(initial1.contains("fallback"));
+                assert_eq!(initial1, initial2);
+
+                // Resolve the second stream first.
+                sender2.send(()).unwrap();
+                let fragment2 = stream2.next().await.unwrap();
+                assert!(futures::poll!(stream1.next()).is_pending());
+                sender1.send(()).unwrap();
+                let fragment1 = stream1.next().await.unwrap();
+
+                let expect = expect![[
+                    r#"<template id="sycamore-suspense-1"><!--/--><!--/-->Hello, async!<!--/--><!--/--></template><script>__sycamore_suspense(1)</script>"#
+                ]];
+                expect.assert_eq(&fragment1);
+                expect.assert_eq(&fragment2);
+                assert!(stream1.next().await.is_none());
+                assert!(stream2.next().await.is_none());
+            })
+            .await;
+    }
+
+    #[test]
+    fn dropping_lease_disposes_root() {
+        let disposed = Arc::new(AtomicBool::new(false));
+        let _ = render_to_string({
+            let disposed = disposed.clone();
+            move || {
+                on_cleanup(move || disposed.store(true, Ordering::Relaxed));
+                view! { "Hello" }
+            }
+        });
+        assert!(disposed.load(Ordering::Relaxed));
+    }
+
+    // The pool is per thread, so this does not work with the `sync` feature, which renders on
+    // blocking threads.
+    #[tokio::test]
+    #[cfg(not(feature = "sync"))]
+    async fn ssr_root_pool_is_capped() {
+        let pool_len = || SSR_ROOTS.with(|roots| roots.borrow().len());
+        for _ in 0..2 {
+            let (senders, renders): (Vec<_>, Vec<_>) = (0..MAX_SSR_ROOTS + 4)
+                .map(|_| {
+                    let (sender, receiver) = oneshot::channel();
+                    let ssr = Box::pin(render_to_string_await_suspense(
+                        move || view! { App(receiver=receiver) },
+                    ));
+                    (sender, ssr)
+                })
+                .unzip();
+            let mut renders = renders;
+            for ssr in &mut renders {
+                assert!(futures::poll!(ssr).is_pending());
+            }
+            assert_eq!(pool_len(), MAX_SSR_ROOTS + 4);
+
+            for sender in senders {
+                sender.send(()).unwrap();
+            }
+            futures::future::join_all(renders).await;
+            // The roots above the cap are not kept after the burst.
+            assert_eq!(pool_len(), MAX_SSR_ROOTS);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(feature = "sync")]
+    async fn render_to_string_await_suspense_is_send() {
+        let (sender, receiver) = oneshot::channel();
+        let ssr = tokio::spawn(render_to_string_await_suspense(
+            move || view! { App(receiver=receiver) },
+        ));
+        sender.send(()).unwrap();
+        let res = ssr.await.unwrap();
+
+        let expect = expect![[
+            r#"<suspense-start data-key="1" data-hk="0.0"></suspense-start><!--/--><!--/--><!--/-->Hello, async!<!--/--><!--/--><!--/--><suspense-end data-key="1"></suspense-end>"#
+        ]];
+        expect.assert_eq(&res);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(feature = "sync")]
+    async fn render_to_string_stream_is_send() {
+        use futures::StreamExt;
+
+        let (sender, receiver) = oneshot::channel();
+        let ssr = tokio::spawn(async move {
+            render_to_string_stream(move || view! { App(receiver=receiver) })
+                .collect::<Vec<_>>()
+                .await
+        });
+        sender.send(()).unwrap();
+        let res = ssr.await.unwrap();
+
+        assert_eq!(res.len(), 2);
+        assert!(res[0].contains("fallback"));
+        let expect = expect![[
+            r#"<template id="sycamore-suspense-1"><!--/--><!--/-->Hello, async!<!--/--><!--/--></template><script>__sycamore_suspense(1)</script>"#
+        ]];
+        expect.assert_eq(&res[1]);
+    }
+
+    #[component]
+    fn Moved() -> View {
+        let res = use_ssr_response().unwrap();
+        res.set_status(301);
+        res.redirect("/new");
+        res.set_header("Cache-Control", "no-cache");
+        res.set_header("cache-control", "no-store");
+        res.append_header("Set-Cookie", "a=1");
+        res.append_header("Set-Cookie", "b=2");
+        view! { "Moved" }
+    }
+
+    #[test]
+    fn render_to_string_with_response_collects_response() {
+        let (html, res) = render_to_string_with_response(Moved);
+        assert_eq!(html, "Moved");
+        assert_eq!(
+            res,
+            SsrResponse {
+                status: Some(301),
+                redirect: Some("/new".to_string()),
+                headers: vec![
+                    ("cache-control".to_string(), "no-store".to_string()),
+                    ("Set-Cookie".to_string(), "a=1".to_string()),
+                    ("Set-Cookie".to_string(), "b=2".to_string()),
+                ],
+            }
+        );
+
+        // The response is not shared between renders.
+        let (_, res) = render_to_string_with_response(|| view! { "Hello" });
+        assert_eq!(res, SsrResponse::default());
+    }
+
+    #[tokio::test]
+    async fn render_to_string_await_suspense_with_response_waits_for_suspense() {
+        #[component]
+        async fn NotFound() -> View {
+            use_ssr_response().unwrap().set_status(404);
+            view! { "Not found" }
+        }
+
+        let (_, res) = render_to_string_await_suspense_with_response(|| {
+            view! {
+                Suspense(fallback=|| "fallback".into()) {
+                    NotFound {}
+                }
+            }
+        })
+        .await;
+        assert_eq!(res.status, Some(404));
+        assert_eq!(res.redirect, None);
+    }
 }
@@ -88,6 +88,41 @@ impl NodeRef {
     pub fn set(&self, node: Option<web_sys::Node>) {
         self.0.set(node);
     }
+
+    /// Dispatches a custom event with the specified detail on the node. The event bubbles and is
+    /// cancelable.
+    ///
+    /// Returns `false` if the event was canceled by a handler, and `true` otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// # use sycamore::prelude::*;
+    /// # use sycamore::web::custom_event;
+    /// custom_event! {
+    ///     pub value_changed: String = "value-changed";
+    /// }
+    ///
+    /// # fn Component() -> View {
+    /// let input_ref = create_node_ref();
+    /// view! {
+    ///     input(ref=input_ref, on:input=move |_| {
+    ///         input_ref.dispatch(value_changed, "new value".to_string());
+    ///     })
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the node ref is not set yet.
+    #[track_caller]
+    pub fn dispatch<E, D>(&self, _event: E, detail: D) -> bool
+    where
+        E: events::EventDescriptor<EventTy = events::CustomEvent<D>>,
+        D: events::EventDetail,
+    {
+        let event = events::CustomEvent::new(E::NAME, detail);
+        self.get().dispatch_event(&event).unwrap()
+    }
 }
 
 impl Default for NodeRef {
@@ -4,6 +4,9 @@ use crate::*;
 
 /// A portal into a different part of the DOM. Only renders in client side rendering (CSR) mode.
 /// Does nothing in SSR mode.
+///
+/// If events are delegated (see [`delegate_events`]), events bubble out of the portal into the
+/// element that contains the portal.
 #[component(inline_props)]
 pub fn Portal<'a, T: Into<View> + Default>(selector: &'a str, children: T) -> View {
     if is_not_ssr!() {
@@ -22,6 +25,19 @@ pub fn Portal<'a, T: Into<View> + Default>(selector: &'a str, children: T) -> Vi
             parent.append_child(node).unwrap();
         }
 
+        // Let delegated events bubble out of the portal into the element that contains it. The
+        // marker node marks the position of the portal.
+        let host = try_use_context::<EventDelegation>().map(|delegation| {
+            let host = HtmlNode::create_marker_node();
+            delegation.add_portal(
+                parent.clone().into(),
+                start_node.clone(),
+                end_node.clone(),
+                host.as_web_sys().clone(),
+            );
+            host
+        });
+
         on_cleanup(move || {
             let nodes = utils::get_nodes_between(&start_node, &end_node);
             for node in nodes {
@@ -30,6 +46,10 @@ pub fn Portal<'a, T: Into<View> + Default>(selector: &'a str, children: T) -> Vi
             parent.remove_child(&start_node).unwrap();
             parent.remove_child(&end_node).unwrap();
         });
+
+        if let Some(host) = host {
+            return host.into();
+        }
     }
     View::default()
 }
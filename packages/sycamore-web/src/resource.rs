@@ -3,9 +3,9 @@
 use std::future::Future;
 use std::ops::Deref;
 
-use futures::future::{FutureExt, LocalBoxFuture};
 use sycamore_futures::{SuspenseScope, SuspenseTaskGuard};
 
+use crate::utils::ThreadBound;
 use crate::*;
 
 /// Represents a asynchronous resource.
@@ -18,34 +18,31 @@ pub struct Resource<T: 'static> {
     value: Signal<Option<T>>,
     /// Whether the resource is currently loading or not.
     is_loading: Signal<bool>,
-    /// The function that fetches the resource.
-    #[allow(clippy::complexity)]
-    refetch: Signal<Box<dyn FnMut() -> LocalBoxFuture<'static, T>>>,
     /// A list of all the suspense scopes in which the resource is accessed.
     scopes: Signal<Vec<SuspenseScope>>,
     /// A list of suspense guards that are currently active.
     guards: Signal<Vec<SuspenseTaskGuard>>,
 }
 
-impl<T: 'static> Resource<T> {
+impl<T: MaybeSend + 'static> Resource<T> {
     /// Create a new resource. By itself, this doesn't do anything.
-    fn new<F, Fut>(mut refetch: F) -> Self
-    where
-        F: FnMut() -> Fut + 'static,
-        Fut: Future<Output = T> + 'static,
-    {
+    fn new() -> Self {
         Self {
             value: create_signal(None),
             is_loading: create_signal(true),
-            refetch: create_signal(Box::new(move || refetch().boxed_local())),
             scopes: create_signal(Vec::new()),
             guards: create_signal(Vec::new()),
         }
     }
 
     /// Attach handlers to call the refetch function on the client side.
-    fn fetch_on_client(self) -> Self {
+    fn fetch_on_client<F, Fut>(self, refetch: F) -> Self
+    where
+        F: FnMut() -> Fut + 'static,
+        Fut: Future<Output = T> + 'static,
+    {
         if is_not_ssr!() {
+            let mut refetch = ThreadBound::new(refetch);
             create_effect(move || {
                 self.is_loading.set(true);
                 // Take all the scopes and create a new guard.
@@ -54,7 +51,7 @@ impl<T: 'static> Resource<T> {
                     self.guards.update(|guards| guards.push(guard));
                 }
 
-                let fut = self.refetch.update_silent(|f| f());
+                let fut = refetch();
 
                 sycamore_futures::create_suspense_task(async move {
                     let value = fut.await;
@@ -106,7 +103,7 @@ pub fn create_client_resource<F, Fut, T>(f: F) -> Resource<T>
 where
     F: FnMut() -> Fut + 'static,
     Fut: Future<Output = T> + 'static,
-    T: 'static,
+    T: MaybeSend + 'static,
 {
-    Resource::new(f).fetch_on_client()
+    Resource::new().fetch_on_client(f)
 }
@@ -0,0 +1,366 @@
+//! Static site generation.
+//!
+//! See [`StaticSite`] for more details.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::*;
+
+type ShellFn = dyn Fn(&str) -> String;
+
+/// Renders pages of an app ahead of time and writes them to an output directory, along with a
+/// `sitemap.xml`.
+///
+/// Every page is rendered using [`render_to_string_await_suspense_with_response`] and written to
+/// `<out_dir>/<path>/index.html`, so that it can be served by any static file server. Pages that
+/// respond with a redirect (see [`SsrResponseContext::redirect`]) are written as a page that
+/// redirects the browser and are left out of the sitemap.
+///
+/// If the router is used, see `sycamore_router::generate_static_site` instead, which renders all
+/// the routes of a `Route` enum.
+///
+/// # Example
+/// ```no_run
+/// # use sycamore::prelude::*;
+/// # use sycamore::web::StaticSite;
+/// # tokio_test::block_on(async move {
+/// let site = StaticSite::new("dist", "https://example.com")
+///     .shell(|body| format!("<!doctype html><html><body>{body}</body></html>"));
+/// site.generate(["/".to_string(), "/about".to_string()], |path| {
+///     let path = path.to_string();
+///     view! { h1 { (path) } }
+/// })
+/// .await
+/// .unwrap();
+/// # })
+/// ```
+pub struct StaticSite {
+    out_dir: PathBuf,
+    base_url: String,
+    shell: Box<ShellFn>,
+}
+
+impl fmt::Debug for StaticSite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StaticSite")
+            .field("out_dir", &self.out_dir)
+            .field("base_url", &self.base_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StaticSite {
+    /// Creates a new [`StaticSite`] that writes the pages to `out_dir`. The `base_url` is the url
+    /// at which the site is deployed (e.g. `https://example.com`) and is used for the sitemap.
+    pub fn new(out_dir: impl Into<PathBuf>, base_url: impl Into<String>) -> Self {
+        Self {
+            out_dir: out_dir.into(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            shell: Box::new(|body| format!("<!doctype html>{body}")),
+        }
+    }
+
+    /// Sets the function that wraps the rendered HTML of a page into a complete document. This is
+    /// where the `<head>` and the scripts for hydrating the page should be added.
+    ///
+    /// By default, the rendered HTML is only prefixed with `<!doctype html>`.
+    pub fn shell(mut self, f: impl Fn(&str) -> String + 'static) -> Self {
+        self.shell = Box::new(f);
+        self
+    }
+
+    /// Renders the page of every path using `view` and writes them to the output directory, along
+    /// with a `sitemap.xml`. Duplicate paths are only rendered once.
+    ///
+    /// All pages are rendered before anything is written. If any page responds with an error
+    /// status code (e.g. `404` for a page that is not found), nothing is written and the failed
+    /// pages are returned in a [`StaticSiteError::Status`].
+    pub async fn generate(
+        &self,
+        paths: impl IntoIterator<Item = String>,
+        view: impl Fn(&str) -> View,
+    ) -> Result<Vec<StaticPage>, StaticSiteError> {
+        let mut pages = Vec::new();
+        let mut contents = Vec::new();
+        let mut failed = Vec::new();
+        for path in paths {
+            if pages.iter().any(|page: &StaticPage| page.path == path)
+                || failed.iter().any(|(failed, _)| *failed == path)
+            {
+                continue;
+            }
+            let file = page_file(&self.out_dir, &path)
+                .ok_or_else(|| StaticSiteError::InvalidPath(path.clone()))?;
+            // Pages are rendered on the current thread, so `view` does not need to be `Send`.
+            let (html, response) = render_to_string_await_suspense_impl(|| view(&path)).await;
+            match response.status {
+                Some(status) if status >= 400 => failed.push((path, status)),
+                _ => {
+                    contents.push(match &response.redirect {
+                        Some(url) => redirect_page(url),
+                        None => (self.shell)(&html),
+                    });
+                    pages.push(StaticPage {
+                        path,
+                        file,
+                        response,
+                    });
+                }
+            }
+        }
+        if !failed.is_empty() {
+            return Err(StaticSiteError::Status(failed));
+        }
+
+        for (page, content) in pages.iter().zip(contents) {
+            write_file(&page.file, &content)?;
+        }
+        let sitemap = sitemap(
+            &self.base_url,
+            pages
+                .iter()
+                .filter(|page| page.response.redirect.is_none())
+                .map(|page| page.path.as_str()),
+        );
+        write_file(&self.out_dir.join("sitemap.xml"), &sitemap)?;
+
+        Ok(pages)
+    }
+}
+
+/// A page that was written by [`StaticSite::generate`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StaticPage {
+    /// The path of the page.
+    pub path: String,
+    /// The file that the page was written to.
+    pub file: PathBuf,
+    /// The response that was collected while rendering the page.
+    pub response: SsrResponse,
+}
+
+/// An error that occurred while generating a static site.
+#[derive(Debug)]
+pub enum StaticSiteError {
+    /// A path cannot be written to a file, e.g. because it has a query string or a `..` segment.
+    InvalidPath(String),
+    /// Some pages responded with an error status code. Contains the path and the status code of
+    /// every page that failed.
+    Status(Vec<(String, u16)>),
+    /// A file could not be written.
+    Io {
+        /// The file that could not be written.
+        path: PathBuf,
+        /// The underlying error.
+        source: io::Error,
+    },
+}
+
+impl fmt::Display for StaticSiteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPath(path) => write!(f, "path `{path}` cannot be written to a file"),
+            Self::Status(failed) => {
+                write!(f, "failed to render {} page(s):", failed.len())?;
+                for (path, status) in failed {
+                    match status {
+                        404 => write!(f, " `{path}` (not found)")?,
+                        status => write!(f, " `{path}` (status {status})")?,
+                    }
+                }
+                Ok(())
+            }
+            Self::Io { path, source } => {
+                write!(f, "failed to write `{}`: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for StaticSiteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the file that the page at `path` is written to, or `None` if the path cannot be mapped
+/// to a file inside `out_dir`.
+fn page_file(out_dir: &Path, path: &str) -> Option<PathBuf> {
+    if !path.starts_with('/') || path.contains(['?', '#']) {
+        return None;
+    }
+    let mut file = out_dir.to_path_buf();
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        let segment = percent_decode(segment)?;
+        if segment == "." || segment == ".." || segment.contains(['/', '\\']) {
+            return None;
+        }
+        file.push(segment);
+    }
+    file.push("index.html");
+    Some(file)
+}
+
+/// Decodes a percent-encoded path segment. Returns `None` if the result is not valid UTF-8.
+fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+fn write_file(path: &Path, content: &str) -> Result<(), StaticSiteError> {
+    let write = || {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)
+    };
+    write().map_err(|source| StaticSiteError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Returns a page that redirects the browser to `url`.
+fn redirect_page(url: &str) -> String {
+    let url = escape_xml(url);
+    format!(
+        "<!doctype html><html><head><meta charset=\"utf-8\">\
+         <meta http-equiv=\"refresh\" content=\"0; url={url}\">\
+         <link rel=\"canonical\" href=\"{url}\"></head></html>"
+    )
+}
+
+fn sitemap<'a>(base_url: &str, paths: impl Iterator<Item = &'a str>) -> String {
+    let mut sitemap = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for path in paths {
+        sitemap.push_str("  <url><loc>");
+        sitemap.push_str(&escape_xml(&format!("{base_url}{path}")));
+        sitemap.push_str("</loc></url>\n");
+    }
+    sitemap.push_str("</urlset>\n");
+    sitemap
+}
+
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+#[cfg_ssr]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_files() {
+        let out = Path::new("dist");
+        assert_eq!(page_file(out, "/"), Some(out.join("index.html")));
+        assert_eq!(
+            page_file(out, "/posts/hello%20world"),
+            Some(out.join("posts").join("hello world").join("index.html"))
+        );
+        assert_eq!(page_file(out, "/search?q=rust"), None);
+        assert_eq!(page_file(out, "/a/%2E%2E/b"), None);
+        assert_eq!(page_file(out, "/a%2Fb"), None);
+        assert_eq!(page_file(out, "relative"), None);
+    }
+
+    #[tokio::test]
+    async fn generate_static_site() {
+        let out_dir = std::env::temp_dir().join(format!("sycamore-ssg-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&out_dir);
+
+        let site = StaticSite::new(&out_dir, "https://example.com/")
+            .shell(|body| format!("<body>{body}</body>"));
+        let pages = site
+            .generate(
+                ["/", "/about", "/old", "/"].map(String::from),
+                |path| match path {
+                    "/old" => {
+                        use_ssr_response().unwrap().redirect("/about");
+                        View::default()
+                    }
+                    path => path.to_string().into(),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            pages
+                .iter()
+                .map(|page| page.path.as_str())
+                .collect::<Vec<_>>(),
+            ["/", "/about", "/old"]
+        );
+
+        let read = |path: &str| fs::read_to_string(out_dir.join(path)).unwrap();
+        assert_eq!(read("index.html"), "<body>/</body>");
+        assert_eq!(read("about/index.html"), "<body>/about</body>");
+        assert!(read("old/index.html").contains("url=/about"));
+        assert_eq!(
+            read("sitemap.xml"),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n  \
+             <url><loc>https://example.com/</loc></url>\n  \
+             <url><loc>https://example.com/about</loc></url>\n\
+             </urlset>\n"
+        );
+
+        let err = site
+            .generate(["/", "/missing"].map(String::from), |path| {
+                if path == "/missing" {
+                    use_ssr_response().unwrap().set_status(404);
+                }
+                View::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, StaticSiteError::Status(failed) if failed == &[("/missing".to_string(), 404)])
+        );
+        assert_eq!(
+            err.to_string(),
+            "failed to render 1 page(s): `/missing` (not found)"
+        );
+
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+}
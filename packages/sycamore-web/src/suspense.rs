@@ -8,6 +8,7 @@ use sycamore_futures::{
 };
 use sycamore_macro::{component, Props};
 
+use crate::utils::ThreadBound;
 use crate::*;
 
 /// Props for [`Suspense`] and [`Transition`].
@@ -96,7 +97,7 @@ pub fn Suspense(props: SuspenseProps) -> View {
                     debug_assert!(!suspense_scope.sent.get());
                     // Make sure parent is sent first.
                     create_effect(move || {
-                        if !suspense_scope.sent.get() && suspense_scope.parent.as_ref().map_or(true, |parent| parent.get().sent.get()) {
+                        if !suspense_scope.sent.get() && suspense_scope.parent.as_ref().is_none_or(|parent| parent.get().sent.get()) {
                             let view = std::mem::take(&mut view);
                             let fragment = SuspenseFragment::new(key, view! { Show(when=true) { (view) } });
                             let mut state = state.clone();
@@ -130,7 +131,7 @@ pub fn Suspense(props: SuspenseProps) -> View {
         }
     }
     is_not_ssr! {
-        let mode = if IS_HYDRATING.get() {
+        let mode = if is_hydrating() {
             use_context::<SsrMode>()
         } else {
             SsrMode::Sync
@@ -168,6 +169,7 @@ pub fn Suspense(props: SuspenseProps) -> View {
 
                 let (view, suspense_scope) = HydrationRegistry::in_suspense_scope(key, move || create_suspense_scope(move || children.call()));
                 let is_loading = suspense_scope.is_loading();
+                let is_hydrating = use_context::<IsHydrating>();
 
                 create_effect(move || {
                     set_is_loading(is_loading.get());
@@ -175,11 +177,11 @@ pub fn Suspense(props: SuspenseProps) -> View {
 
                 view! {
                     NoSsr {
-                        Show(when=move || !IS_HYDRATING.get() && is_loading.get()) {
+                        Show(when=move || !is_hydrating.get() && is_loading.get()) {
                             (fallback())
                         }
                     }
-                    Show(when=move || !IS_HYDRATING.get() && !is_loading.get()) {
+                    Show(when=move || !is_hydrating.get() && !is_loading.get()) {
                         (view)
                     }
                 }
@@ -193,7 +195,7 @@ pub fn Suspense(props: SuspenseProps) -> View {
 #[component]
 pub fn WrapAsync<F: Future<Output = View>>(f: impl FnOnce() -> F + 'static) -> View {
     is_not_ssr! {
-        let mode = if IS_HYDRATING.get() {
+        let mode = if is_hydrating() {
             use_context::<SsrMode>()
         } else {
             SsrMode::Sync
@@ -241,9 +243,6 @@ pub fn Transition(props: SuspenseProps) -> View {
     /// scope.
     #[component(inline_props)]
     fn TransitionInner(children: Children, set_is_loading: Box<dyn FnMut(bool)>) -> View {
-        // TODO: Workaround for https://github.com/sycamore-rs/sycamore/issues/718.
-        let mut set_is_loading = set_is_loading;
-
         // We create a detatched suspense scope here to not create a deadlock with the outer
         // suspense.
         let (children, scope) = create_detatched_suspense_scope(move || children.call());
@@ -251,10 +250,13 @@ pub fn Transition(props: SuspenseProps) -> View {
         // and future renders will be captured by the inner suspense scope.
         create_suspense_task(scope.until_finished());
 
-        let is_loading = scope.is_loading();
-        create_effect(move || {
-            set_is_loading(is_loading.get());
-        });
+        if is_not_ssr!() {
+            let is_loading = scope.is_loading();
+            let mut set_is_loading = ThreadBound::new(set_is_loading);
+            create_effect(move || {
+                set_is_loading(is_loading.get());
+            });
+        }
 
         view! {
             (children)
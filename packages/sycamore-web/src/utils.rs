@@ -87,3 +87,105 @@ pub fn clone_nodes_via_web_sys(view: &View) -> View {
         .collect();
     View::from_nodes(nodes)
 }
+
+/// A value that can only be used on the thread that created it.
+///
+/// With the `sync` feature of `sycamore-reactive`, everything that is stored in the reactive graph
+/// must be `Send`. Client-side state, such as JS closures, is not, but it is only ever used on the
+/// main thread of the browser, so it is wrapped in this instead. Using or dropping the value on
+/// another thread panics.
+pub struct ThreadBound<T> {
+    value: std::mem::ManuallyDrop<T>,
+    thread: std::thread::ThreadId,
+}
+
+// SAFETY: The value is only accessed on the thread that created it. This is checked every time the
+// value is accessed or dropped.
+unsafe impl<T> Send for ThreadBound<T> {}
+
+impl<T> ThreadBound<T> {
+    /// Binds the value to the current thread.
+    pub fn new(value: T) -> Self {
+        Self {
+            value: std::mem::ManuallyDrop::new(value),
+            thread: std::thread::current().id(),
+        }
+    }
+
+    fn is_owner(&self) -> bool {
+        self.thread == std::thread::current().id()
+    }
+
+    #[track_caller]
+    fn check(&self) {
+        assert!(
+            self.is_owner(),
+            "value used on a different thread than the one that created it"
+        );
+    }
+
+    /// Returns the value.
+    pub fn into_inner(self) -> T {
+        self.check();
+        let mut this = std::mem::ManuallyDrop::new(self);
+        // SAFETY: `this` is never dropped, so the value is not dropped twice.
+        unsafe { std::mem::ManuallyDrop::take(&mut this.value) }
+    }
+}
+
+impl<T> std::ops::Deref for ThreadBound<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.check();
+        &self.value
+    }
+}
+
+impl<T> std::ops::DerefMut for ThreadBound<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.check();
+        &mut self.value
+    }
+}
+
+impl<T: Clone> Clone for ThreadBound<T> {
+    fn clone(&self) -> Self {
+        Self::new(T::clone(self))
+    }
+}
+
+impl<T> Drop for ThreadBound<T> {
+    fn drop(&mut self) {
+        if self.is_owner() {
+            // SAFETY: The value is not used after this.
+            unsafe { std::mem::ManuallyDrop::drop(&mut self.value) }
+        } else if !std::thread::panicking() {
+            panic!("value dropped on a different thread than the one that created it");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[test]
+    fn thread_bound_on_same_thread() {
+        let value = ThreadBound::new(Rc::new(1));
+        assert_eq!(**value.clone(), 1);
+        assert_eq!(*value.into_inner(), 1);
+    }
+
+    #[test]
+    fn thread_bound_panics_on_other_thread() {
+        let value = ThreadBound::new(Rc::new(1));
+        let res = std::thread::spawn(move || {
+            let _ = **value;
+        })
+        .join();
+        assert!(res.is_err());
+    }
+}
@@ -92,6 +92,17 @@ impl<T> From<Option<View<T>>> for View<T> {
     }
 }
 
+/// Reports the error to the nearest error handler, e.g. an [`ErrorBoundary`], and renders nothing.
+/// This allows components to return a `Result`.
+impl<T, E: Into<Box<DynError>>> From<Result<View<T>, E>> for View<T> {
+    fn from(result: Result<View<T>, E>) -> Self {
+        result.unwrap_or_else(|error| {
+            report_error(error);
+            View::default()
+        })
+    }
+}
+
 macro_rules! impl_view_from {
     ($($ty:ty),*) => {
         $(
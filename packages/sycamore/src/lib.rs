@@ -10,12 +10,20 @@
 //! - `hydrate` - Enables hydration support in DOM nodes. By default, hydration is disabled to
 //!   reduce binary size.
 //!
+//! - `inspect` - Enables `inspect_graph` for taking a snapshot of the reactive graph when
+//!   debugging.
+//!
 //! - `serde` - Enables serializing and deserializing `Signal`s and other wrapper types using
 //!   `serde`.
 //!
 //! - `suspense` - Enables wrappers around `wasm-bindgen-futures` to make it easier to extend a
 //!   reactive scope into an `async` function.
 //!
+//! - `sync` - Makes reactive roots and signals `Send + Sync`, so that they can be moved between
+//!   threads. Every value that is stored in a signal must be `Send`. The futures and streams of
+//!   the server side rendering functions that wait for suspense are `Send` as well, so they can be
+//!   spawned with `tokio::spawn`.
+//!
 //! - `nightly` - Enables nightly-only features. This makes it slightly more ergonomic to use
 //!   signals.
 //!
@@ -69,9 +77,14 @@ pub mod futures {
 
 #[cfg(feature = "hydrate")]
 pub use sycamore_web::{hydrate, hydrate_in_scope, hydrate_to};
-pub use sycamore_web::{render, render_in_scope, render_to, render_to_string};
+pub use sycamore_web::{
+    render, render_in_scope, render_to, render_to_string, render_to_string_with_response,
+};
 #[cfg(feature = "suspense")]
-pub use sycamore_web::{render_to_string_await_suspense, render_to_string_stream};
+pub use sycamore_web::{
+    render_to_string_await_suspense, render_to_string_await_suspense_with_response,
+    render_to_string_stream, render_to_string_stream_with_response,
+};
 
 /// The Sycamore prelude.
 ///
@@ -92,8 +105,8 @@ pub mod prelude {
     #[cfg(feature = "web")]
     pub use sycamore_web::{
         console_dbg, console_log, create_node_ref, document, is_not_ssr, is_ssr, on_mount, window,
-        Attributes, Children, GlobalAttributes, GlobalProps, HtmlGlobalAttributes, Indexed, Keyed,
-        NodeRef, SvgGlobalAttributes, View,
+        Attributes, Children, ChildrenFn, GlobalAttributes, GlobalProps, HtmlGlobalAttributes,
+        Indexed, Keyed, NodeRef, SvgGlobalAttributes, View,
     };
 
     pub use crate::reactive::*;
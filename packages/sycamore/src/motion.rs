@@ -90,7 +90,7 @@ pub fn create_raf_loop(mut f: impl FnMut() -> bool + 'static) -> RafState {
 }
 
 /// Create a new [`Tweened`] signal.
-pub fn create_tweened_signal<T: Lerp + Clone>(
+pub fn create_tweened_signal<T: Lerp + Clone + MaybeSend>(
     initial: T,
     transition_duration: std::time::Duration,
     easing_fn: impl Fn(f32) -> f32 + 'static,
@@ -154,15 +154,20 @@ impl<T: Lerp + Clone> std::fmt::Debug for Tweened<T> {
     }
 }
 
+// The state that is only used for tweening is not stored when not on `wasm32` so that this can be
+// stored in a signal with the `sync` feature of `sycamore-reactive`.
 struct TweenedInner<T: Lerp + Clone + 'static> {
     value: Signal<T>,
     is_tweening: Signal<bool>,
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
     raf_state: Option<RafState>,
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
     transition_duration_ms: f32,
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
     easing_fn: Rc<dyn Fn(f32) -> f32>,
 }
 
-impl<T: Lerp + Clone> Tweened<T> {
+impl<T: Lerp + Clone + MaybeSend> Tweened<T> {
     /// Create a new tweened state with the given value.
     ///
     /// End users should use [`Scope::create_tweened_signal`] instead.
@@ -172,11 +177,16 @@ impl<T: Lerp + Clone> Tweened<T> {
         easing_fn: impl Fn(f32) -> f32 + 'static,
     ) -> Self {
         let value = create_signal(initial);
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        let _ = (transition_duration, easing_fn);
         Self(create_signal(TweenedInner {
             value,
             is_tweening: create_signal(false),
+            #[cfg(all(target_arch = "wasm32", feature = "web"))]
             raf_state: None,
+            #[cfg(all(target_arch = "wasm32", feature = "web"))]
             transition_duration_ms: transition_duration.as_millis() as f32,
+            #[cfg(all(target_arch = "wasm32", feature = "web"))]
             easing_fn: Rc::new(easing_fn),
         }))
     }
@@ -273,8 +283,11 @@ impl<T: Lerp + Clone + 'static> Clone for TweenedInner<T> {
         Self {
             value: self.value,
             is_tweening: self.is_tweening,
+            #[cfg(all(target_arch = "wasm32", feature = "web"))]
             raf_state: self.raf_state.clone(),
+            #[cfg(all(target_arch = "wasm32", feature = "web"))]
             transition_duration_ms: self.transition_duration_ms,
+            #[cfg(all(target_arch = "wasm32", feature = "web"))]
             easing_fn: Rc::clone(&self.easing_fn),
         }
     }
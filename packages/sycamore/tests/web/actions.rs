@@ -0,0 +1,79 @@
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use super::*;
+
+fn set_title(el: Element, title: MaybeDyn<Cow<'static, str>>) {
+    create_effect(move || el.set_attribute("title", &title.get_clone()).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn action_is_called_with_element() {
+    let _ = create_root(|| {
+        let node = view! {
+            div(use:set_title="Hello")
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+        assert_eq!(query("div").get_attribute("title").unwrap(), "Hello");
+    });
+}
+
+#[wasm_bindgen_test]
+fn action_tracks_dynamic_argument() {
+    let _ = create_root(|| {
+        let title = create_signal("Hello".to_string());
+        let node = view! {
+            div(use:set_title=title.get_clone())
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+        let div = query("div");
+        assert_eq!(div.get_attribute("title").unwrap(), "Hello");
+
+        title.set("World".to_string());
+        assert_eq!(div.get_attribute("title").unwrap(), "World");
+    });
+}
+
+#[wasm_bindgen_test]
+fn action_cleanup_runs_with_element() {
+    let cleaned_up = Arc::new(AtomicBool::new(false));
+    let root = create_root(|| {
+        let cleaned_up = cleaned_up.clone();
+        let mark = move |_: Element, _: MaybeDyn<()>| {
+            on_cleanup(move || cleaned_up.store(true, Ordering::Relaxed));
+        };
+        let node = view! {
+            div(use:mark)
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+    });
+    assert!(!cleaned_up.load(Ordering::Relaxed));
+
+    root.dispose();
+    assert!(cleaned_up.load(Ordering::Relaxed));
+}
+
+#[derive(Props)]
+struct ButtonProps {
+    #[prop(attributes(html, button))]
+    attributes: Attributes,
+}
+
+#[component]
+fn Button(props: ButtonProps) -> View {
+    view! {
+        button(..props.attributes)
+    }
+}
+
+#[wasm_bindgen_test]
+fn action_through_attributes() {
+    let _ = create_root(|| {
+        let node = view! {
+            Button(use:set_title="Hello")
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+        assert_eq!(query("button").get_attribute("title").unwrap(), "Hello");
+    });
+}
@@ -0,0 +1,203 @@
+use web_sys::{HtmlOptionElement, HtmlSelectElement};
+
+use super::*;
+
+fn change(el: &Element) {
+    el.dispatch_event(&Event::new("change").unwrap()).unwrap();
+}
+
+fn input(el: &Element) {
+    el.dispatch_event(&Event::new("input").unwrap()).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn bind_radio_group() {
+    let _ = create_root(|| {
+        let size = create_signal("medium");
+        let node = view! {
+            input(id="small", r#type="radio", bind:group=(size, "small"))
+            input(id="medium", r#type="radio", bind:group=(size, "medium"))
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+        let small: HtmlInputElement = query_into("#small");
+        let medium: HtmlInputElement = query_into("#medium");
+        assert!(!small.checked());
+        assert!(medium.checked());
+
+        size.set("small");
+        assert!(small.checked());
+        assert!(!medium.checked());
+
+        medium.set_checked(true);
+        change(&medium);
+        assert_eq!(size.get(), "medium");
+    });
+}
+
+#[wasm_bindgen_test]
+fn bind_checkbox_group() {
+    let _ = create_root(|| {
+        let toppings = create_signal(vec!["cheese"]);
+        let node = view! {
+            input(id="cheese", r#type="checkbox", bind:group=(toppings, "cheese"))
+            input(id="olives", r#type="checkbox", bind:group=(toppings, "olives"))
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+        let cheese: HtmlInputElement = query_into("#cheese");
+        let olives: HtmlInputElement = query_into("#olives");
+        assert!(cheese.checked());
+        assert!(!olives.checked());
+
+        olives.set_checked(true);
+        change(&olives);
+        assert_eq!(toppings.get_clone(), ["cheese", "olives"]);
+
+        cheese.set_checked(false);
+        change(&cheese);
+        assert_eq!(toppings.get_clone(), ["olives"]);
+
+        toppings.set(vec!["cheese"]);
+        assert!(cheese.checked());
+        assert!(!olives.checked());
+    });
+}
+
+#[wasm_bindgen_test]
+fn bind_select_multiple() {
+    let _ = create_root(|| {
+        let values = create_signal(vec!["b".to_string()]);
+        let node = view! {
+            select(multiple=true, bind:selectedValues=values) {
+                option(value="a") { "A" }
+                option(value="b") { "B" }
+                option(value="c") { "C" }
+            }
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+        let select = query("select").unchecked_into::<HtmlSelectElement>();
+        let option = |i| {
+            select
+                .options()
+                .item(i)
+                .unwrap()
+                .unchecked_into::<HtmlOptionElement>()
+        };
+        assert!(!option(0).selected());
+        assert!(option(1).selected());
+
+        option(2).set_selected(true);
+        change(&select);
+        assert_eq!(values.get_clone(), ["b", "c"]);
+
+        values.set(vec!["a".to_string()]);
+        assert!(option(0).selected());
+        assert!(!option(1).selected());
+        assert!(!option(2).selected());
+    });
+}
+
+#[wasm_bindgen_test]
+fn bind_files() {
+    let _ = create_root(|| {
+        let files = create_signal(None);
+        let node = view! {
+            input(r#type="file", bind:files=files)
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+        let input: HtmlInputElement = query_into("input");
+
+        change(&input);
+        assert!(files.with(Option::is_none));
+        assert_eq!(input.files().unwrap().length(), 0);
+    });
+}
+
+#[wasm_bindgen_test]
+fn bind_value_as_date() {
+    let _ = create_root(|| {
+        let date = create_signal(None);
+        let node = view! {
+            input(r#type="date", bind:valueAsDate=date)
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+        let el: HtmlInputElement = query_into("input");
+
+        el.set_value("2024-05-06");
+        input(&el);
+        let value = date.get_clone().unwrap();
+        assert_eq!(value.get_utc_full_year(), 2024);
+
+        date.set(None);
+        assert_eq!(el.value(), "");
+    });
+}
+
+#[wasm_bindgen_test]
+fn bind_text_content() {
+    let _ = create_root(|| {
+        let text = create_signal("Hello".to_string());
+        let node = view! {
+            div(contenteditable="true", bind:textContent=text)
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+        let div = query("div");
+        assert_eq!(div.text_content().unwrap(), "Hello");
+
+        div.set_text_content(Some("Hello World"));
+        input(&div);
+        assert_eq!(text.get_clone(), "Hello World");
+
+        text.set("Bye".to_string());
+        assert_eq!(div.text_content().unwrap(), "Bye");
+    });
+}
+
+#[wasm_bindgen_test]
+fn bind_inner_html() {
+    let _ = create_root(|| {
+        let html = create_signal("<b>Hello</b>".to_string());
+        let node = view! {
+            div(contenteditable="true", bind:innerHTML=html)
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+        let div = query("div");
+        assert_eq!(div.inner_html(), "<b>Hello</b>");
+
+        div.set_inner_html("<i>Hello</i>");
+        input(&div);
+        assert_eq!(html.get_clone(), "<i>Hello</i>");
+    });
+}
+
+#[derive(Props)]
+struct CheckboxProps {
+    #[prop(attributes(html, input))]
+    attributes: Attributes,
+}
+
+#[component]
+fn Checkbox(props: CheckboxProps) -> View {
+    view! {
+        input(r#type="checkbox", ..props.attributes)
+    }
+}
+
+#[wasm_bindgen_test]
+fn bind_through_attributes() {
+    let _ = create_root(|| {
+        let toppings = create_signal(Vec::new());
+        let node = view! {
+            Checkbox(bind:group=(toppings, "cheese"))
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+        let cheese: HtmlInputElement = query_into("input");
+        assert!(!cheese.checked());
+
+        cheese.set_checked(true);
+        change(&cheese);
+        assert_eq!(toppings.get_clone(), ["cheese"]);
+
+        toppings.set(Vec::new());
+        assert!(!cheese.checked());
+    });
+}
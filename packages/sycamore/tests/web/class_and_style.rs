@@ -0,0 +1,90 @@
+use super::*;
+
+fn style(el: &Element, name: &str) -> String {
+    el.unchecked_ref::<HtmlElement>()
+        .style()
+        .get_property_value(name)
+        .unwrap()
+}
+
+#[wasm_bindgen_test]
+fn class_directive_toggles_class() {
+    let _ = create_root(|| {
+        let active = create_signal(false);
+        let node = view! {
+            div(class:active=active.get(), class:is-open=true, class="item")
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+        let div = query("div");
+        assert_eq!(div.class_name(), "item is-open");
+
+        active.set(true);
+        assert_eq!(div.class_name(), "item is-open active");
+
+        active.set(false);
+        assert_eq!(div.class_name(), "item is-open");
+    });
+}
+
+#[wasm_bindgen_test]
+fn class_directive_keeps_other_classes() {
+    let _ = create_root(|| {
+        let active = create_signal(false);
+        let node = view! {
+            div(class:active=active.get())
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+        let div = query("div");
+        div.class_list().add_1("external").unwrap();
+
+        active.set(true);
+        assert_eq!(div.class_name(), "external active");
+    });
+}
+
+#[wasm_bindgen_test]
+fn class_directive_is_reapplied_after_dynamic_class() {
+    let _ = create_root(|| {
+        let class = create_signal("item");
+        let active = create_signal(false);
+        let node = view! {
+            div(class=class.get(), class:active=active.get(), class:is-open=true)
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+        let div = query("div");
+        assert_eq!(div.class_name(), "item is-open");
+
+        class.set("item selected");
+        assert_eq!(div.class_name(), "item selected is-open");
+
+        active.set(true);
+        class.set("item");
+        assert_eq!(div.class_name(), "item active is-open");
+
+        active.set(false);
+        class.set("item selected");
+        assert_eq!(div.class_name(), "item selected is-open");
+    });
+}
+
+#[wasm_bindgen_test]
+fn style_directive_sets_property() {
+    let _ = create_root(|| {
+        let color = create_signal(Some("red"));
+        let node = view! {
+            div(style:color=color.get(), style:background-color="blue", style="margin: 0px")
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+        let div = query("div");
+        assert_eq!(style(&div, "color"), "red");
+        assert_eq!(style(&div, "background-color"), "blue");
+        assert_eq!(style(&div, "margin"), "0px");
+
+        color.set(Some("green"));
+        assert_eq!(style(&div, "color"), "green");
+
+        color.set(None);
+        assert_eq!(style(&div, "color"), "");
+        assert_eq!(style(&div, "background-color"), "blue");
+    });
+}
@@ -0,0 +1,81 @@
+use sycamore::web::custom_event;
+use sycamore::web::events::CustomEvent;
+
+use super::*;
+
+custom_event! {
+    value_changed: String = "value-changed";
+    item_selected: Option<f64> = "item-selected";
+}
+
+#[wasm_bindgen_test]
+fn dispatch_with_typed_detail() {
+    let _ = create_root(|| {
+        let value = create_signal(String::new());
+        let node_ref = create_node_ref();
+        let node = view! {
+            custom-picker(ref=node_ref, on:value-changed=move |ev: CustomEvent<String>| {
+                value.set(ev.detail());
+            })
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+
+        assert!(node_ref.dispatch(value_changed, "abc".to_string()));
+        assert_eq!(value.get_clone(), "abc");
+    });
+}
+
+#[wasm_bindgen_test]
+fn custom_events_bubble() {
+    let _ = create_root(|| {
+        let selected = create_signal(Some(0.0));
+        let node_ref = create_node_ref();
+        let node = view! {
+            div(on:item-selected=move |ev: CustomEvent<Option<f64>>| selected.set(ev.detail())) {
+                custom-list(ref=node_ref)
+            }
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+
+        node_ref.dispatch(item_selected, None);
+        assert_eq!(selected.get(), None);
+        node_ref.dispatch(item_selected, Some(2.0));
+        assert_eq!(selected.get(), Some(2.0));
+    });
+}
+
+#[wasm_bindgen_test]
+fn dispatch_returns_false_if_canceled() {
+    let _ = create_root(|| {
+        let node_ref = create_node_ref();
+        let node = view! {
+            custom-picker(ref=node_ref, on:value-changed=|ev: CustomEvent<String>| {
+                ev.prevent_default();
+            })
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+
+        assert!(!node_ref.dispatch(value_changed, String::new()));
+    });
+}
+
+#[wasm_bindgen_test]
+fn detail_from_js_event() {
+    let _ = create_root(|| {
+        let detail = create_signal(None);
+        let node_ref = create_node_ref();
+        let node = view! {
+            custom-picker(ref=node_ref, on:value-changed=move |ev: CustomEvent<String>| {
+                detail.set(Some(ev.try_detail()));
+            })
+        };
+        sycamore::render_in_scope(|| node, &test_container());
+
+        // An event that is dispatched from JS with a detail of the wrong type.
+        let init = web_sys::CustomEventInit::new();
+        init.set_detail(&123.into());
+        let event = web_sys::CustomEvent::new_with_event_init_dict("value-changed", &init).unwrap();
+        node_ref.get().dispatch_event(&event).unwrap();
+        assert_eq!(detail.get_clone(), Some(None));
+    });
+}
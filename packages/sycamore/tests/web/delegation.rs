@@ -0,0 +1,143 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use sycamore::web::{delegate_events, Portal};
+use web_sys::MouseEvent;
+
+use super::*;
+
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+fn logger<E>(log: &Log, name: &'static str) -> impl Fn(E) + 'static {
+    let log = log.clone();
+    move |_| log.borrow_mut().push(name)
+}
+
+#[wasm_bindgen_test]
+fn delegated_handlers_bubble() {
+    let log = Log::default();
+    let _ = create_root(|| {
+        let view = {
+            let log = log.clone();
+            move || {
+                delegate_events();
+                view! {
+                    div(on:click=logger(&log, "outer")) {
+                        button(id="inner", on:click=logger(&log, "inner")) { "Click" }
+                    }
+                }
+            }
+        };
+        sycamore::render_in_scope(view, &test_container());
+    });
+
+    query_into::<HtmlElement>("#inner").click();
+    assert_eq!(*log.borrow(), ["inner", "outer"]);
+}
+
+#[wasm_bindgen_test]
+fn stop_propagation() {
+    let log = Log::default();
+    let _ = create_root(|| {
+        let view = {
+            let log = log.clone();
+            move || {
+                delegate_events();
+                let inner_log = log.clone();
+                view! {
+                    div(on:click=logger(&log, "outer")) {
+                        button(id="inner", on:click=move |ev: MouseEvent| {
+                            inner_log.borrow_mut().push("inner");
+                            ev.stop_propagation();
+                        }) { "Click" }
+                    }
+                }
+            }
+        };
+        sycamore::render_in_scope(view, &test_container());
+    });
+
+    query_into::<HtmlElement>("#inner").click();
+    assert_eq!(*log.borrow(), ["inner"]);
+}
+
+#[wasm_bindgen_test]
+fn current_target_is_element() {
+    let current_target = Rc::new(RefCell::new(None));
+    let _ = create_root(|| {
+        let view = {
+            let current_target = current_target.clone();
+            move || {
+                delegate_events();
+                view! {
+                    div(id="outer", on:click=move |ev: MouseEvent| {
+                        *current_target.borrow_mut() = ev.current_target();
+                    }) {
+                        button(id="inner") { "Click" }
+                    }
+                }
+            }
+        };
+        sycamore::render_in_scope(view, &test_container());
+    });
+
+    query_into::<HtmlElement>("#inner").click();
+    let current_target = current_target.borrow_mut().take().unwrap();
+    assert_eq!(current_target.unchecked_into::<Element>().id(), "outer");
+}
+
+#[wasm_bindgen_test]
+fn events_bubble_out_of_portal() {
+    let test_container = test_container();
+    let portal_target = document().create_element("div").unwrap();
+    portal_target.set_id("portal-target");
+    test_container.append_child(&portal_target).unwrap();
+    let root = document().create_element("div").unwrap();
+    test_container.append_child(&root).unwrap();
+
+    let log = Log::default();
+    let _ = create_root(|| {
+        let view = {
+            let log = log.clone();
+            move || {
+                delegate_events();
+                let inner = logger(&log, "inner");
+                view! {
+                    div(on:click=logger(&log, "outer")) {
+                        Portal(selector="#portal-target") {
+                            button(id="inner", on:click=inner) { "Click" }
+                        }
+                    }
+                }
+            }
+        };
+        sycamore::render_in_scope(view, &root);
+    });
+
+    query_into::<HtmlElement>("#portal-target #inner").click();
+    assert_eq!(*log.borrow(), ["inner", "outer"]);
+}
+
+#[wasm_bindgen_test]
+fn handlers_are_removed_on_cleanup() {
+    let log = Log::default();
+    let root = create_root(|| {
+        let view = {
+            let log = log.clone();
+            move || {
+                delegate_events();
+                view! {
+                    button(id="inner", on:click=logger(&log, "inner")) { "Click" }
+                }
+            }
+        };
+        sycamore::render_in_scope(view, &test_container());
+    });
+    let button = query_into::<HtmlElement>("#inner");
+    button.click();
+    assert_eq!(*log.borrow(), ["inner"]);
+
+    root.dispose();
+    button.click();
+    assert_eq!(*log.borrow(), ["inner"]);
+}
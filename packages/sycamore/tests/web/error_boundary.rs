@@ -0,0 +1,88 @@
+use sycamore::web::{ErrorBoundary, ResetErrorBoundary};
+
+use super::*;
+
+#[wasm_bindgen_test]
+fn error_boundary_reset() {
+    let root = test_container();
+
+    let _ = create_root(|| {
+        let fail = create_signal(false);
+        let reset = create_signal(None::<ResetErrorBoundary>);
+
+        #[component(inline_props)]
+        fn Child(fail: Signal<bool>) -> View {
+            view! {
+                (if fail.get() {
+                    report_error("failed");
+                    view! {}
+                } else {
+                    view! { "ok" }
+                })
+            }
+        }
+
+        sycamore::render_in_scope(
+            move || {
+                view! {
+                    ErrorBoundary(fallback=move |error, r| {
+                        reset.set(Some(r));
+                        view! { "error: " (error.to_string()) }
+                    }) {
+                        Child(fail=fail)
+                    }
+                }
+            },
+            &root,
+        );
+        assert_text_content!(root, "ok");
+
+        fail.set(true);
+        assert_text_content!(root, "error: failed");
+
+        fail.set(false);
+        reset.get().unwrap().reset();
+        assert_text_content!(root, "ok");
+    });
+}
+
+#[wasm_bindgen_test]
+fn error_boundary_reset_creates_children_again() {
+    let root = test_container();
+
+    let _ = create_root(|| {
+        let fail = create_signal(true);
+        let created = create_signal(0);
+        let reset = create_signal(None::<ResetErrorBoundary>);
+
+        #[component(inline_props)]
+        fn Child(fail: Signal<bool>, created: Signal<i32>) -> Result<View, String> {
+            created.set(created.get_untracked() + 1);
+            if fail.get_untracked() {
+                Err("failed".to_string())
+            } else {
+                Ok(view! { "ok" })
+            }
+        }
+
+        sycamore::render_in_scope(
+            move || {
+                view! {
+                    ErrorBoundary(fallback=move |error, r| {
+                        reset.set(Some(r));
+                        view! { "error: " (error.to_string()) }
+                    }) {
+                        Child(fail=fail, created=created)
+                    }
+                }
+            },
+            &root,
+        );
+        assert_text_content!(root, "error: failed");
+
+        fail.set(false);
+        reset.get().unwrap().reset();
+        assert_text_content!(root, "ok");
+        assert_eq!(created.get(), 2);
+    });
+}
@@ -195,3 +195,142 @@ mod top_level_dynamic_with_siblings {
         });
     }
 }
+
+mod static_class_toggles {
+    use super::*;
+    fn v() -> View {
+        view! {
+            p(class="item active", class:active=false, class:is-open=true) { "Item" }
+        }
+    }
+    static EXPECT: Expect = expect![[r#"<p class="item is-open" data-hk="0.0">Item</p>"#]];
+    #[test]
+    fn ssr() {
+        check(v, &EXPECT);
+    }
+    #[wasm_bindgen_test]
+    fn test() {
+        let c = test_container();
+        c.set_inner_html(EXPECT.data());
+
+        sycamore::hydrate_to(v, &c);
+
+        assert_eq!(query("p").class_name(), "item is-open");
+    }
+    #[wasm_bindgen_test]
+    fn csr() {
+        let _ = create_root(|| {
+            sycamore::render_in_scope(v, &test_container());
+
+            // The client renders the same classes as the server.
+            assert_eq!(query("p").class_name(), "item is-open");
+        });
+    }
+}
+
+mod class_and_style_directives {
+    use super::*;
+    fn v(active: ReadSignal<bool>) -> View {
+        view! {
+            p(class="item", style="margin: 0", class:active=active.get(), style:color="red") {
+                "Item"
+            }
+        }
+    }
+    static EXPECT: Expect =
+        expect![[r#"<p class="item" style="margin: 0; color: red;" data-hk="0.0">Item</p>"#]];
+    #[test]
+    fn ssr() {
+        check(|| v(*create_signal(false)), &EXPECT);
+    }
+    #[wasm_bindgen_test]
+    fn test() {
+        let c = test_container();
+        c.set_inner_html(EXPECT.data());
+
+        let _ = create_root(|| {
+            let active = create_signal(false);
+
+            sycamore::hydrate_in_scope(|| v(*active), &c);
+
+            // The directives should keep the classes and styles of the SSR-ed node.
+            let p = query("p");
+            assert_eq!(p.get_attribute("class").as_deref(), Some("item"));
+
+            active.set(true);
+            assert_eq!(p.get_attribute("class").as_deref(), Some("item active"));
+            assert_eq!(
+                p.unchecked_ref::<HtmlElement>()
+                    .style()
+                    .get_property_value("color")
+                    .unwrap(),
+                "red"
+            );
+        });
+    }
+}
+
+mod error_boundary_reset {
+    use sycamore::web::{ErrorBoundary, ResetErrorBoundary};
+
+    use super::*;
+
+    #[component(inline_props)]
+    fn Child(fail: Signal<bool>, created: Signal<i32>) -> View {
+        created.set(created.get_untracked() + 1);
+        create_effect(move || {
+            if fail.get() {
+                report_error("failed");
+            }
+        });
+        view! { p { "ok" } }
+    }
+
+    fn v(
+        fail: Signal<bool>,
+        created: Signal<i32>,
+        reset: Signal<Option<ResetErrorBoundary>>,
+    ) -> View {
+        view! {
+            ErrorBoundary(fallback=move |error, r| {
+                reset.set(Some(r));
+                view! { "error: " (error.to_string()) }
+            }) {
+                Child(fail=fail, created=created)
+            }
+        }
+    }
+    static EXPECT: Expect =
+        expect![[r#"<!--/--><!--/--><p data-hk="0.0">ok</p><!--/--><!--/--><!--/--><!--/-->"#]];
+    #[test]
+    fn ssr() {
+        check(
+            || v(create_signal(false), create_signal(0), create_signal(None)),
+            &EXPECT,
+        );
+    }
+    #[wasm_bindgen_test]
+    fn test() {
+        let c = test_container();
+        c.set_inner_html(EXPECT.data());
+
+        let _ = create_root(|| {
+            let fail = create_signal(false);
+            let created = create_signal(0);
+            let reset = create_signal(None);
+
+            sycamore::hydrate_in_scope(|| v(fail, created, reset), &c);
+            assert_text_content!(c, "ok");
+            assert_eq!(query("p").get_attribute("data-hk").as_deref(), Some("0.0"));
+
+            fail.set(true);
+            assert_text_content!(c, "error: failed");
+
+            // Resetting the boundary creates the children again.
+            fail.set(false);
+            reset.get().unwrap().reset();
+            assert_text_content!(c, "ok");
+            assert_eq!(created.get(), 2);
+        });
+    }
+}
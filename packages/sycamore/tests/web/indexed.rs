@@ -419,3 +419,36 @@ fn template_with_other_nodes_at_same_level() {
         assert_text_content!(elem, "before145after");
     });
 }
+
+#[wasm_bindgen_test]
+fn signal_vec() {
+    let _ = create_root(|| {
+        let list = create_signal_vec(vec![1, 2]);
+
+        let view = move || {
+            view! {
+                ul {
+                    Indexed(
+                        list=list,
+                        view=|item| view! {
+                            li { (item) }
+                        },
+                    )
+                }
+            }
+        };
+
+        sycamore::render_in_scope(view, &test_container());
+
+        let p = query("ul");
+        assert_text_content!(p, "12");
+
+        list.push(3);
+        list.swap(0, 2);
+        assert_text_content!(p, "321");
+
+        list.set(1, 5);
+        list.pop();
+        assert_text_content!(p, "35");
+    });
+}
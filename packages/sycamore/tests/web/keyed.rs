@@ -465,3 +465,81 @@ fn template_with_other_nodes_at_same_level() {
         assert_text_content!(elem, "before145after");
     });
 }
+
+#[wasm_bindgen_test]
+fn signal_vec() {
+    let _ = create_root(|| {
+        let list = create_signal_vec(vec![1, 2, 3]);
+
+        let view = move || {
+            view! {
+                ul {
+                    Keyed(
+                        list=list,
+                        view=|item| view! {
+                            li { (item) }
+                        },
+                        key=|item| *item,
+                    )
+                }
+            }
+        };
+
+        sycamore::render_in_scope(view, &test_container());
+
+        let p = query("ul");
+        assert_text_content!(p, "123");
+
+        list.push(4);
+        assert_text_content!(p, "1234");
+
+        list.insert(0, 0);
+        assert_text_content!(p, "01234");
+
+        list.swap(1, 3);
+        assert_text_content!(p, "03214");
+
+        list.remove(2);
+        assert_text_content!(p, "0314");
+
+        list.set(0, 5);
+        assert_text_content!(p, "5314");
+
+        list.replace(vec![4, 3, 6]);
+        assert_text_content!(p, "436");
+
+        list.clear();
+        assert_text_content!(p, "");
+    });
+}
+
+#[wasm_bindgen_test]
+fn signal_vec_changed_before_mount() {
+    let _ = create_root(|| {
+        let list = create_signal_vec(vec![1, 2, 3]);
+
+        let node = view! {
+            Keyed(
+                list=list,
+                view=|item| view! {
+                    li { (item) }
+                },
+                key=|item| *item,
+            )
+        };
+        // The nodes are not mounted yet.
+        list.push(4);
+
+        sycamore::render_in_scope(|| node, &test_container());
+        let p = query("test-container");
+
+        list.remove(0);
+        assert_text_content!(p, "234");
+
+        list.pop();
+        assert_text_content!(p, "23");
+
+        list.push(5);
+        assert_text_content!(p, "235");
+    });
+}
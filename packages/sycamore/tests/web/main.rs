@@ -1,4 +1,10 @@
+pub mod actions;
+pub mod bind;
+pub mod class_and_style;
 pub mod cleanup;
+pub mod custom_events;
+pub mod delegation;
+pub mod error_boundary;
 pub mod hydrate;
 pub mod indexed;
 pub mod keyed;